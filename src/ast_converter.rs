@@ -857,8 +857,18 @@ impl<'a> AstConverter<'a> {
                         .names()
                         .iter()
                         .zip(statement.type_specifiers())
-                        .map(|(token_ref, type_specifier)| {
-                            self.convert_typed_identifier(token_ref, type_specifier)
+                        .zip(
+                            statement
+                                .attributes()
+                                .chain(std::iter::repeat_with(|| None)),
+                        )
+                        .map(|((token_ref, type_specifier), attribute)| {
+                            let mut variable =
+                                self.convert_typed_identifier(token_ref, type_specifier)?;
+                            if let Some(attribute) = attribute {
+                                self.convert_attribute(attribute, &mut variable)?;
+                            }
+                            Ok(variable)
                         })
                         .collect::<Result<Vec<_>, _>>()?;
 
@@ -1362,6 +1372,11 @@ impl<'a> AstConverter<'a> {
 
         let mut block = self.blocks.pop().expect("root block should be converted");
 
+        if let Some((shebang, new_line)) = find_shebang(ast) {
+            block.set_shebang(shebang);
+            block.set_shebang_new_line(new_line);
+        }
+
         if self.hold_token_data {
             if let Some(tokens) = block.mutate_tokens() {
                 let token = self.convert_token(ast.eof())?;
@@ -1568,6 +1583,27 @@ impl<'a> AstConverter<'a> {
             ast::Stmt::TypeDeclaration(type_declaration) => {
                 self.convert_type_declaration(type_declaration, None);
             }
+            ast::Stmt::Goto(goto) => {
+                let mut goto_statement =
+                    GotoStatement::new(self.convert_token_to_identifier(goto.label_name())?);
+                if self.hold_token_data {
+                    goto_statement.set_tokens(GotoTokens {
+                        goto: self.convert_token(goto.goto_token())?,
+                    });
+                }
+                self.statements.push(goto_statement.into());
+            }
+            ast::Stmt::Label(label) => {
+                let mut label_statement =
+                    LabelStatement::new(self.convert_token_to_identifier(label.name())?);
+                if self.hold_token_data {
+                    label_statement.set_tokens(LabelTokens {
+                        left_colons: self.convert_token(label.left_colons())?,
+                        right_colons: self.convert_token(label.right_colons())?,
+                    });
+                }
+                self.statements.push(label_statement.into());
+            }
             _ => {
                 return Err(ConvertError::Statement {
                     statement: statement.to_string(),
@@ -2352,7 +2388,31 @@ impl<'a> AstConverter<'a> {
         let position = self.convert_token_position(token)?;
         let mut new_token = Token::new_with_line(position.0, position.1, position.2);
 
-        for trivia_token in token.leading_trivia() {
+        let mut leading_trivia = token.leading_trivia().peekable();
+
+        // the shebang is stored on the root block and generators write the new line
+        // that ends it, so it is removed along with that new line
+        if leading_trivia
+            .next_if(|trivia| matches!(trivia.token_type(), TokenType::Shebang { .. }))
+            .is_some()
+        {
+            if let Some(whitespace) = leading_trivia
+                .next_if(|trivia| trivia.token_kind() == tokenizer::TokenKind::Whitespace)
+            {
+                let content = whitespace.to_string();
+                let new_line_length = starting_new_line(&content).len();
+
+                if content.len() > new_line_length {
+                    new_token.push_leading_trivia(TriviaKind::Whitespace.at(
+                        whitespace.start_position().bytes() + new_line_length,
+                        whitespace.end_position().bytes(),
+                        whitespace.start_position().line() + new_line_length.min(1),
+                    ));
+                }
+            }
+        }
+
+        for trivia_token in leading_trivia {
             new_token.push_leading_trivia(self.convert_trivia(trivia_token)?);
         }
 
@@ -2412,6 +2472,31 @@ impl<'a> AstConverter<'a> {
         })
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn convert_attribute(
+        &self,
+        attribute: &ast::lua54::Attribute,
+        typed_identifier: &mut TypedIdentifier,
+    ) -> Result<(), ConvertError> {
+        let name = attribute.name().token().to_string();
+        typed_identifier.set_attribute(Attribute::from_name(&name).ok_or_else(|| {
+            ConvertError::Attribute {
+                attribute: format!("<{}>", name),
+            }
+        })?);
+
+        if self.hold_token_data {
+            let (opening_chevron, closing_chevron) = attribute.brackets().tokens();
+            typed_identifier.set_attribute_tokens(AttributeTokens {
+                opening_chevron: self.convert_token(opening_chevron)?,
+                name: self.convert_token(attribute.name())?,
+                closing_chevron: self.convert_token(closing_chevron)?,
+            });
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn extract_tokens_from_punctuation<T>(
         &self,
@@ -2717,6 +2802,42 @@ impl<'a> AstConverter<'a> {
     }
 }
 
+/// Finds the shebang line of the code with the new line that ends it. The shebang
+/// token from full-moon ends before the `\n` character, so it contains the `\r`
+/// character of a CRLF line ending.
+fn find_shebang(ast: &ast::Ast) -> Option<(String, String)> {
+    let first_token = ast.nodes().tokens().next().unwrap_or_else(|| ast.eof());
+    let mut leading_trivia = first_token.leading_trivia();
+
+    let line = leading_trivia.find_map(|trivia| match trivia.token_type() {
+        TokenType::Shebang { line } => Some(line.to_string()),
+        _ => None,
+    })?;
+
+    let (line, carriage_return) = match line.strip_suffix('\r') {
+        Some(line) => (line.to_owned(), "\r"),
+        None => (line, ""),
+    };
+
+    let new_line = leading_trivia
+        .next()
+        .filter(|trivia| trivia.token_kind() == tokenizer::TokenKind::Whitespace)
+        .map(|whitespace| starting_new_line(&whitespace.to_string()))
+        .unwrap_or_default();
+
+    Some((line, format!("{}{}", carriage_return, new_line)))
+}
+
+fn starting_new_line(content: &str) -> &'static str {
+    if content.starts_with("\r\n") {
+        "\r\n"
+    } else if content.starts_with('\n') {
+        "\n"
+    } else {
+        ""
+    }
+}
+
 fn is_argument_variadic(mut r#type: &ast::luau::TypeInfo) -> bool {
     use ast::luau::TypeInfo;
     loop {
@@ -3038,6 +3159,9 @@ pub(crate) enum ConvertError {
     GenericDeclaration {
         generics: String,
     },
+    Attribute {
+        attribute: String,
+    },
     UnexpectedTrivia(tokenizer::TokenKind),
     ExpectedFunctionName,
     TokenPositionNotFound {
@@ -3081,6 +3205,7 @@ impl fmt::Display for ConvertError {
             ConvertError::TypeInfo { type_info } => ("type", type_info),
             ConvertError::TableTypeProperty { property } => ("table type property", property),
             ConvertError::GenericDeclaration { generics } => ("generics", generics),
+            ConvertError::Attribute { attribute } => ("attribute", attribute),
             ConvertError::UnexpectedTrivia(token_kind) => {
                 return write!(
                     f,
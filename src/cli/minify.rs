@@ -3,7 +3,7 @@ use crate::cli::utils::report_process;
 use crate::cli::{CommandResult, GlobalOptions};
 
 use clap::Args;
-use darklua_core::{Configuration, GeneratorParameters, Resources};
+use darklua_core::{generator::GeneratorBuilder, Configuration, Resources};
 use std::path::PathBuf;
 use std::time::Instant;
 
@@ -24,14 +24,13 @@ pub fn run(options: &Options, _global: &GlobalOptions) -> CommandResult {
     let resources = Resources::from_file_system();
     let process_options = darklua_core::Options::new(&options.input_path)
         .with_output(&options.output_path)
-        .with_configuration(
-            Configuration::empty().with_generator(
-                options
-                    .column_span
-                    .map(|column_span| GeneratorParameters::Dense { column_span })
-                    .unwrap_or_else(GeneratorParameters::default_dense),
-            ),
-        );
+        .with_configuration(Configuration::empty().with_generator({
+            let mut generator = GeneratorBuilder::dense();
+            if let Some(column_span) = options.column_span {
+                generator = generator.max_width(column_span);
+            }
+            generator
+        }));
 
     let process_start_time = Instant::now();
 
@@ -7,21 +7,39 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    generator::{DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, TokenBasedLuaGenerator},
-    nodes::Block,
+    generator::{
+        DenseLuaGenerator, GeneratorBuilder, Indentation, LongStringThreshold, LuaGenerator,
+        QuoteStyle, ReadableLuaGenerator, SemicolonStyle, TokenBasedLuaGenerator, UnicodeEscape,
+        DEFAULT_COLUMN_SPAN,
+    },
+    nodes::{Block, Identifier},
     rules::{
         bundle::{BundleRequireMode, Bundler},
         get_default_rules, Rule,
     },
-    Parser,
+    LuaDialect, Parser,
 };
 
-const DEFAULT_COLUMN_SPAN: usize = 80;
-
 fn get_default_column_span() -> usize {
     DEFAULT_COLUMN_SPAN
 }
 
+fn get_default_include_types() -> bool {
+    true
+}
+
+fn get_default_final_newline() -> bool {
+    true
+}
+
+/// The line endings used in generated files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEndings {
+    Lf,
+    Crlf,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Configuration {
@@ -29,6 +47,12 @@ pub struct Configuration {
     rules: Vec<Box<dyn Rule>>,
     #[serde(default, deserialize_with = "crate::utils::string_or_struct")]
     generator: GeneratorParameters,
+    #[serde(default = "get_default_final_newline")]
+    final_newline: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    line_endings: Option<LineEndings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dialect: Option<LuaDialect>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     bundle: Option<BundleConfiguration>,
     #[serde(default, skip)]
@@ -42,20 +66,46 @@ impl Configuration {
         Self {
             rules: Vec::new(),
             generator: GeneratorParameters::default(),
+            final_newline: get_default_final_newline(),
+            line_endings: None,
+            dialect: None,
             bundle: None,
             location: None,
         }
     }
 
     #[inline]
-    pub fn with_generator(mut self, generator: GeneratorParameters) -> Self {
-        self.generator = generator;
+    pub fn with_generator(mut self, generator: impl Into<GeneratorParameters>) -> Self {
+        self.generator = generator.into();
         self
     }
 
     #[inline]
-    pub fn set_generator(&mut self, generator: GeneratorParameters) {
-        self.generator = generator;
+    pub fn set_generator(&mut self, generator: impl Into<GeneratorParameters>) {
+        self.generator = generator.into();
+    }
+
+    /// Sets if generated files should end with a new line. This is enabled by default.
+    #[inline]
+    pub fn with_final_newline(mut self, final_newline: bool) -> Self {
+        self.final_newline = final_newline;
+        self
+    }
+
+    /// Sets the line endings used in generated files. When not set, new lines are written
+    /// as produced by the generator.
+    #[inline]
+    pub fn with_line_endings(mut self, line_endings: LineEndings) -> Self {
+        self.line_endings = Some(line_endings);
+        self
+    }
+
+    /// Sets the Lua dialect used to parse input files. When not set, files are parsed
+    /// as Luau.
+    #[inline]
+    pub fn with_dialect(mut self, dialect: LuaDialect) -> Self {
+        self.dialect = Some(dialect);
+        self
     }
 
     #[inline]
@@ -88,12 +138,33 @@ impl Configuration {
 
     #[inline]
     pub(crate) fn build_parser(&self) -> Parser {
-        self.generator.build_parser()
+        let parser = self.generator.build_parser();
+        match self.dialect {
+            Some(dialect) => parser.with_dialect(dialect),
+            None => parser,
+        }
     }
 
-    #[inline]
     pub(crate) fn generate_lua(&self, block: &Block, code: &str) -> String {
-        self.generator.generate_lua(block, code)
+        let mut lua_code = self.generator.generate_lua(block, code);
+
+        if self.final_newline && !lua_code.is_empty() && !lua_code.ends_with('\n') {
+            // without any configured line endings, the final new line matches the line
+            // endings of the generated code (or of the original code when the generated
+            // code is on a single line)
+            let uses_crlf = if lua_code.contains('\n') {
+                uses_crlf(&lua_code)
+            } else {
+                uses_crlf(code)
+            };
+            lua_code.push_str(if uses_crlf { "\r\n" } else { "\n" });
+        }
+
+        match self.line_endings {
+            Some(LineEndings::Lf) => lua_code.replace("\r\n", "\n"),
+            Some(LineEndings::Crlf) => lua_code.replace("\r\n", "\n").replace('\n', "\r\n"),
+            None => lua_code,
+        }
     }
 
     pub(crate) fn bundle(&self) -> Option<Bundler> {
@@ -121,11 +192,20 @@ impl Configuration {
     }
 }
 
+fn uses_crlf(code: &str) -> bool {
+    code.find('\n')
+        .map(|index| code[..index].ends_with('\r'))
+        .unwrap_or_default()
+}
+
 impl Default for Configuration {
     fn default() -> Self {
         Self {
             rules: get_default_rules(),
             generator: Default::default(),
+            final_newline: get_default_final_newline(),
+            line_endings: None,
+            dialect: None,
             bundle: None,
             location: None,
         }
@@ -136,6 +216,9 @@ impl std::fmt::Debug for Configuration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Config")
             .field("generator", &self.generator)
+            .field("final_newline", &self.final_newline)
+            .field("line_endings", &self.line_endings)
+            .field("dialect", &self.dialect)
             .field(
                 "rules",
                 &self
@@ -158,13 +241,43 @@ impl std::fmt::Debug for Configuration {
 pub enum GeneratorParameters {
     #[serde(alias = "retain-lines")]
     RetainLines,
+    #[non_exhaustive]
     Dense {
         #[serde(default = "get_default_column_span")]
         column_span: usize,
+        #[serde(default)]
+        quote_style: QuoteStyle,
+        #[serde(default)]
+        long_string_threshold: LongStringThreshold,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefer_hex_above: Option<u64>,
+        #[serde(default)]
+        unicode_escape: UnicodeEscape,
+        #[serde(default)]
+        semicolons: SemicolonStyle,
+        #[serde(default = "get_default_include_types")]
+        include_types: bool,
+        #[serde(default)]
+        remove_redundant_parentheses: bool,
     },
+    #[non_exhaustive]
     Readable {
         #[serde(default = "get_default_column_span")]
         column_span: usize,
+        #[serde(default)]
+        indentation: Indentation,
+        #[serde(default)]
+        quote_style: QuoteStyle,
+        #[serde(default)]
+        long_string_threshold: LongStringThreshold,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefer_hex_above: Option<u64>,
+        #[serde(default)]
+        unicode_escape: UnicodeEscape,
+        #[serde(default)]
+        keep_comments: bool,
+        #[serde(default = "get_default_include_types")]
+        include_types: bool,
     },
 }
 
@@ -176,40 +289,83 @@ impl Default for GeneratorParameters {
 
 impl GeneratorParameters {
     pub fn default_dense() -> Self {
-        Self::Dense {
-            column_span: DEFAULT_COLUMN_SPAN,
-        }
+        GeneratorBuilder::dense().build()
     }
 
     pub fn default_readable() -> Self {
-        Self::Readable {
-            column_span: DEFAULT_COLUMN_SPAN,
-        }
+        GeneratorBuilder::readable().build()
     }
 
-    fn generate_lua(&self, block: &Block, code: &str) -> String {
+    /// Generates the code of a block with the generator described by these parameters.
+    /// The block should be parsed with the parser returned by
+    /// [`build_parser`](Self::build_parser) and `code` is the code it was parsed from.
+    pub fn generate_lua(&self, block: &Block, code: &str) -> String {
         match self {
             Self::RetainLines => {
                 let mut generator = TokenBasedLuaGenerator::new(code);
                 generator.write_block(block);
                 generator.into_string()
             }
-            Self::Dense { column_span } => {
-                let mut generator = DenseLuaGenerator::new(*column_span);
+            Self::Dense {
+                column_span,
+                quote_style,
+                long_string_threshold,
+                prefer_hex_above,
+                unicode_escape,
+                semicolons,
+                include_types,
+                remove_redundant_parentheses,
+            } => {
+                let mut generator = DenseLuaGenerator::new(*column_span)
+                    .with_quote_style(*quote_style)
+                    .with_long_string_threshold(*long_string_threshold)
+                    .with_unicode_escape(*unicode_escape)
+                    .with_semicolon_style(*semicolons)
+                    .with_include_types(*include_types)
+                    .with_remove_redundant_parentheses(*remove_redundant_parentheses);
+                if let Some(value) = prefer_hex_above {
+                    generator = generator.with_prefer_hex_above(*value);
+                }
                 generator.write_block(block);
                 generator.into_string()
             }
-            Self::Readable { column_span } => {
-                let mut generator = ReadableLuaGenerator::new(*column_span);
+            Self::Readable {
+                column_span,
+                indentation,
+                quote_style,
+                long_string_threshold,
+                prefer_hex_above,
+                unicode_escape,
+                keep_comments,
+                include_types,
+            } => {
+                let mut generator = ReadableLuaGenerator::new(*column_span)
+                    .with_indentation(*indentation)
+                    .with_quote_style(*quote_style)
+                    .with_long_string_threshold(*long_string_threshold)
+                    .with_unicode_escape(*unicode_escape)
+                    .with_include_types(*include_types);
+                if let Some(value) = prefer_hex_above {
+                    generator = generator.with_prefer_hex_above(*value);
+                }
+                if *keep_comments {
+                    generator = generator.with_comments(code);
+                }
                 generator.write_block(block);
                 generator.into_string()
             }
         }
     }
 
-    fn build_parser(&self) -> Parser {
+    /// Creates a parser that keeps the information needed by the generator (like tokens
+    /// for the retain_lines generator).
+    pub fn build_parser(&self) -> Parser {
         match self {
-            Self::RetainLines => Parser::default().preserve_tokens(),
+            Self::RetainLines
+            | Self::Readable {
+                keep_comments: true,
+                ..
+            } => Parser::default().preserve_tokens(),
             Self::Dense { .. } | Self::Readable { .. } => Parser::default(),
         }
     }
@@ -222,12 +378,8 @@ impl FromStr for GeneratorParameters {
         Ok(match s {
             // keep "retain-lines" for back-compatibility
             "retain_lines" | "retain-lines" => Self::RetainLines,
-            "dense" => Self::Dense {
-                column_span: DEFAULT_COLUMN_SPAN,
-            },
-            "readable" => Self::Readable {
-                column_span: DEFAULT_COLUMN_SPAN,
-            },
+            "dense" => Self::default_dense(),
+            "readable" => Self::default_readable(),
             _ => return Err(format!("invalid generator name `{}`", s)),
         })
     }
@@ -238,12 +390,29 @@ impl FromStr for GeneratorParameters {
 pub struct BundleConfiguration {
     #[serde(deserialize_with = "crate::utils::string_or_struct")]
     require_mode: BundleRequireMode,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_modules_identifier"
+    )]
     modules_identifier: Option<String>,
     #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     excludes: HashSet<String>,
 }
 
+fn deserialize_modules_identifier<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let identifier = Option::<String>::deserialize(deserializer)?;
+
+    if let Some(identifier) = &identifier {
+        Identifier::try_new(identifier).map_err(serde::de::Error::custom)?;
+    }
+
+    Ok(identifier)
+}
+
 impl BundleConfiguration {
     pub fn new(require_mode: impl Into<BundleRequireMode>) -> Self {
         Self {
@@ -306,80 +475,367 @@ mod test {
         fn deserialize_dense_params() {
             let config: Configuration = json5::from_str("{ generator: { name: 'dense' }}").unwrap();
 
+            pretty_assertions::assert_eq!(config.generator, GeneratorParameters::default_dense());
+        }
+
+        #[test]
+        fn deserialize_dense_params_with_column_span() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', column_span: 110 } }").unwrap();
+
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Dense {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: 110,
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    semicolons: SemicolonStyle::AmbiguousOnly,
+                    include_types: true,
+                    remove_redundant_parentheses: false,
                 }
             );
         }
 
         #[test]
-        fn deserialize_dense_params_with_column_span() {
+        fn deserialize_readable_params() {
             let config: Configuration =
-                json5::from_str("{ generator: { name: 'dense', column_span: 110 } }").unwrap();
+                json5::from_str("{ generator: { name: 'readable' } }").unwrap();
 
             pretty_assertions::assert_eq!(
                 config.generator,
-                GeneratorParameters::Dense { column_span: 110 }
+                GeneratorParameters::default_readable()
             );
         }
 
         #[test]
-        fn deserialize_readable_params() {
+        fn deserialize_readable_params_with_column_span() {
             let config: Configuration =
-                json5::from_str("{ generator: { name: 'readable' } }").unwrap();
+                json5::from_str("{ generator: { name: 'readable', column_span: 110 }}").unwrap();
 
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Readable {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: 110,
+                    indentation: Indentation::default(),
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    keep_comments: false,
+                    include_types: true,
                 }
             );
         }
 
         #[test]
-        fn deserialize_readable_params_with_column_span() {
+        fn deserialize_dense_params_with_double_quote_style() {
             let config: Configuration =
-                json5::from_str("{ generator: { name: 'readable', column_span: 110 }}").unwrap();
+                json5::from_str("{ generator: { name: 'dense', quote_style: 'double' } }").unwrap();
 
             pretty_assertions::assert_eq!(
                 config.generator,
-                GeneratorParameters::Readable { column_span: 110 }
+                GeneratorParameters::Dense {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    quote_style: QuoteStyle::Double,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    semicolons: SemicolonStyle::AmbiguousOnly,
+                    include_types: true,
+                    remove_redundant_parentheses: false,
+                }
             );
         }
 
         #[test]
-        fn deserialize_retain_lines_params_as_string() {
-            let config: Configuration = json5::from_str("{generator: 'retain_lines'}").unwrap();
+        fn deserialize_readable_params_with_double_quote_style() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'readable', quote_style: 'double' } }")
+                    .unwrap();
 
-            pretty_assertions::assert_eq!(config.generator, GeneratorParameters::RetainLines);
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    indentation: Indentation::default(),
+                    quote_style: QuoteStyle::Double,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    keep_comments: false,
+                    include_types: true,
+                }
+            );
         }
 
         #[test]
-        fn deserialize_dense_params_as_string() {
-            let config: Configuration = json5::from_str("{generator: 'dense'}").unwrap();
+        fn deserialize_dense_params_with_long_string_threshold_never() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', long_string_threshold: 'never' } }")
+                    .unwrap();
 
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Dense {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Never,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    semicolons: SemicolonStyle::AmbiguousOnly,
+                    include_types: true,
+                    remove_redundant_parentheses: false,
                 }
             );
         }
 
         #[test]
-        fn deserialize_readable_params_as_string() {
-            let config: Configuration = json5::from_str("{generator: 'readable'}").unwrap();
+        fn deserialize_readable_params_with_long_string_threshold_always() {
+            let config: Configuration = json5::from_str(
+                "{ generator: { name: 'readable', long_string_threshold: 'always' } }",
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    indentation: Indentation::default(),
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Always,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    keep_comments: false,
+                    include_types: true,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_long_string_threshold_length() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'readable', long_string_threshold: 40 } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    indentation: Indentation::default(),
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::MinLength(40),
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    keep_comments: false,
+                    include_types: true,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_tab_indentation() {
+            let config: Configuration = json5::from_str(
+                "{ generator: { name: 'readable', indentation: { character: 'tab' } } }",
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    indentation: Indentation::tabs(1),
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    keep_comments: false,
+                    include_types: true,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_with_two_spaces_indentation() {
+            let config: Configuration = json5::from_str(
+                "{ generator: { name: 'readable', indentation: { character: 'space', width: 2 } } }",
+            )
+            .unwrap();
 
             pretty_assertions::assert_eq!(
                 config.generator,
                 GeneratorParameters::Readable {
-                    column_span: DEFAULT_COLUMN_SPAN
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    indentation: Indentation::spaces(2),
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    keep_comments: false,
+                    include_types: true,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_dense_params_with_always_semicolons() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', semicolons: 'always' } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Dense {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    semicolons: SemicolonStyle::Always,
+                    include_types: true,
+                    remove_redundant_parentheses: false,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_dense_params_with_prefer_hex_above() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', prefer_hex_above: 255 } }").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Dense {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: Some(255),
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    semicolons: SemicolonStyle::AmbiguousOnly,
+                    include_types: true,
+                    remove_redundant_parentheses: false,
                 }
             );
         }
 
+        #[test]
+        fn deserialize_dense_params_with_remove_redundant_parentheses() {
+            let config: Configuration = json5::from_str(
+                "{ generator: { name: 'dense', remove_redundant_parentheses: true } }",
+            )
+            .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorBuilder::dense()
+                    .remove_redundant_parentheses(true)
+                    .build()
+            );
+        }
+
+        #[test]
+        fn deserialize_dense_params_with_bytes_unicode_escape() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'dense', unicode_escape: 'bytes' } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Dense {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::Bytes,
+                    semicolons: SemicolonStyle::AmbiguousOnly,
+                    include_types: true,
+                    remove_redundant_parentheses: false,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_readable_params_without_types() {
+            let config: Configuration =
+                json5::from_str("{ generator: { name: 'readable', include_types: false } }")
+                    .unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::Readable {
+                    column_span: DEFAULT_COLUMN_SPAN,
+                    indentation: Indentation::default(),
+                    quote_style: QuoteStyle::Single,
+                    long_string_threshold: LongStringThreshold::Auto,
+                    prefer_hex_above: None,
+                    unicode_escape: UnicodeEscape::CodePoint,
+                    keep_comments: false,
+                    include_types: false,
+                }
+            );
+        }
+
+        #[test]
+        fn deserialize_final_newline_and_line_endings() {
+            let config: Configuration =
+                json5::from_str("{ final_newline: false, line_endings: 'crlf' }").unwrap();
+
+            assert!(!config.final_newline);
+            pretty_assertions::assert_eq!(config.line_endings, Some(LineEndings::Crlf));
+        }
+
+        #[test]
+        fn deserialize_default_final_newline_and_line_endings() {
+            let config: Configuration = json5::from_str("{}").unwrap();
+
+            assert!(config.final_newline);
+            pretty_assertions::assert_eq!(config.line_endings, None);
+        }
+
+        #[test]
+        fn deserialize_dialect() {
+            let config: Configuration = json5::from_str("{ dialect: 'lua52' }").unwrap();
+
+            pretty_assertions::assert_eq!(config.dialect, Some(LuaDialect::Lua52));
+            pretty_assertions::assert_eq!(
+                config.build_parser(),
+                Parser::default()
+                    .preserve_tokens()
+                    .with_dialect(LuaDialect::Lua52)
+            );
+        }
+
+        #[test]
+        fn deserialize_lua54_dialect() {
+            let config: Configuration = json5::from_str("{ dialect: 'lua54' }").unwrap();
+
+            pretty_assertions::assert_eq!(config.dialect, Some(LuaDialect::Lua54));
+        }
+
+        #[test]
+        fn deserialize_retain_lines_params_as_string() {
+            let config: Configuration = json5::from_str("{generator: 'retain_lines'}").unwrap();
+
+            pretty_assertions::assert_eq!(config.generator, GeneratorParameters::RetainLines);
+        }
+
+        #[test]
+        fn deserialize_dense_params_as_string() {
+            let config: Configuration = json5::from_str("{generator: 'dense'}").unwrap();
+
+            pretty_assertions::assert_eq!(config.generator, GeneratorParameters::default_dense());
+        }
+
+        #[test]
+        fn deserialize_readable_params_as_string() {
+            let config: Configuration = json5::from_str("{generator: 'readable'}").unwrap();
+
+            pretty_assertions::assert_eq!(
+                config.generator,
+                GeneratorParameters::default_readable()
+            );
+        }
+
         #[test]
         fn deserialize_unknown_generator_name() {
             let result: Result<Configuration, _> = json5::from_str("{generator: 'oops'}");
@@ -457,6 +913,18 @@ mod test {
             );
         }
 
+        #[test]
+        fn deserialize_path_require_mode_with_invalid_module_identifier() {
+            let result = json5::from_str::<Configuration>(
+                "{bundle: { require_mode: 'path', modules_identifier: 'my modules' } }",
+            );
+
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("invalid identifier `my modules`: unexpected character ` ` at 2"));
+        }
+
         #[test]
         fn deserialize_path_require_mode_with_excludes() {
             let config: Configuration = json5::from_str(
@@ -8,7 +8,7 @@ mod work_item;
 mod worker;
 mod worker_tree;
 
-pub use configuration::{BundleConfiguration, Configuration, GeneratorParameters};
+pub use configuration::{BundleConfiguration, Configuration, GeneratorParameters, LineEndings};
 pub use error::{DarkluaError, DarkluaResult};
 pub use options::Options;
 pub use resources::Resources;
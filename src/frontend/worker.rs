@@ -93,9 +93,9 @@ impl<'a> Worker<'a> {
                 "override with {} generator",
                 match generator {
                     GeneratorParameters::RetainLines => "`retain_lines`".to_owned(),
-                    GeneratorParameters::Dense { column_span } =>
+                    GeneratorParameters::Dense { column_span, .. } =>
                         format!("dense ({})", column_span),
-                    GeneratorParameters::Readable { column_span } =>
+                    GeneratorParameters::Readable { column_span, .. } =>
                         format!("readable ({})", column_span),
                 }
             );
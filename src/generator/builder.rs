@@ -0,0 +1,253 @@
+use crate::frontend::GeneratorParameters;
+
+use super::{Indentation, LongStringThreshold, QuoteStyle, SemicolonStyle, UnicodeEscape};
+
+pub(crate) const DEFAULT_COLUMN_SPAN: usize = 80;
+
+/// The entry point to configure a generator from code. Each generator has its own
+/// builder, so only the options supported by that generator can be set.
+///
+/// ```
+/// # use darklua_core::generator::{GeneratorBuilder, Indentation};
+/// let generator = GeneratorBuilder::readable()
+///     .indent(Indentation::spaces(2))
+///     .max_width(100)
+///     .build();
+///
+/// let code = "if ok then print('ok') end";
+/// let block = generator.build_parser().parse(code).unwrap();
+///
+/// assert_eq!(generator.generate_lua(&block, code), "if ok then\n  print('ok')\nend\n");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GeneratorBuilder;
+
+impl GeneratorBuilder {
+    /// Starts configuring the dense generator.
+    pub fn dense() -> DenseGeneratorBuilder {
+        DenseGeneratorBuilder::default()
+    }
+
+    /// Starts configuring the readable generator.
+    pub fn readable() -> ReadableGeneratorBuilder {
+        ReadableGeneratorBuilder::default()
+    }
+
+    /// Starts configuring the retain_lines generator.
+    pub fn retain_lines() -> RetainLinesGeneratorBuilder {
+        RetainLinesGeneratorBuilder::default()
+    }
+}
+
+/// A builder for the parameters of the [`DenseLuaGenerator`](super::DenseLuaGenerator).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGeneratorBuilder {
+    column_span: usize,
+    quote_style: QuoteStyle,
+    long_string_threshold: LongStringThreshold,
+    prefer_hex_above: Option<u64>,
+    unicode_escape: UnicodeEscape,
+    semicolons: SemicolonStyle,
+    include_types: bool,
+    remove_redundant_parentheses: bool,
+}
+
+impl Default for DenseGeneratorBuilder {
+    fn default() -> Self {
+        Self {
+            column_span: DEFAULT_COLUMN_SPAN,
+            quote_style: QuoteStyle::default(),
+            long_string_threshold: LongStringThreshold::default(),
+            prefer_hex_above: None,
+            unicode_escape: UnicodeEscape::default(),
+            semicolons: SemicolonStyle::default(),
+            include_types: true,
+            remove_redundant_parentheses: false,
+        }
+    }
+}
+
+impl DenseGeneratorBuilder {
+    /// Sets the column span the generator tries to fit lines in.
+    pub fn max_width(mut self, column_span: usize) -> Self {
+        self.column_span = column_span;
+        self
+    }
+
+    /// Sets the quote character preferred when writing strings.
+    pub fn quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Sets when strings are written with long brackets.
+    pub fn long_string_threshold(mut self, threshold: LongStringThreshold) -> Self {
+        self.long_string_threshold = threshold;
+        self
+    }
+
+    /// Writes integers above the given value with the hexadecimal notation.
+    pub fn prefer_hex_above(mut self, value: u64) -> Self {
+        self.prefer_hex_above = Some(value);
+        self
+    }
+
+    /// Sets how non-ASCII characters are escaped in strings.
+    pub fn unicode_escape(mut self, unicode_escape: UnicodeEscape) -> Self {
+        self.unicode_escape = unicode_escape;
+        self
+    }
+
+    /// Sets when semicolons are written after statements.
+    pub fn semicolons(mut self, semicolons: SemicolonStyle) -> Self {
+        self.semicolons = semicolons;
+        self
+    }
+
+    /// Sets if Luau types are written. This is enabled by default.
+    pub fn include_types(mut self, include_types: bool) -> Self {
+        self.include_types = include_types;
+        self
+    }
+
+    /// Sets if parentheses that do not change the meaning of the code are removed.
+    pub fn remove_redundant_parentheses(mut self, remove: bool) -> Self {
+        self.remove_redundant_parentheses = remove;
+        self
+    }
+
+    pub fn build(self) -> GeneratorParameters {
+        GeneratorParameters::Dense {
+            column_span: self.column_span,
+            quote_style: self.quote_style,
+            long_string_threshold: self.long_string_threshold,
+            prefer_hex_above: self.prefer_hex_above,
+            unicode_escape: self.unicode_escape,
+            semicolons: self.semicolons,
+            include_types: self.include_types,
+            remove_redundant_parentheses: self.remove_redundant_parentheses,
+        }
+    }
+}
+
+impl From<DenseGeneratorBuilder> for GeneratorParameters {
+    fn from(builder: DenseGeneratorBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// A builder for the parameters of the
+/// [`ReadableLuaGenerator`](super::ReadableLuaGenerator).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadableGeneratorBuilder {
+    column_span: usize,
+    indentation: Indentation,
+    quote_style: QuoteStyle,
+    long_string_threshold: LongStringThreshold,
+    prefer_hex_above: Option<u64>,
+    unicode_escape: UnicodeEscape,
+    keep_comments: bool,
+    include_types: bool,
+}
+
+impl Default for ReadableGeneratorBuilder {
+    fn default() -> Self {
+        Self {
+            column_span: DEFAULT_COLUMN_SPAN,
+            indentation: Indentation::default(),
+            quote_style: QuoteStyle::default(),
+            long_string_threshold: LongStringThreshold::default(),
+            prefer_hex_above: None,
+            unicode_escape: UnicodeEscape::default(),
+            keep_comments: false,
+            include_types: true,
+        }
+    }
+}
+
+impl ReadableGeneratorBuilder {
+    /// Sets the column span the generator tries to fit lines in.
+    pub fn max_width(mut self, column_span: usize) -> Self {
+        self.column_span = column_span;
+        self
+    }
+
+    /// Sets the indentation written for each nested block.
+    pub fn indent(mut self, indentation: Indentation) -> Self {
+        self.indentation = indentation;
+        self
+    }
+
+    /// Sets the quote character preferred when writing strings.
+    pub fn quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Sets when strings are written with long brackets.
+    pub fn long_string_threshold(mut self, threshold: LongStringThreshold) -> Self {
+        self.long_string_threshold = threshold;
+        self
+    }
+
+    /// Writes integers above the given value with the hexadecimal notation.
+    pub fn prefer_hex_above(mut self, value: u64) -> Self {
+        self.prefer_hex_above = Some(value);
+        self
+    }
+
+    /// Sets how non-ASCII characters are escaped in strings.
+    pub fn unicode_escape(mut self, unicode_escape: UnicodeEscape) -> Self {
+        self.unicode_escape = unicode_escape;
+        self
+    }
+
+    /// Sets if the comments of statements are written.
+    pub fn keep_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
+    }
+
+    /// Sets if Luau types are written. This is enabled by default.
+    pub fn include_types(mut self, include_types: bool) -> Self {
+        self.include_types = include_types;
+        self
+    }
+
+    pub fn build(self) -> GeneratorParameters {
+        GeneratorParameters::Readable {
+            column_span: self.column_span,
+            indentation: self.indentation,
+            quote_style: self.quote_style,
+            long_string_threshold: self.long_string_threshold,
+            prefer_hex_above: self.prefer_hex_above,
+            unicode_escape: self.unicode_escape,
+            keep_comments: self.keep_comments,
+            include_types: self.include_types,
+        }
+    }
+}
+
+impl From<ReadableGeneratorBuilder> for GeneratorParameters {
+    fn from(builder: ReadableGeneratorBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// A builder for the parameters of the
+/// [`TokenBasedLuaGenerator`](super::TokenBasedLuaGenerator). This generator keeps the
+/// lines of the original code and has no options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetainLinesGeneratorBuilder {}
+
+impl RetainLinesGeneratorBuilder {
+    pub fn build(self) -> GeneratorParameters {
+        GeneratorParameters::RetainLines
+    }
+}
+
+impl From<RetainLinesGeneratorBuilder> for GeneratorParameters {
+    fn from(builder: RetainLinesGeneratorBuilder) -> Self {
+        builder.build()
+    }
+}
@@ -1,4 +1,7 @@
-use crate::generator::{utils, LuaGenerator};
+use crate::generator::{
+    source_map::{OriginalPosition, SourceMapBuilder},
+    utils, LongStringThreshold, LuaGenerator, QuoteStyle, SemicolonStyle, SourceMap, UnicodeEscape,
+};
 use crate::nodes;
 
 /// This implementation of [LuaGenerator](trait.LuaGenerator.html) attempts to produce Lua code as
@@ -6,6 +9,14 @@ use crate::nodes;
 #[derive(Debug, Clone)]
 pub struct DenseLuaGenerator {
     column_span: usize,
+    quote_style: QuoteStyle,
+    long_string_threshold: LongStringThreshold,
+    prefer_hex_above: Option<u64>,
+    unicode_escape: UnicodeEscape,
+    include_types: bool,
+    remove_redundant_parentheses: bool,
+    source_map: Option<SourceMapBuilder>,
+    semicolon_style: SemicolonStyle,
     current_line_length: usize,
     output: String,
     last_push_length: usize,
@@ -17,12 +28,101 @@ impl DenseLuaGenerator {
     pub fn new(column_span: usize) -> Self {
         Self {
             column_span,
+            quote_style: QuoteStyle::default(),
+            long_string_threshold: LongStringThreshold::default(),
+            prefer_hex_above: None,
+            unicode_escape: UnicodeEscape::default(),
+            include_types: true,
+            remove_redundant_parentheses: false,
+            source_map: None,
+            semicolon_style: SemicolonStyle::default(),
             current_line_length: 0,
             output: String::new(),
             last_push_length: 0,
         }
     }
 
+    /// Sets the quote character preferred when writing quoted strings.
+    pub fn with_quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Sets when strings are written with long brackets instead of quotes.
+    pub fn with_long_string_threshold(mut self, threshold: LongStringThreshold) -> Self {
+        self.long_string_threshold = threshold;
+        self
+    }
+
+    /// Writes decimal integers greater than the given value using the hexadecimal
+    /// notation.
+    pub fn with_prefer_hex_above(mut self, value: u64) -> Self {
+        self.prefer_hex_above = Some(value);
+        self
+    }
+
+    /// Sets how non-ASCII characters are escaped in strings.
+    pub fn with_unicode_escape(mut self, unicode_escape: UnicodeEscape) -> Self {
+        self.unicode_escape = unicode_escape;
+        self
+    }
+
+    /// Sets if Luau type annotations are written. When types are not included, type
+    /// annotations, generic parameters and type declarations are not written and type
+    /// casts are replaced by their inner expression.
+    pub fn with_include_types(mut self, include_types: bool) -> Self {
+        self.include_types = include_types;
+        self
+    }
+
+    /// Sets if parentheses that do not change the meaning of the code are removed, like
+    /// doubled parentheses or parentheses around identifiers and literals. Parentheses
+    /// that truncate multiple values (like `(f())` or `(...)`) are kept.
+    pub fn with_remove_redundant_parentheses(mut self, remove: bool) -> Self {
+        self.remove_redundant_parentheses = remove;
+        self
+    }
+
+    /// Records the original positions of the written statements, which can be obtained
+    /// with [`into_string_with_source_map`](Self::into_string_with_source_map). The block
+    /// must be parsed with tokens and the original code is needed to compute the original
+    /// columns.
+    pub fn with_source_map(mut self, original_code: impl Into<String>) -> Self {
+        self.source_map = Some(SourceMapBuilder::new(original_code));
+        self
+    }
+
+    /// Consumes the generator and produce the generated code with its source map. The
+    /// source map is empty if the generator was not created
+    /// [`with_source_map`](Self::with_source_map).
+    pub fn into_string_with_source_map(self) -> (String, SourceMap) {
+        let output = self.output;
+        let source_map = self
+            .source_map
+            .map(|builder| builder.build(&output))
+            .unwrap_or_default();
+        (output, source_map)
+    }
+
+    fn enter_source_position(&mut self, token: Option<&nodes::Token>) -> Option<OriginalPosition> {
+        let offset = self.output.len();
+        self.source_map
+            .as_mut()
+            .and_then(|source_map| source_map.enter(offset, token))
+    }
+
+    fn exit_source_position(&mut self, previous: Option<OriginalPosition>) {
+        if let Some(source_map) = self.source_map.as_mut() {
+            source_map.exit(previous);
+        }
+    }
+
+    /// Sets when semicolons are written after statements.
+    pub fn with_semicolon_style(mut self, semicolon_style: SemicolonStyle) -> Self {
+        self.semicolon_style = semicolon_style;
+        self
+    }
+
     /// Appends a string to the current content of the DenseLuaGenerator. A space may be added
     /// depending of the last character of the current content and the first character pushed.
     fn push_str(&mut self, content: &str) {
@@ -193,6 +293,7 @@ impl DenseLuaGenerator {
         is_variadic: bool,
         variadic_type: Option<&nodes::FunctionVariadicType>,
     ) {
+        let variadic_type = variadic_type.filter(|_| self.include_types);
         let last_index = parameters.len().saturating_sub(1);
 
         parameters.iter().enumerate().for_each(|(index, variable)| {
@@ -219,7 +320,13 @@ impl DenseLuaGenerator {
     fn write_typed_identifier(&mut self, typed_identifier: &nodes::TypedIdentifier) {
         self.push_str(typed_identifier.get_name());
 
-        if let Some(r#type) = typed_identifier.get_type() {
+        if let Some(attribute) = typed_identifier.get_attribute() {
+            self.push_char('<');
+            self.push_str(attribute.as_str());
+            self.push_char('>');
+        }
+
+        if let Some(r#type) = typed_identifier.get_type().filter(|_| self.include_types) {
             self.push_char(':');
             self.write_type(r#type);
         }
@@ -244,6 +351,61 @@ impl DenseLuaGenerator {
         self.push_char(')');
     }
 
+    /// Returns the expression inside nested parentheses (and type casts when types are
+    /// not written).
+    fn skip_parentheses<'a>(&self, mut expression: &'a nodes::Expression) -> &'a nodes::Expression {
+        loop {
+            match expression {
+                nodes::Expression::Parenthese(parenthese) => {
+                    expression = parenthese.inner_expression();
+                }
+                nodes::Expression::TypeCast(type_cast) if !self.include_types => {
+                    expression = type_cast.get_expression();
+                }
+                _ => break expression,
+            }
+        }
+    }
+
+    /// Writes the condition of a statement. Since only the first value of a condition is
+    /// used, its parentheses can be removed.
+    fn write_condition(&mut self, condition: &nodes::Expression) {
+        match condition {
+            nodes::Expression::Parenthese(_) if self.remove_redundant_parentheses => {
+                let inner = self.skip_parentheses(condition);
+                if matches!(inner, nodes::Expression::If(_)) {
+                    self.write_expression_in_parentheses(inner);
+                } else {
+                    self.write_expression(inner);
+                }
+            }
+            _ => self.write_expression(condition),
+        }
+    }
+
+    fn write_binary_operand(
+        &mut self,
+        operand: &nodes::Expression,
+        needs_parentheses: bool,
+        parentheses_are_redundant: impl Fn(&nodes::ParentheseExpression) -> bool,
+    ) {
+        if needs_parentheses {
+            self.write_expression_in_parentheses(operand);
+            return;
+        }
+        match operand {
+            nodes::Expression::Parenthese(parenthese) if self.remove_redundant_parentheses => {
+                let inner = self.skip_parentheses(operand);
+                if parentheses_are_redundant(parenthese) {
+                    self.write_expression(inner);
+                } else {
+                    self.write_expression_in_parentheses(inner);
+                }
+            }
+            _ => self.write_expression(operand),
+        }
+    }
+
     fn write_type_in_parentheses(&mut self, r#type: &nodes::Type) {
         self.push_char('(');
         self.write_type(r#type);
@@ -289,22 +451,48 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_block(&mut self, block: &nodes::Block) {
-        let mut statements = block.iter_statements().peekable();
+        // the shebang ends with the same new line as every other line written by this
+        // generator (line endings can be converted with the `line_endings` option)
+        if let Some(shebang) = block.get_shebang() {
+            self.raw_push_str(shebang);
+            self.push_new_line();
+        }
+
+        let include_types = self.include_types;
+        let mut statements = block
+            .iter_statements()
+            .filter(|statement| {
+                include_types || !matches!(statement, nodes::Statement::TypeDeclaration(_))
+            })
+            .peekable();
 
         while let Some(statement) = statements.next() {
+            let previous_position = self.enter_source_position(statement.get_first_token());
             self.write_statement(statement);
-
-            if let Some(next_statement) = statements.peek() {
-                if utils::starts_with_parenthese(next_statement)
-                    && utils::ends_with_prefix(statement)
-                {
-                    self.push_char(';');
+            self.exit_source_position(previous_position);
+
+            match self.semicolon_style {
+                SemicolonStyle::Always => self.push_char(';'),
+                SemicolonStyle::AmbiguousOnly => {
+                    if let Some(next_statement) = statements.peek() {
+                        if utils::starts_with_parenthese(next_statement)
+                            && utils::ends_with_prefix(statement)
+                        {
+                            self.push_char(';');
+                        }
+                    }
                 }
             }
         }
 
         if let Some(last_statement) = block.get_last_statement() {
+            let previous_position = self.enter_source_position(last_statement.get_first_token());
             self.write_last_statement(last_statement);
+            self.exit_source_position(previous_position);
+
+            if self.semicolon_style == SemicolonStyle::Always {
+                self.push_char(';');
+            }
         }
     }
 
@@ -333,6 +521,17 @@ impl LuaGenerator for DenseLuaGenerator {
         });
     }
 
+    fn write_goto_statement(&mut self, goto: &nodes::GotoStatement) {
+        self.push_str("goto");
+        self.push_str(goto.get_label().get_name());
+    }
+
+    fn write_label_statement(&mut self, label: &nodes::LabelStatement) {
+        self.push_str("::");
+        self.push_str(label.get_name().get_name());
+        self.push_str("::");
+    }
+
     fn write_do_statement(&mut self, do_statement: &nodes::DoStatement) {
         self.push_str("do");
         self.write_block(do_statement.get_block());
@@ -384,7 +583,7 @@ impl LuaGenerator for DenseLuaGenerator {
                 self.push_str("elseif");
             }
 
-            self.write_expression(branch.get_condition());
+            self.write_condition(branch.get_condition());
             self.push_str("then");
             self.write_block(branch.get_block());
         });
@@ -413,7 +612,10 @@ impl LuaGenerator for DenseLuaGenerator {
             self.push_str(method.get_name());
         }
 
-        if let Some(generics) = function.get_generic_parameters() {
+        if let Some(generics) = function
+            .get_generic_parameters()
+            .filter(|_| self.include_types)
+        {
             self.write_function_generics(generics);
         }
 
@@ -425,7 +627,7 @@ impl LuaGenerator for DenseLuaGenerator {
         );
         self.push_char(')');
 
-        if let Some(return_type) = function.get_return_type() {
+        if let Some(return_type) = function.get_return_type().filter(|_| self.include_types) {
             self.push_char(':');
             self.write_function_return_type(return_type);
         }
@@ -503,7 +705,10 @@ impl LuaGenerator for DenseLuaGenerator {
         self.push_str("local function");
         self.push_str(function.get_name());
 
-        if let Some(generics) = function.get_generic_parameters() {
+        if let Some(generics) = function
+            .get_generic_parameters()
+            .filter(|_| self.include_types)
+        {
             self.write_function_generics(generics);
         }
 
@@ -517,7 +722,7 @@ impl LuaGenerator for DenseLuaGenerator {
         );
         self.push_char(')');
 
-        if let Some(return_type) = function.get_return_type() {
+        if let Some(return_type) = function.get_return_type().filter(|_| self.include_types) {
             self.push_char(':');
             self.write_function_return_type(return_type);
         }
@@ -567,12 +772,12 @@ impl LuaGenerator for DenseLuaGenerator {
         }
 
         self.push_str("until");
-        self.write_expression(repeat.get_condition());
+        self.write_condition(repeat.get_condition());
     }
 
     fn write_while_statement(&mut self, while_statement: &nodes::WhileStatement) {
         self.push_str("while");
-        self.write_expression(while_statement.get_condition());
+        self.write_condition(while_statement.get_condition());
 
         let block = while_statement.get_block();
 
@@ -660,22 +865,18 @@ impl LuaGenerator for DenseLuaGenerator {
         let left = binary.left();
         let right = binary.right();
 
-        if operator.left_needs_parentheses(left) {
-            self.write_expression_in_parentheses(left);
-        } else {
-            self.write_expression(left);
-        }
+        self.write_binary_operand(left, operator.left_needs_parentheses(left), |left| {
+            operator.left_parentheses_are_redundant(left)
+        });
 
         match operator {
             BinaryOperator::Concat => self.push_str_and_break_if("..", utils::break_concat),
             _ => self.push_str(operator.to_str()),
         }
 
-        if operator.right_needs_parentheses(right) {
-            self.write_expression_in_parentheses(right);
-        } else {
-            self.write_expression(right);
-        }
+        self.write_binary_operand(right, operator.right_needs_parentheses(right), |right| {
+            operator.right_parentheses_are_redundant(right)
+        });
     }
 
     fn write_unary_expression(&mut self, unary: &nodes::UnaryExpression) {
@@ -700,7 +901,10 @@ impl LuaGenerator for DenseLuaGenerator {
     fn write_function(&mut self, function: &nodes::FunctionExpression) {
         self.push_str("function");
 
-        if let Some(generics) = function.get_generic_parameters() {
+        if let Some(generics) = function
+            .get_generic_parameters()
+            .filter(|_| self.include_types)
+        {
             self.write_function_generics(generics);
         }
 
@@ -714,7 +918,7 @@ impl LuaGenerator for DenseLuaGenerator {
         );
         self.push_char(')');
 
-        if let Some(return_type) = function.get_return_type() {
+        if let Some(return_type) = function.get_return_type().filter(|_| self.include_types) {
             self.push_char(':');
             self.write_function_return_type(return_type);
         }
@@ -829,7 +1033,7 @@ impl LuaGenerator for DenseLuaGenerator {
                     self.push_char('0');
                     self.push_char(')');
                 } else {
-                    let result = utils::write_number(number);
+                    let result = utils::write_number(number, self.prefer_hex_above);
 
                     self.push_str(&result);
                 }
@@ -882,7 +1086,12 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_string(&mut self, string: &nodes::StringExpression) {
-        let result = utils::write_string(string.get_value());
+        let result = utils::write_string(
+            string.as_bytes(),
+            self.quote_style,
+            self.long_string_threshold,
+            self.unicode_escape,
+        );
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -921,13 +1130,75 @@ impl LuaGenerator for DenseLuaGenerator {
         self.push_str(identifier.get_name());
     }
 
+    fn write_prefix(&mut self, prefix: &nodes::Prefix) {
+        use nodes::{Expression, Prefix};
+
+        match prefix {
+            Prefix::Call(call) => self.write_function_call(call),
+            Prefix::Field(field) => self.write_field(field),
+            Prefix::Identifier(identifier) => self.write_identifier(identifier),
+            Prefix::Index(index) => self.write_index(index),
+            Prefix::Parenthese(parenthese) if self.remove_redundant_parentheses => {
+                let inner = self.skip_parentheses(parenthese.inner_expression());
+                match inner {
+                    // only the first value of a prefix is used, so calls do not need to
+                    // be truncated
+                    Expression::Call(_)
+                    | Expression::Field(_)
+                    | Expression::Identifier(_)
+                    | Expression::Index(_) => self.write_expression(inner),
+                    _ => self.write_expression_in_parentheses(inner),
+                }
+            }
+            Prefix::Parenthese(parenthese) => self.write_parenthese(parenthese),
+        }
+    }
+
     fn write_parenthese(&mut self, parenthese: &nodes::ParentheseExpression) {
-        self.write_expression_in_parentheses(parenthese.inner_expression());
+        let inner_expression = parenthese.inner_expression();
+        if self.remove_redundant_parentheses {
+            let inner = self.skip_parentheses(inner_expression);
+            match inner {
+                nodes::Expression::False(_)
+                | nodes::Expression::Field(_)
+                | nodes::Expression::Function(_)
+                | nodes::Expression::Identifier(_)
+                | nodes::Expression::Index(_)
+                | nodes::Expression::Nil(_)
+                | nodes::Expression::Number(_)
+                | nodes::Expression::String(_)
+                | nodes::Expression::InterpolatedString(_)
+                | nodes::Expression::Table(_)
+                | nodes::Expression::True(_) => self.write_expression(inner),
+                _ => self.write_expression_in_parentheses(inner),
+            }
+        } else if self.include_types {
+            self.write_expression_in_parentheses(inner_expression);
+        } else {
+            self.write_expression_in_parentheses(utils::skip_type_casts(inner_expression));
+        }
     }
 
     fn write_type_cast(&mut self, type_cast: &nodes::TypeCastExpression) {
         let inner_expression = type_cast.get_expression();
 
+        if !self.include_types {
+            let inner_expression = utils::skip_type_casts(inner_expression);
+            // keep parentheses to preserve the precedence and the truncation of
+            // multiple values done by the type cast
+            if nodes::TypeCastExpression::needs_parentheses(inner_expression)
+                || matches!(
+                    inner_expression,
+                    nodes::Expression::Call(_) | nodes::Expression::VariableArguments(_)
+                )
+            {
+                self.write_expression_in_parentheses(inner_expression);
+            } else {
+                self.write_expression(inner_expression);
+            }
+            return;
+        }
+
         if nodes::TypeCastExpression::needs_parentheses(inner_expression) {
             self.write_expression_in_parentheses(inner_expression);
         } else {
@@ -974,7 +1245,12 @@ impl LuaGenerator for DenseLuaGenerator {
     }
 
     fn write_string_type(&mut self, string_type: &nodes::StringType) {
-        let result = utils::write_string(string_type.get_value());
+        let result = utils::write_string(
+            string_type.as_bytes(),
+            self.quote_style,
+            self.long_string_threshold,
+            self.unicode_escape,
+        );
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
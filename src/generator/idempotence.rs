@@ -0,0 +1,202 @@
+use std::fmt;
+
+use crate::{
+    nodes::Block,
+    parser::{Parser, ParserError},
+};
+
+/// The error returned by [`check_idempotence`] when generating the generated code again
+/// does not produce the same output.
+#[derive(Debug, Clone)]
+pub enum IdempotenceError {
+    /// The generated code could not be parsed.
+    Parse {
+        generated_code: String,
+        error: ParserError,
+    },
+    /// The code generated a second time is different from the first generated code.
+    Divergence {
+        first_output: String,
+        second_output: String,
+        /// The line where both outputs start to differ (starting at 1).
+        line: usize,
+        /// The column (in bytes) where both outputs start to differ (starting at 1).
+        column: usize,
+    },
+}
+
+impl IdempotenceError {
+    fn divergence(first_output: String, second_output: String) -> Self {
+        let offset = first_output
+            .bytes()
+            .zip(second_output.bytes())
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| first_output.len().min(second_output.len()));
+
+        let before = &first_output.as_bytes()[..offset];
+        let line = before.iter().filter(|byte| **byte == b'\n').count() + 1;
+        let column = offset
+            - before
+                .iter()
+                .rposition(|byte| *byte == b'\n')
+                .map(|index| index + 1)
+                .unwrap_or(0)
+            + 1;
+
+        Self::Divergence {
+            first_output,
+            second_output,
+            line,
+            column,
+        }
+    }
+}
+
+fn get_line(code: &str, line: usize) -> &str {
+    code.lines().nth(line.saturating_sub(1)).unwrap_or_default()
+}
+
+impl fmt::Display for IdempotenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse {
+                generated_code,
+                error,
+            } => write!(
+                f,
+                "unable to parse generated code: {}\ngenerated code:\n{}",
+                error, generated_code
+            ),
+            Self::Divergence {
+                first_output,
+                second_output,
+                line,
+                column,
+            } => write!(
+                f,
+                "generated code changed when generated again (line {}, column {})\n  first: {}\n second: {}",
+                line,
+                column,
+                get_line(first_output, *line),
+                get_line(second_output, *line),
+            ),
+        }
+    }
+}
+
+/// Generates the given block, then parses the generated code and generates it again.
+/// Both outputs must be identical: otherwise, the returned error tells where they
+/// start to differ. The `generate` function receives the block to generate and the
+/// code it was parsed from, and the parser is used to parse the generated code.
+pub fn check_idempotence<F>(
+    parser: &Parser,
+    block: &Block,
+    code: &str,
+    generate: F,
+) -> Result<String, IdempotenceError>
+where
+    F: Fn(&Block, &str) -> String,
+{
+    let first_output = generate(block, code);
+
+    let generated_block = match parser.parse(&first_output) {
+        Ok(block) => block,
+        Err(error) => {
+            return Err(IdempotenceError::Parse {
+                generated_code: first_output,
+                error,
+            })
+        }
+    };
+
+    let second_output = generate(&generated_block, &first_output);
+
+    if first_output == second_output {
+        Ok(first_output)
+    } else {
+        Err(IdempotenceError::divergence(first_output, second_output))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::generator::{
+        DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator, TokenBasedLuaGenerator,
+    };
+
+    fn generate_with<G: LuaGenerator>(mut generator: G, block: &Block) -> String {
+        generator.write_block(block);
+        generator.into_string()
+    }
+
+    const CODE: &str = "local a = { 1, 2 }\nif a[1] then\n    print(a[2] .. '!')\nend\n";
+
+    #[test]
+    fn readable_generator_is_idempotent() {
+        let parser = Parser::default();
+        let block = parser.parse(CODE).unwrap();
+
+        assert!(check_idempotence(&parser, &block, CODE, |block, _| {
+            generate_with(ReadableLuaGenerator::default(), block)
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn dense_generator_is_idempotent() {
+        let parser = Parser::default();
+        let block = parser.parse(CODE).unwrap();
+
+        assert!(check_idempotence(&parser, &block, CODE, |block, _| {
+            generate_with(DenseLuaGenerator::default(), block)
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn token_based_generator_is_idempotent() {
+        let parser = Parser::default().preserve_tokens();
+        let block = parser.parse(CODE).unwrap();
+
+        pretty_assertions::assert_eq!(
+            check_idempotence(&parser, &block, CODE, |block, code| {
+                generate_with(TokenBasedLuaGenerator::new(code), block)
+            })
+            .unwrap(),
+            CODE
+        );
+    }
+
+    #[test]
+    fn divergence_points_to_first_different_character() {
+        let error = IdempotenceError::divergence(
+            "local a = 1\nreturn a".to_owned(),
+            "local a = 1\nreturn  a".to_owned(),
+        );
+
+        match &error {
+            IdempotenceError::Divergence { line, column, .. } => {
+                assert_eq!((*line, *column), (2, 8));
+            }
+            _ => panic!("divergence error expected"),
+        }
+        pretty_assertions::assert_eq!(
+            error.to_string(),
+            "generated code changed when generated again (line 2, column 8)\n  first: return a\n second: return  a"
+        );
+    }
+
+    #[test]
+    fn divergence_when_second_output_is_longer() {
+        let error = IdempotenceError::divergence("return a".to_owned(), "return a\n".to_owned());
+
+        match error {
+            IdempotenceError::Divergence { line, column, .. } => {
+                assert_eq!((line, column), (1, 9));
+            }
+            _ => panic!("divergence error expected"),
+        }
+    }
+}
@@ -1,14 +1,27 @@
 //! A module that contains the main [LuaGenerator](trait.LuaGenerator.html) trait
 //! and its implementations.
 
+mod builder;
 mod dense;
+mod idempotence;
 mod readable;
+mod source_map;
 mod token_based;
 mod utils;
 
+pub(crate) use builder::DEFAULT_COLUMN_SPAN;
+pub use builder::{
+    DenseGeneratorBuilder, GeneratorBuilder, ReadableGeneratorBuilder, RetainLinesGeneratorBuilder,
+};
 pub use dense::DenseLuaGenerator;
+pub use idempotence::{check_idempotence, IdempotenceError};
 pub use readable::ReadableLuaGenerator;
+pub use source_map::{SourceMap, SourceMapping};
 pub use token_based::TokenBasedLuaGenerator;
+pub use utils::{
+    Indentation, IndentationCharacter, LongStringThreshold, QuoteStyle, SemicolonStyle,
+    UnicodeEscape,
+};
 
 use crate::nodes;
 
@@ -30,7 +43,9 @@ pub trait LuaGenerator {
             CompoundAssign(statement) => self.write_compound_assign(statement),
             Function(statement) => self.write_function_statement(statement),
             GenericFor(statement) => self.write_generic_for(statement),
+            Goto(statement) => self.write_goto_statement(statement),
             If(statement) => self.write_if_statement(statement),
+            Label(statement) => self.write_label_statement(statement),
             LocalAssign(statement) => self.write_local_assign(statement),
             LocalFunction(statement) => self.write_local_function(statement),
             NumericFor(statement) => self.write_numeric_for(statement),
@@ -44,7 +59,9 @@ pub trait LuaGenerator {
     fn write_do_statement(&mut self, do_statement: &nodes::DoStatement);
     fn write_compound_assign(&mut self, assign: &nodes::CompoundAssignStatement);
     fn write_generic_for(&mut self, generic_for: &nodes::GenericForStatement);
+    fn write_goto_statement(&mut self, goto: &nodes::GotoStatement);
     fn write_if_statement(&mut self, if_statement: &nodes::IfStatement);
+    fn write_label_statement(&mut self, label: &nodes::LabelStatement);
     fn write_function_statement(&mut self, function: &nodes::FunctionStatement);
     fn write_last_statement(&mut self, statement: &nodes::LastStatement);
     fn write_local_assign(&mut self, assign: &nodes::LocalAssignStatement);
@@ -331,6 +348,33 @@ mod test {
         };
     }
 
+    macro_rules! dialect_blocks_consistency {
+        (
+            $generator:expr, $dialect:ident => (
+                $($name:ident => $code:literal),+,
+            )
+        ) => {
+            $(
+                #[test]
+                fn $name() {
+                    let parser = $crate::Parser::default().with_dialect($crate::LuaDialect::$dialect);
+
+                    let expected_block = parser.parse($code)
+                        .expect(&format!("unable to parse `{}`", $code));
+
+                    let mut generator = $generator;
+                    generator.write_block(&expected_block);
+                    let generated_code = generator.into_string();
+
+                    let generated_block = parser.parse(&generated_code)
+                        .expect(&format!("unable to parse generated code `{}`", &generated_code));
+
+                    assert_eq!(expected_block, generated_block);
+                }
+            )*
+        };
+    }
+
     macro_rules! binary_precedence {
         (
             $generator:expr => (
@@ -399,9 +443,71 @@ mod $mod_name {
             concat_variable_arguments_with_number => "return ... ..1",
             double_unary_minus => "return - -10",
             binary_minus_with_unary_minus => "return 100- -10",
+            interpolated_string_starting_with_value => "return `{value} items`",
+            interpolated_string_ending_with_value => "return `items: {value}`",
+            interpolated_string_with_adjacent_values => "return `{a}{b}{c}`",
+            interpolated_string_with_escaped_backtick => "return `a \\` {b} \\``",
+            interpolated_string_with_escaped_brace => "return `\\{ {value} }`",
+            interpolated_string_with_control_characters => "return `line\\n\\t{value}\\0`",
+            interpolated_string_with_nested_interpolated_string => "return `{`{value}`}`",
+            interpolated_string_with_table => "return `{ {} }`",
+            string_with_null_followed_by_digit => "return '\\0005', '\\1\\0023'",
+            interpolated_string_with_null_followed_by_digit => "return `\\0005{value}\\0017`",
+            string_with_invalid_utf8_bytes => "return '\\255\\0\\1'",
+            string_with_mixed_binary_and_text => "return 'caf\\u{e9} \\255\\254 ok', \"\\200'\\0019\"",
+        ));
+    }
+
+    mod lua52 {
+        use super::*;
+
+        dialect_blocks_consistency!($generator, Lua52 => (
+            goto_statement => "goto done",
+            label_statement => "::done::",
+            consecutive_labels => "::first:: ::second::",
+            goto_followed_by_label => "goto skip ::skip::",
+            label_after_call => "call() ::done::",
+            goto_continue_in_loop => "for i = 1, 10 do if i % 2 == 0 then goto continue end print(i) ::continue:: end",
+        ));
+    }
+
+    mod lua54 {
+        use super::*;
+
+        dialect_blocks_consistency!($generator, Lua54 => (
+            local_const => "local x <const> = 1",
+            local_close => "local file <close> = io.open('file.txt')",
+            local_const_without_value => "local x <const>",
+            multiple_attributes => "local a <const>, b, c <close> = 1, 2, 3",
+            local_const_compared => "local x <const> = a >= b",
+            goto_in_lua54 => "goto done ::done::",
         ));
     }
 
+    mod shebang {
+        use super::*;
+
+        blocks_consistency!($generator => (
+            shebang_only => "#!/usr/bin/env lua\n",
+            shebang_with_statement => "#!/usr/bin/env lua\nprint('hello')",
+            shebang_with_last_statement => "#!/usr/bin/env lua\nreturn 1",
+            shebang_with_directive => "#!/usr/bin/env luau\n--!strict\nlocal x = 1",
+            strict_directive => "--!strict\nlocal x = 1",
+        ));
+
+        #[test]
+        fn writes_shebang_on_first_line() {
+            let block = $crate::Parser::default()
+                .parse("#!/usr/bin/env lua\nprint('hello')")
+                .unwrap();
+
+            let mut generator = $generator;
+            generator.write_block(&block);
+
+            assert!(generator.into_string().starts_with("#!/usr/bin/env lua\n"));
+        }
+    }
+
     mod numbers {
         use super::*;
 
@@ -745,6 +851,10 @@ mod $mod_name {
             ),
         ));
 
+        snapshot_node!($mod_name, $generator, goto_statement, write_statement => (
+            goto_label => GotoStatement::new("continue"),
+        ));
+
         snapshot_node!($mod_name, $generator, type_declaration, write_type_declaration_statement => (
             string_alias => TypeDeclarationStatement::new("Str", TypeName::new("string")),
             type_field => TypeDeclarationStatement::new("Object", TypeField::new("module", TypeName::new("Object"))),
@@ -842,6 +952,15 @@ mod $mod_name {
                 .with_new_branch(false, Block::default()),
         ));
 
+        snapshot_node!($mod_name, $generator, label_statement, write_statement => (
+            label => LabelStatement::new("continue"),
+            label_after_goto => DoStatement::new(
+                Block::default()
+                    .with_statement(GotoStatement::new("continue"))
+                    .with_statement(LabelStatement::new("continue"))
+            ),
+        ));
+
         snapshot_node!($mod_name, $generator, intersection_type, write_intersection_type => (
             single_type => IntersectionType::from(vec![Type::from(true)]),
             two_types => IntersectionType::from(vec![Type::from(true), Type::from(false)]),
@@ -1119,6 +1238,605 @@ mod $mod_name {
         };
     }
 
+    mod readable_indentation {
+        use super::*;
+
+        const NESTED_BLOCKS: &str = "local function process(list)\n\
+            for i = 1, #list do\n\
+                if list[i] then\n\
+                    print(i)\n\
+                elseif i > 10 then\n\
+                    for _, value in pairs(list[i - 1]) do\n\
+                        print(value)\n\
+                    end\n\
+                end\n\
+            end\n\
+        end\n";
+
+        macro_rules! snapshot_indentation {
+            ($($name:ident => $indentation:expr),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let block = crate::Parser::default().parse(NESTED_BLOCKS).unwrap();
+
+                        let mut generator =
+                            ReadableLuaGenerator::default().with_indentation($indentation);
+                        generator.write_block(&block);
+
+                        insta::assert_snapshot!(
+                            concat!("readable_indentation_", stringify!($name)),
+                            generator.into_string()
+                        );
+                    }
+                )*
+            };
+        }
+
+        snapshot_indentation!(
+            tab => Indentation::tabs(1),
+            two_spaces => Indentation::spaces(2),
+            four_spaces => Indentation::spaces(4),
+        );
+    }
+
+    mod readable_comments {
+        use super::*;
+
+        macro_rules! snapshot_comments {
+            ($($name:ident => $code:expr),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let code = $code;
+                        let block = crate::Parser::default()
+                            .preserve_tokens()
+                            .parse(code)
+                            .unwrap();
+
+                        let mut generator = ReadableLuaGenerator::default().with_comments(code);
+                        generator.write_block(&block);
+
+                        insta::assert_snapshot!(
+                            concat!("readable_comments_", stringify!($name)),
+                            generator.into_string()
+                        );
+                    }
+                )*
+            };
+        }
+
+        snapshot_comments!(
+            file_header => "--!strict\n-- Copyright (c) 2024 Example\n-- MIT License\n\nlocal value = 1\nreturn value\n",
+            trailing_comments => "local a = 1 -- first\nlocal b = 2 --[[ second ]]\nreturn a + b -- sum\n",
+            nested_blocks => "local function run(list)\n    -- iterate\n    for _, v in list do\n        print(v) -- log\n    end\n    -- done\nend\n",
+            if_branches => "if a then\n    print(a)\n    -- after a\nelseif b then\n    print(b)\n    -- after b\nelse\n    print(c)\n    -- after c\nend\n",
+            multiline_block_comment => "do\n    --[[\n        Keeps\n          indentation\n    ]]\n    print(true)\nend\n",
+            comment_at_end_of_file => "print('hello')\n-- end of file\n",
+            comments_in_expression_are_dropped => "local value = 1 + -- one\n    2\n",
+        );
+
+        #[test]
+        fn without_original_code_comments_are_not_written() {
+            let code = "-- comment\nlocal a = 1 -- trailing\n";
+            let block = crate::Parser::default()
+                .preserve_tokens()
+                .parse(code)
+                .unwrap();
+
+            let mut generator = ReadableLuaGenerator::default();
+            generator.write_block(&block);
+
+            assert_eq!(generator.into_string(), "local a = 1\n");
+        }
+    }
+
+    mod readable_wrapping {
+        use super::*;
+
+        macro_rules! snapshot_readable {
+            ($($name:ident => $code:expr),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let parser = crate::Parser::default();
+                        let block = parser.parse($code).unwrap();
+
+                        let mut generator = ReadableLuaGenerator::new(80);
+                        generator.write_block(&block);
+                        let generated_code = generator.into_string();
+
+                        assert!(
+                            generated_code.lines().all(|line| line.len() <= 80),
+                            "line exceeds column span in:\n{}",
+                            generated_code
+                        );
+                        pretty_assertions::assert_eq!(
+                            block,
+                            parser.parse(&generated_code).unwrap()
+                        );
+
+                        insta::assert_snapshot!(
+                            concat!("readable_wrapping_", stringify!($name)),
+                            generated_code
+                        );
+                    }
+                )*
+            };
+        }
+
+        snapshot_readable!(
+            table_with_twenty_entries => "return { \
+                alpha = 1, bravo = 2, charlie = 3, delta = 4, echo = 5, foxtrot = 6, golf = 7, \
+                hotel = 8, india = 9, juliett = 10, kilo = 11, lima = 12, mike = 13, \
+                november = 14, oscar = 15, papa = 16, quebec = 17, romeo = 18, sierra = 19, \
+                tango = 20 }",
+            nested_tables => "return { \
+                first = { 'a long string value', 'another long string value', 'one more' }, \
+                second = { value = true } }",
+            short_call => "print('hello', 'world')",
+            call_with_long_string_arguments => "print(\
+                'this is a rather long string argument', \
+                'and another one that makes the call too long')",
+            method_call_with_long_arguments => "object:method(\
+                firstArgumentIdentifier, secondArgumentIdentifier, thirdArgumentIdentifier)",
+            nested_calls_with_long_arguments => "outer(inner(\
+                'this is a rather long string argument', \
+                'and another one that makes the call too long'), 'last')",
+            short_table_near_column_span => "local someLongVariableNameToPushTheTable = \
+                callSomething(variable, { first, second, third })",
+            condition_with_eight_terms => "if player.isAlive and player.health > 0 \
+                and not player.isStunned and player.team == 'red' and player.x > minX \
+                and player.x < maxX and (player.level >= 10 or player.isAdmin) \
+                and hasPermission(player, 'move') then print('move') end",
+            concat_with_six_terms => "local message = 'Player ' .. player.name \
+                .. ' reached the level ' .. tostring(player.level) .. ' in the team ' \
+                .. player.team",
+            short_condition => "if a and b or not c then print(a) end",
+            while_with_long_condition => "while not finished and attempts < maxAttempts \
+                and (lastError == nil or isRecoverable(lastError)) do attempts += 1 end",
+            nested_chains_in_parentheses => "return (firstCondition or secondCondition) \
+                and (thirdConditionWithLongName or fourthConditionWithLongName) \
+                and (fifthCondition or sixthCondition)",
+            mixed_arithmetic_operators => "local total = baseAmount + bonusAmount - \
+                penaltyAmount + adjustmentAmount - taxAmount + someOtherLongAmountName",
+        );
+
+        fn generate_within_time_limit(code: &str) -> String {
+            let parser = crate::Parser::default();
+            let block = parser.parse(code).unwrap();
+
+            let start = std::time::Instant::now();
+            let mut generator = ReadableLuaGenerator::new(80);
+            generator.write_block(&block);
+            let generated_code = generator.into_string();
+            let elapsed = start.elapsed();
+
+            assert!(
+                elapsed < std::time::Duration::from_secs(2),
+                "generation took {:?}",
+                elapsed
+            );
+            pretty_assertions::assert_eq!(block, parser.parse(&generated_code).unwrap());
+
+            generated_code
+        }
+
+        fn generate_binary_chain(operator: &str, terms: usize) -> String {
+            let operands: Vec<_> = (0..terms).map(|i| format!("value{}", i)).collect();
+            let generated_code =
+                generate_within_time_limit(&format!("return {}", operands.join(operator)));
+
+            assert!(generated_code.lines().all(|line| line.len() <= 80));
+
+            generated_code
+        }
+
+        #[test]
+        fn long_concat_chain() {
+            let generated_code = generate_binary_chain(" .. ", 50);
+
+            pretty_assertions::assert_eq!(generated_code.lines().count(), 50);
+        }
+
+        #[test]
+        fn long_and_chain() {
+            generate_binary_chain(" and ", 50);
+        }
+
+        #[test]
+        fn long_addition_chain() {
+            generate_binary_chain(" + ", 50);
+        }
+
+        #[test]
+        fn deeply_nested_tables_and_calls() {
+            let depth = 30;
+            generate_within_time_limit(&format!(
+                "return {}value{}",
+                "call({ ".repeat(depth),
+                " })".repeat(depth)
+            ));
+        }
+
+        #[test]
+        fn deeply_nested_callbacks() {
+            let depth = 25;
+            generate_within_time_limit(&format!(
+                "{}print('done'){}",
+                "run(function() ".repeat(depth),
+                " end)".repeat(depth)
+            ));
+        }
+    }
+
+    mod dense_semicolons {
+        use super::*;
+
+        fn ambiguous_block() -> nodes::Block {
+            nodes::Block::default()
+                .with_statement(
+                    nodes::LocalAssignStatement::from_variable("a")
+                        .with_value(nodes::Expression::identifier("b")),
+                )
+                .with_statement(nodes::FunctionCall::from_prefix(
+                    nodes::ParentheseExpression::new(nodes::Expression::identifier("f")),
+                ))
+                .with_statement(
+                    nodes::FunctionCall::from_name("print")
+                        .with_argument(nodes::Expression::identifier("a")),
+                )
+                .with_last_statement(nodes::ReturnStatement::one(nodes::Expression::identifier(
+                    "a",
+                )))
+        }
+
+        fn generate(semicolon_style: SemicolonStyle) -> String {
+            let mut generator = DenseLuaGenerator::default().with_semicolon_style(semicolon_style);
+            generator.write_block(&ambiguous_block());
+            generator.into_string()
+        }
+
+        #[test]
+        fn ambiguous_only() {
+            assert_eq!(
+                generate(SemicolonStyle::AmbiguousOnly),
+                "local a=b;(f)()print(a)return a"
+            );
+        }
+
+        #[test]
+        fn always() {
+            assert_eq!(
+                generate(SemicolonStyle::Always),
+                "local a=b;(f)();print(a);return a;"
+            );
+        }
+
+        #[test]
+        fn always_in_nested_blocks() {
+            let block = nodes::Block::default()
+                .with_statement(nodes::DoStatement::new(ambiguous_block()))
+                .with_statement(nodes::WhileStatement::new(
+                    nodes::Block::default().with_last_statement(nodes::LastStatement::new_break()),
+                    true,
+                ));
+
+            let mut generator =
+                DenseLuaGenerator::default().with_semicolon_style(SemicolonStyle::Always);
+            generator.write_block(&block);
+
+            assert_eq!(
+                generator.into_string(),
+                "do local a=b;(f)();print(a);return a;end;while true do break;end;"
+            );
+        }
+    }
+
+    mod dense_attributes {
+        use super::*;
+
+        fn generate(code: &str) -> String {
+            let block = crate::Parser::default()
+                .with_dialect(crate::LuaDialect::Lua54)
+                .parse(code)
+                .unwrap();
+            let mut generator = DenseLuaGenerator::default();
+            generator.write_block(&block);
+            generator.into_string()
+        }
+
+        #[test]
+        fn separates_attribute_from_equal_sign() {
+            assert_eq!(generate("local x <const> = 1"), "local x<const> =1");
+        }
+
+        #[test]
+        fn writes_attributes_next_to_commas() {
+            assert_eq!(
+                generate("local a <const>, b <close> = 1, f()"),
+                "local a<const>,b<close> =1,f()"
+            );
+        }
+
+        #[test]
+        fn writes_attribute_without_value() {
+            assert_eq!(generate("local a <const> local b"), "local a<const>local b");
+        }
+    }
+
+    mod dense_redundant_parentheses {
+        use super::*;
+
+        use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
+
+        fn generate(code: &str) -> String {
+            let block = crate::Parser::default().parse(code).unwrap();
+            let mut generator =
+                DenseLuaGenerator::default().with_remove_redundant_parentheses(true);
+            generator.write_block(&block);
+            generator.into_string()
+        }
+
+        macro_rules! test_output {
+            ($($name:ident($code:literal) => $expected:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(generate($code), $expected);
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            doubled_parentheses("return ((x))") => "return x",
+            if_condition("if (a) then end") => "if a then end",
+            elseif_condition("if a then elseif (b) then end") => "if a then elseif b then end",
+            while_condition("while (a and b) do end") => "while a and b do end",
+            repeat_condition("repeat until (a)") => "repeat until a",
+            call_in_condition("if (f()) then end") => "if f()then end",
+            if_expression_in_condition("if (if a then b else c) then end")
+                => "if(if a then b else c)then end",
+            literal_arguments("print((1), ('a'), ({}))") => "print(1,'a',{})",
+            table_entries("local t = { (a), [(b)] = (c) }") => "local t={a,[b]=c}",
+            higher_precedence_operand("return (a * b) + c") => "return a*b+c",
+            lower_precedence_operand("return (a + b) * c") => "return(a+b)*c",
+            doubled_lower_precedence_operand("return ((a + b)) * c") => "return(a+b)*c",
+            right_associative_operand("return a .. (b .. c)") => "return a..b..c",
+            left_operand_of_right_associative_operator("return (a .. b) .. c")
+                => "return(a..b)..c",
+            call_operand("return (f()) + 1") => "return f()+1",
+            number_operand_before_concat("return (1) .. x") => "return 1 ..x",
+            unary_operand("return -(a)") => "return-a",
+            unary_right_operand("return a - (-b)") => "return a- -b",
+            unary_left_operand_of_caret("return (-x) ^ 2") => "return(-x)^2",
+            binary_in_unary_operand("return not (a == b)") => "return not(a==b)",
+            if_expression_operand("return a + (if b then c else d) + e")
+                => "return a+(if b then c else d)+e",
+            type_cast_operand("return (a :: T) < b") => "return(a::T)<b",
+            call_in_return("return (f())") => "return(f())",
+            doubled_call_in_return("return ((f()))") => "return(f())",
+            call_in_local_assign("local a, b = (f())") => "local a,b=(f())",
+            call_in_table_entry("local t = { a = (f()) }") => "local t={a=(f())}",
+            variable_arguments("local function f(...) return (...) end")
+                => "local function f(...)return(...)end",
+            identifier_prefix("(f)()") => "f()",
+            call_prefix("return (f()).x") => "return f().x",
+            string_prefix("return ('a'):upper()") => "return('a'):upper()",
+            doubled_string_prefix("return (('a')):upper()") => "return('a'):upper()",
+        );
+
+        #[test]
+        fn parentheses_are_kept_by_default() {
+            let block = crate::Parser::default()
+                .parse("return ((x)) + (y)")
+                .unwrap();
+            let mut generator = DenseLuaGenerator::default();
+            generator.write_block(&block);
+
+            assert_eq!(generator.into_string(), "return((x))+(y)");
+        }
+
+        // removes the parentheses that only group expressions, since the tree already
+        // encodes the precedence, and keeps the ones that truncate multiple values
+        struct NormalizeParentheses;
+
+        fn unwrap_parentheses(expression: &mut nodes::Expression) {
+            if let nodes::Expression::Parenthese(parenthese) = expression {
+                *expression = parenthese.innermost_expression().clone();
+            }
+        }
+
+        impl NodeProcessor for NormalizeParentheses {
+            fn process_expression(&mut self, expression: &mut nodes::Expression) {
+                if let nodes::Expression::Parenthese(parenthese) = expression {
+                    let inner = parenthese.innermost_expression().clone();
+                    *expression = match inner {
+                        nodes::Expression::Call(_) | nodes::Expression::VariableArguments(_) => {
+                            nodes::ParentheseExpression::new(inner).into()
+                        }
+                        _ => inner,
+                    };
+                }
+            }
+
+            fn process_prefix_expression(&mut self, prefix: &mut nodes::Prefix) {
+                if let nodes::Prefix::Parenthese(parenthese) = prefix {
+                    *prefix = parenthese.innermost_expression().clone().into();
+                }
+            }
+
+            fn process_binary_expression(&mut self, binary: &mut nodes::BinaryExpression) {
+                unwrap_parentheses(binary.mutate_left());
+                unwrap_parentheses(binary.mutate_right());
+            }
+
+            fn process_if_statement(&mut self, statement: &mut nodes::IfStatement) {
+                for branch in statement.mutate_branches() {
+                    unwrap_parentheses(branch.mutate_condition());
+                }
+            }
+
+            fn process_while_statement(&mut self, statement: &mut nodes::WhileStatement) {
+                unwrap_parentheses(statement.mutate_condition());
+            }
+
+            fn process_repeat_statement(&mut self, statement: &mut nodes::RepeatStatement) {
+                unwrap_parentheses(statement.mutate_condition());
+            }
+        }
+
+        fn normalize(mut block: nodes::Block) -> nodes::Block {
+            DefaultVisitor::visit_block(&mut block, &mut NormalizeParentheses);
+            block
+        }
+
+        #[test]
+        fn simplified_code_parses_to_an_equivalent_tree() {
+            let parser = crate::Parser::default();
+            let codes = [
+                "return ((a)) + ((b * c)) - (d - e) - ((f - g))",
+                "return (a or b) and (c or (d and e)), (not (a)) == ((b))",
+                "return (a .. b) .. (c .. (d .. e)), (a ^ b) ^ (c ^ d), (-a) ^ (-b)",
+                "return (a + (if b then c else d)) + e, (a :: number) < b",
+                "return (f()) + (g()), (f()), ((...)), (f()).x, ((g))(), (('s')):rep(2)",
+                "local t = { ((a)), [((b))] = (c), d = (f()), ({}) }",
+                "if ((a)) then elseif (b == c) then end while (f()) do end repeat until (a or b)",
+                "x = ((a))[((b))]; (f)(); ((g)).h = (1) .. (2)",
+            ];
+
+            for code in codes {
+                let block = parser.parse(code).unwrap();
+
+                let mut generator =
+                    DenseLuaGenerator::default().with_remove_redundant_parentheses(true);
+                generator.write_block(&block);
+                let generated_code = generator.into_string();
+
+                let generated_block = parser.parse(&generated_code).unwrap_or_else(|_| {
+                    panic!("unable to parse generated code `{}`", generated_code)
+                });
+
+                pretty_assertions::assert_eq!(
+                    normalize(generated_block),
+                    normalize(block),
+                    "`{}` generated `{}`",
+                    code,
+                    generated_code
+                );
+            }
+        }
+    }
+
+    mod include_types {
+        use super::*;
+
+        const TYPED_CODE: &str = "export type Point<T = number> = { x: T, y: T }\n\
+            type Callback = (Point) -> ()\n\
+            local origin: Point = { x = 0, y = 0 }\n\
+            local function distance<T>(a: Point<T>, b: Point<T>, ...: number): number\n    \
+                local dx, dy: number = b.x - a.x, b.y - a.y\n    \
+                return math.sqrt(dx * dx + dy * dy)\n\
+            end\n\
+            local function first(...: any): any\n    \
+                return (... :: any)\n\
+            end\n\
+            for index: number, value: string in ipairs(list) do\n    \
+                print((value :: any) :: string, (index + 1) :: number, first() :: any)\n\
+            end\n\
+            return function<T...>(callback: Callback): (boolean, T...)\n    \
+                return pcall(callback :: any)\n\
+            end\n";
+
+        macro_rules! snapshot_include_types {
+            ($($name:ident => $generator:expr),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let block = crate::Parser::default().parse(TYPED_CODE).unwrap();
+
+                        let mut generator = $generator;
+                        generator.write_block(&block);
+                        let generated_code = generator.into_string();
+
+                        crate::Parser::default()
+                            .parse(&generated_code)
+                            .unwrap_or_else(|_| panic!("unable to parse generated code `{}`", generated_code));
+
+                        insta::assert_snapshot!(
+                            concat!("include_types_", stringify!($name)),
+                            generated_code
+                        );
+                    }
+                )*
+            };
+        }
+
+        snapshot_include_types!(
+            dense_with_types => DenseLuaGenerator::default().with_include_types(true),
+            dense_without_types => DenseLuaGenerator::default().with_include_types(false),
+            readable_with_types => ReadableLuaGenerator::default().with_include_types(true),
+            readable_without_types => ReadableLuaGenerator::default().with_include_types(false),
+        );
+    }
+
+    mod operand_adjacency {
+        use super::*;
+
+        const OPERANDS: &[&str] = &[
+            "1", "1.", ".5", "1.5", "1e5", "0x2", "0xA", "0b1", "a", "a2", "_1", "...", "'s'",
+            "[[s]]", "-1", "- -1", "(1)", "f()", "t.a2", "t[1]", "a2:m()", "{}", "#t", "not a",
+        ];
+
+        const OPERATORS: &[&str] = &[
+            "..", "+", "-", "*", "/", "//", "%", "^", "==", "~=", "<", "<=", ">", ">=", "and", "or",
+        ];
+
+        fn assert_round_trip<G: LuaGenerator>(create_generator: impl Fn() -> G) {
+            let parser = crate::Parser::default();
+            let mut failures = Vec::new();
+
+            for left in OPERANDS {
+                for operator in OPERATORS {
+                    for right in OPERANDS {
+                        let code =
+                            format!("return function(...) return {left} {operator} {right} end");
+                        let expected_block = parser
+                            .parse(&code)
+                            .unwrap_or_else(|_| panic!("unable to parse `{}`", code));
+
+                        let mut generator = create_generator();
+                        generator.write_block(&expected_block);
+                        let generated_code = generator.into_string();
+
+                        match parser.parse(&generated_code) {
+                            Ok(generated_block) if generated_block == expected_block => {}
+                            _ => failures.push(format!("`{}` => `{}`", code, generated_code)),
+                        }
+                    }
+                }
+            }
+
+            assert!(failures.is_empty(), "{}", failures.join("\n"));
+        }
+
+        #[test]
+        fn dense_generator_round_trips_binary_operands() {
+            assert_round_trip(DenseLuaGenerator::default);
+        }
+
+        #[test]
+        fn dense_generator_with_small_column_span_round_trips_binary_operands() {
+            assert_round_trip(|| DenseLuaGenerator::new(1));
+        }
+
+        #[test]
+        fn readable_generator_round_trips_binary_operands() {
+            assert_round_trip(ReadableLuaGenerator::default);
+        }
+    }
+
     snapshot_generator!(dense, DenseLuaGenerator::default());
     snapshot_generator!(readable, ReadableLuaGenerator::default());
     snapshot_generator!(token_based, TokenBasedLuaGenerator::new(""));
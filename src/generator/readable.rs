@@ -1,6 +1,11 @@
-use crate::generator::{utils, LuaGenerator};
+use crate::generator::{
+    source_map::{OriginalPosition, SourceMapBuilder},
+    utils, Indentation, LongStringThreshold, LuaGenerator, QuoteStyle, SourceMap, UnicodeEscape,
+};
 use crate::nodes;
 
+use std::collections::HashMap;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum StatementType {
     Assign,
@@ -9,7 +14,9 @@ enum StatementType {
     CompoundAssign,
     Function,
     GenericFor,
+    Goto,
     If,
+    Label,
     LocalAssign,
     LocalFunction,
     NumericFor,
@@ -31,7 +38,9 @@ impl From<&nodes::Statement> for StatementType {
             CompoundAssign(_) => Self::CompoundAssign,
             Function(_) => Self::Function,
             GenericFor(_) => Self::GenericFor,
+            Goto(_) => Self::Goto,
             If(_) => Self::If,
+            Label(_) => Self::Label,
             LocalAssign(_) => Self::LocalAssign,
             LocalFunction(_) => Self::LocalFunction,
             NumericFor(_) => Self::NumericFor,
@@ -58,24 +67,188 @@ impl From<&nodes::LastStatement> for StatementType {
 #[derive(Debug, Clone)]
 pub struct ReadableLuaGenerator {
     column_span: usize,
-    indentation: usize,
+    indentation: String,
+    quote_style: QuoteStyle,
+    long_string_threshold: LongStringThreshold,
+    prefer_hex_above: Option<u64>,
+    unicode_escape: UnicodeEscape,
+    include_types: bool,
+    source_map: Option<SourceMapBuilder>,
+    original_code: Option<String>,
     current_line_length: usize,
     current_indentation: usize,
     output: String,
     last_push_length: usize,
     can_add_new_line_stack: Vec<bool>,
+    inline_widths: HashMap<MeasuredNode, usize>,
+}
+
+/// Identifies a node (by its address) for which the length of its inline form is
+/// needed to decide if it fits on the current line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MeasuredNode {
+    Binary(usize),
+    TableEntries(usize),
+    TupleArguments(usize),
+}
+
+impl MeasuredNode {
+    fn binary(binary: &nodes::BinaryExpression) -> Self {
+        Self::Binary(binary as *const nodes::BinaryExpression as usize)
+    }
+
+    fn table_entries(entries: &[nodes::TableEntry]) -> Self {
+        Self::TableEntries(entries.as_ptr() as usize)
+    }
+
+    fn tuple_arguments(arguments: &nodes::TupleArguments) -> Self {
+        Self::TupleArguments(arguments as *const nodes::TupleArguments as usize)
+    }
 }
 
+// a column span large enough to never wrap, while leaving room to add lengths to it
+const UNLIMITED_COLUMN_SPAN: usize = usize::MAX / 2;
+
 impl ReadableLuaGenerator {
     pub fn new(column_span: usize) -> Self {
         Self {
             column_span,
-            indentation: 4,
+            indentation: Indentation::default().to_unit_string(),
+            quote_style: QuoteStyle::default(),
+            long_string_threshold: LongStringThreshold::default(),
+            prefer_hex_above: None,
+            unicode_escape: UnicodeEscape::default(),
+            include_types: true,
+            source_map: None,
+            original_code: None,
             current_line_length: 0,
             current_indentation: 0,
             output: String::new(),
             last_push_length: 0,
             can_add_new_line_stack: Vec::new(),
+            inline_widths: HashMap::new(),
+        }
+    }
+
+    /// Sets the indentation unit written for each nested level.
+    pub fn with_indentation(mut self, indentation: Indentation) -> Self {
+        self.indentation = indentation.to_unit_string();
+        self
+    }
+
+    /// Sets the quote character preferred when writing quoted strings.
+    pub fn with_quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Sets when strings are written with long brackets instead of quotes.
+    pub fn with_long_string_threshold(mut self, threshold: LongStringThreshold) -> Self {
+        self.long_string_threshold = threshold;
+        self
+    }
+
+    /// Writes decimal integers greater than the given value using the hexadecimal
+    /// notation.
+    pub fn with_prefer_hex_above(mut self, value: u64) -> Self {
+        self.prefer_hex_above = Some(value);
+        self
+    }
+
+    /// Sets how non-ASCII characters are escaped in strings.
+    pub fn with_unicode_escape(mut self, unicode_escape: UnicodeEscape) -> Self {
+        self.unicode_escape = unicode_escape;
+        self
+    }
+
+    /// Sets if Luau type annotations are written. When types are not included, type
+    /// annotations, generic parameters and type declarations are not written and type
+    /// casts are replaced by their inner expression.
+    pub fn with_include_types(mut self, include_types: bool) -> Self {
+        self.include_types = include_types;
+        self
+    }
+
+    /// Records the original positions of the written statements, which can be obtained
+    /// with [`into_string_with_source_map`](Self::into_string_with_source_map). The block
+    /// must be parsed with tokens and the original code is needed to compute the original
+    /// columns.
+    pub fn with_source_map(mut self, original_code: impl Into<String>) -> Self {
+        self.source_map = Some(SourceMapBuilder::new(original_code));
+        self
+    }
+
+    /// Consumes the generator and produce the generated code with its source map. The
+    /// source map is empty if the generator was not created
+    /// [`with_source_map`](Self::with_source_map).
+    pub fn into_string_with_source_map(self) -> (String, SourceMap) {
+        let output = self.output;
+        let source_map = self
+            .source_map
+            .map(|builder| builder.build(&output))
+            .unwrap_or_default();
+        (output, source_map)
+    }
+
+    fn enter_source_position(&mut self, token: Option<&nodes::Token>) -> Option<OriginalPosition> {
+        let offset = self.output.len();
+        self.source_map
+            .as_mut()
+            .and_then(|source_map| source_map.enter(offset, token))
+    }
+
+    fn exit_source_position(&mut self, previous: Option<OriginalPosition>) {
+        if let Some(source_map) = self.source_map.as_mut() {
+            source_map.exit(previous);
+        }
+    }
+
+    /// Writes the comments attached to the tokens of statements. Only comments before a
+    /// statement and comments at the end of its last line are written, other comments
+    /// (like comments inside expressions) are dropped. The original code is needed to
+    /// read the content of the comments.
+    pub fn with_comments(mut self, original_code: impl Into<String>) -> Self {
+        self.original_code = Some(original_code.into());
+        self
+    }
+
+    fn write_leading_comments(&mut self, token: Option<&nodes::Token>) {
+        let comments = self.read_comments(
+            token
+                .into_iter()
+                .flat_map(nodes::Token::iter_leading_trivia),
+        );
+
+        for comment in comments {
+            if self.current_line_length == 0 && self.current_indentation != 0 {
+                self.write_indentation();
+            }
+            self.raw_push_str(&comment);
+            self.push_new_line();
+        }
+    }
+
+    fn write_trailing_comments(&mut self, token: Option<&nodes::Token>) {
+        let comments = self.read_comments(
+            token
+                .into_iter()
+                .flat_map(nodes::Token::iter_trailing_trivia),
+        );
+
+        for comment in comments {
+            self.push_space();
+            self.raw_push_str(&comment);
+        }
+    }
+
+    fn read_comments<'a>(&self, trivia: impl Iterator<Item = &'a nodes::Trivia>) -> Vec<String> {
+        if let Some(code) = self.original_code.as_deref() {
+            trivia
+                .filter(|trivia| trivia.kind() == nodes::TriviaKind::Comment)
+                .map(|trivia| trivia.read(code).trim_end().to_owned())
+                .collect()
+        } else {
+            Vec::new()
         }
     }
 
@@ -106,7 +279,7 @@ impl ReadableLuaGenerator {
 
     #[inline]
     fn write_indentation(&mut self) {
-        let indentation = " ".repeat(self.indentation * self.current_indentation);
+        let indentation = self.indentation.repeat(self.current_indentation);
         self.raw_push_str(&indentation);
     }
 
@@ -169,9 +342,14 @@ impl ReadableLuaGenerator {
     }
 
     #[inline]
-    fn indent_and_write_block(&mut self, block: &nodes::Block) {
+    fn indent_and_write_block(
+        &mut self,
+        block: &nodes::Block,
+        closing_token: Option<&nodes::Token>,
+    ) {
         self.push_indentation();
         self.write_block(block);
+        self.write_leading_comments(closing_token);
         self.pop_indentation();
     }
 
@@ -243,7 +421,83 @@ impl ReadableLuaGenerator {
             .unwrap_or("")
     }
 
-    fn table_fits_on_line(&self, entries: &[nodes::TableEntry], _width: usize) -> bool {
+    /// Returns the length of the first line of a node written without any column limit,
+    /// and if that length had to be measured (instead of being already known).
+    ///
+    /// The node is written once with a generator without any column limit, which records
+    /// the inline length of every nested node it writes. These lengths are kept until the
+    /// node that was measured is completely written (see `forget_inline_widths`), so that
+    /// nested nodes are never written again only to be measured.
+    fn inline_width<F>(&mut self, node: MeasuredNode, write: F) -> (usize, bool)
+    where
+        F: FnOnce(&mut Self),
+    {
+        if let Some(width) = self.inline_widths.get(&node) {
+            return (*width, false);
+        }
+
+        let mut generator = self.unlimited_generator();
+        write(&mut generator);
+
+        let width = first_line_length(&generator.output);
+        self.inline_widths.extend(generator.inline_widths);
+        self.inline_widths.insert(node, width);
+
+        (width, true)
+    }
+
+    /// Once a measured node is written, the lengths recorded for it (and its nested
+    /// nodes) are not needed anymore. They must not be kept because another node
+    /// could later be found at the same address.
+    #[inline]
+    fn forget_inline_widths(&mut self, measured: bool) {
+        if measured {
+            self.inline_widths.clear();
+        }
+    }
+
+    fn unlimited_generator(&self) -> Self {
+        Self {
+            column_span: UNLIMITED_COLUMN_SPAN,
+            indentation: self.indentation.clone(),
+            quote_style: self.quote_style,
+            long_string_threshold: self.long_string_threshold,
+            prefer_hex_above: self.prefer_hex_above,
+            unicode_escape: self.unicode_escape,
+            include_types: self.include_types,
+            source_map: None,
+            original_code: None,
+            current_line_length: 0,
+            current_indentation: 0,
+            output: String::new(),
+            last_push_length: 0,
+            can_add_new_line_stack: Vec::new(),
+            inline_widths: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    fn is_measuring(&self) -> bool {
+        self.column_span == UNLIMITED_COLUMN_SPAN
+    }
+
+    /// Writes a node inline. While measuring another node, its length is recorded so
+    /// that it does not have to be measured again.
+    fn write_inline<F>(&mut self, node: MeasuredNode, write: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        if self.is_measuring() {
+            let start = self.output.len();
+            write(self);
+            let width = first_line_length(self.output[start..].trim_start_matches(' '));
+            self.inline_widths.insert(node, width);
+        } else {
+            write(self);
+        }
+    }
+
+    fn is_small_table(&self, entries: &[nodes::TableEntry]) -> bool {
         use nodes::TableEntry;
 
         // small list of simple expressions
@@ -272,15 +526,108 @@ impl ReadableLuaGenerator {
         }
     }
 
+    /// Writes a condition followed by the given keyword (like `then` or `do`). When the
+    /// condition is split on multiple lines, the keyword is written on its own line.
+    fn write_condition(&mut self, condition: &nodes::Expression, keyword: &str) {
+        let start = self.output.len();
+
+        self.write_expression(condition);
+
+        if self.output[start..].contains('\n') {
+            self.push_new_line();
+            self.write_indentation();
+        } else {
+            self.raw_push_char(' ');
+        }
+        self.raw_push_str(keyword);
+    }
+
+    fn write_binary_expression_inline(&mut self, binary: &nodes::BinaryExpression) {
+        let operator = binary.operator();
+        let left = binary.left();
+        let right = binary.right();
+
+        self.write_binary_operand(left, operator.left_needs_parentheses(left));
+
+        self.push_space();
+        self.push_str(operator.to_str());
+        self.push_space();
+
+        self.write_binary_operand(right, operator.right_needs_parentheses(right));
+    }
+
+    /// Writes a chain of binary expressions with one operand per line.
+    fn write_binary_chain(&mut self, binary: &nodes::BinaryExpression) {
+        let mut operands = Vec::new();
+        let mut operators = Vec::new();
+        collect_binary_chain(binary, &mut operands, &mut operators);
+
+        let (first_operand, first_needs_parentheses) = operands[0];
+        self.write_binary_operand(first_operand, first_needs_parentheses);
+
+        self.push_indentation();
+        self.push_can_add_new_line(false);
+
+        for (operator, (operand, needs_parentheses)) in
+            operators.into_iter().zip(operands.into_iter().skip(1))
+        {
+            self.raw_push_char(' ');
+            self.raw_push_str(operator.to_str());
+            self.push_new_line();
+            self.write_indentation();
+            self.write_binary_operand(operand, needs_parentheses);
+        }
+
+        self.pop_can_add_new_line();
+        self.pop_indentation();
+    }
+
+    fn write_binary_operand(&mut self, operand: &nodes::Expression, needs_parentheses: bool) {
+        if needs_parentheses {
+            self.write_expression_in_parentheses(operand);
+        } else {
+            self.write_expression(operand);
+        }
+    }
+
+    fn write_table_entries_inline(&mut self, entries: &[nodes::TableEntry]) {
+        let last_index = entries.len().saturating_sub(1);
+
+        entries.iter().enumerate().for_each(|(index, entry)| {
+            self.write_table_entry(entry);
+
+            if index != last_index {
+                self.raw_push_char(',');
+                self.raw_push_char(' ');
+            }
+        });
+    }
+
+    fn write_tuple_arguments_inline(&mut self, arguments: &nodes::TupleArguments) {
+        let last_index = arguments.len().saturating_sub(1);
+        arguments
+            .iter_values()
+            .enumerate()
+            .for_each(|(index, expression)| {
+                self.write_expression(expression);
+
+                if index != last_index {
+                    self.raw_push_char(',');
+                    self.raw_push_char(' ');
+                }
+            });
+    }
+
     fn write_function_parameters(
         &mut self,
         parameters: &[nodes::TypedIdentifier],
         is_variadic: bool,
         variadic_type: Option<&nodes::FunctionVariadicType>,
     ) {
+        let variadic_type = variadic_type.filter(|_| self.include_types);
         let mut parameters_length = parameters.iter().fold(0, |acc, parameter| {
             acc + parameter.get_name().len()
-                + if parameter.has_type() {
+                + if parameter.has_type() && self.include_types {
                     // put a random estimation of the type probable length
                     10
                 } else {
@@ -370,7 +717,14 @@ impl ReadableLuaGenerator {
     fn write_typed_identifier(&mut self, typed_identifier: &nodes::TypedIdentifier) {
         self.push_str(typed_identifier.get_name());
 
-        if let Some(r#type) = typed_identifier.get_type() {
+        if let Some(attribute) = typed_identifier.get_attribute() {
+            self.push_space();
+            self.push_char('<');
+            self.push_str(attribute.as_str());
+            self.push_char('>');
+        }
+
+        if let Some(r#type) = typed_identifier.get_type().filter(|_| self.include_types) {
             self.push_char(':');
             self.push_space();
             self.write_type(r#type);
@@ -442,19 +796,80 @@ impl Default for ReadableLuaGenerator {
     }
 }
 
+/// Flattens a chain of binary expressions with operators of the same precedence (like
+/// `a and b and c` or `a .. b .. c`) into its operands and operators. Operands that are
+/// wrapped in parentheses are kept as a single operand.
+fn collect_binary_chain<'a>(
+    binary: &'a nodes::BinaryExpression,
+    operands: &mut Vec<(&'a nodes::Expression, bool)>,
+    operators: &mut Vec<nodes::BinaryOperator>,
+) {
+    let operator = binary.operator();
+    let left = binary.left();
+    let right = binary.right();
+
+    let left_needs_parentheses = operator.left_needs_parentheses(left);
+    match left {
+        nodes::Expression::Binary(left)
+            if !left_needs_parentheses && has_same_precedence(left.operator(), operator) =>
+        {
+            collect_binary_chain(left, operands, operators);
+        }
+        _ => operands.push((left, left_needs_parentheses)),
+    }
+
+    operators.push(operator);
+
+    let right_needs_parentheses = operator.right_needs_parentheses(right);
+    match right {
+        nodes::Expression::Binary(right)
+            if !right_needs_parentheses && has_same_precedence(right.operator(), operator) =>
+        {
+            collect_binary_chain(right, operands, operators);
+        }
+        _ => operands.push((right, right_needs_parentheses)),
+    }
+}
+
+fn first_line_length(content: &str) -> usize {
+    content.lines().next().map(str::len).unwrap_or_default()
+}
+
+#[inline]
+fn has_same_precedence(a: nodes::BinaryOperator, b: nodes::BinaryOperator) -> bool {
+    !a.precedes(b) && !b.precedes(a)
+}
+
 impl LuaGenerator for ReadableLuaGenerator {
     fn into_string(self) -> String {
         self.output
     }
 
     fn write_block(&mut self, block: &nodes::Block) {
-        let mut statements = block.iter_statements().peekable();
+        // the shebang ends with the same new line as every other line written by this
+        // generator (line endings can be converted with the `line_endings` option)
+        if let Some(shebang) = block.get_shebang() {
+            self.raw_push_str(shebang);
+            self.push_new_line();
+        }
+
+        let include_types = self.include_types;
+        let mut statements = block
+            .iter_statements()
+            .filter(|statement| {
+                include_types || !matches!(statement, nodes::Statement::TypeDeclaration(_))
+            })
+            .peekable();
 
         while let Some(statement) = statements.next() {
             let current_type: StatementType = statement.into();
 
+            self.write_leading_comments(statement.get_first_token());
+
             self.push_can_add_new_line(false);
+            let previous_position = self.enter_source_position(statement.get_first_token());
             self.write_statement(statement);
+            self.exit_source_position(previous_position);
 
             if let Some(next_statement) = statements.peek() {
                 if utils::starts_with_parenthese(next_statement)
@@ -462,7 +877,11 @@ impl LuaGenerator for ReadableLuaGenerator {
                 {
                     self.push_char(';');
                 }
+            }
 
+            self.write_trailing_comments(utils::statement_last_token(statement));
+
+            if let Some(next_statement) = statements.peek() {
                 if current_type != (*next_statement).into() {
                     self.push_new_line();
                 }
@@ -476,9 +895,17 @@ impl LuaGenerator for ReadableLuaGenerator {
             if block.iter_statements().next().is_some() {
                 self.push_new_line();
             }
+            self.write_leading_comments(last_statement.get_first_token());
+            let previous_position = self.enter_source_position(last_statement.get_first_token());
             self.write_last_statement(last_statement);
+            self.exit_source_position(previous_position);
+            self.write_trailing_comments(utils::last_statement_last_token(last_statement));
             self.push_new_line();
         }
+
+        if let Some(tokens) = block.get_tokens() {
+            self.write_leading_comments(tokens.final_token.as_ref());
+        }
     }
 
     fn write_last_statement(&mut self, statement: &nodes::LastStatement) {
@@ -595,7 +1022,10 @@ impl LuaGenerator for ReadableLuaGenerator {
         self.push_str("local function ");
         self.raw_push_str(function.get_name());
 
-        if let Some(generics) = function.get_generic_parameters() {
+        if let Some(generics) = function
+            .get_generic_parameters()
+            .filter(|_| self.include_types)
+        {
             self.write_function_generics(generics);
         }
 
@@ -609,7 +1039,7 @@ impl LuaGenerator for ReadableLuaGenerator {
         );
         self.raw_push_char(')');
 
-        if let Some(return_type) = function.get_return_type() {
+        if let Some(return_type) = function.get_return_type().filter(|_| self.include_types) {
             self.write_function_return_type_suffix(return_type);
         }
 
@@ -619,7 +1049,12 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.raw_push_str(" end");
         } else {
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(
+                block,
+                function
+                    .get_tokens()
+                    .map(|tokens| &tokens.function_body.end),
+            );
             self.push_str("end");
         }
     }
@@ -664,7 +1099,7 @@ impl LuaGenerator for ReadableLuaGenerator {
         } else {
             self.push_str("do");
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(block, generic_for.get_tokens().map(|tokens| &tokens.end));
             self.push_str("end");
         }
     }
@@ -694,7 +1129,7 @@ impl LuaGenerator for ReadableLuaGenerator {
         } else {
             self.push_str("do");
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(block, numeric_for.get_tokens().map(|tokens| &tokens.end));
             self.push_str("end");
         }
     }
@@ -702,6 +1137,10 @@ impl LuaGenerator for ReadableLuaGenerator {
     fn write_if_statement(&mut self, if_statement: &nodes::IfStatement) {
         let branches = if_statement.get_branches();
 
+        let tokens = if_statement.get_tokens();
+        let else_token = tokens.and_then(|tokens| tokens.r#else.as_ref());
+        let end_token = tokens.map(|tokens| &tokens.end);
+
         branches.iter().enumerate().for_each(|(index, branch)| {
             if index == 0 {
                 self.push_str("if ");
@@ -709,16 +1148,20 @@ impl LuaGenerator for ReadableLuaGenerator {
                 self.push_str("elseif ");
             }
 
-            self.write_expression(branch.get_condition());
-            self.raw_push_str(" then");
+            self.write_condition(branch.get_condition(), "then");
             self.push_new_line();
-            self.indent_and_write_block(branch.get_block());
+
+            let closing_token = match branches.get(index + 1) {
+                Some(next_branch) => next_branch.get_tokens().map(|tokens| &tokens.elseif),
+                None => else_token.or(end_token),
+            };
+            self.indent_and_write_block(branch.get_block(), closing_token);
         });
 
         if let Some(else_block) = if_statement.get_else_block() {
             self.push_str("else");
             self.push_new_line();
-            self.indent_and_write_block(else_block);
+            self.indent_and_write_block(else_block, end_token);
         }
 
         self.push_str("end");
@@ -739,7 +1182,10 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.raw_push_str(method.get_name());
         }
 
-        if let Some(generics) = function.get_generic_parameters() {
+        if let Some(generics) = function
+            .get_generic_parameters()
+            .filter(|_| self.include_types)
+        {
             self.write_function_generics(generics);
         }
 
@@ -751,7 +1197,7 @@ impl LuaGenerator for ReadableLuaGenerator {
         );
         self.raw_push_char(')');
 
-        if let Some(return_type) = function.get_return_type() {
+        if let Some(return_type) = function.get_return_type().filter(|_| self.include_types) {
             self.write_function_return_type_suffix(return_type);
         }
 
@@ -761,11 +1207,22 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.raw_push_str(" end");
         } else {
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(block, function.get_tokens().map(|tokens| &tokens.end));
             self.push_str("end");
         }
     }
 
+    fn write_goto_statement(&mut self, goto: &nodes::GotoStatement) {
+        self.push_str("goto ");
+        self.raw_push_str(goto.get_label().get_name());
+    }
+
+    fn write_label_statement(&mut self, label: &nodes::LabelStatement) {
+        self.push_str("::");
+        self.raw_push_str(label.get_name().get_name());
+        self.raw_push_str("::");
+    }
+
     fn write_do_statement(&mut self, do_statement: &nodes::DoStatement) {
         let block = do_statement.get_block();
 
@@ -774,7 +1231,7 @@ impl LuaGenerator for ReadableLuaGenerator {
         } else {
             self.push_str("do");
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(block, do_statement.get_tokens().map(|tokens| &tokens.end));
             self.push_str("end");
         }
     }
@@ -788,7 +1245,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.raw_push_str(" until ");
         } else {
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(block, repeat.get_tokens().map(|tokens| &tokens.until));
             self.push_str("until ");
         }
 
@@ -798,17 +1255,19 @@ impl LuaGenerator for ReadableLuaGenerator {
     fn write_while_statement(&mut self, while_statement: &nodes::WhileStatement) {
         self.push_str("while");
         self.push_can_add_new_line(false);
-        self.write_expression(while_statement.get_condition());
+        self.write_condition(while_statement.get_condition(), "do");
         self.pop_can_add_new_line();
 
         let block = while_statement.get_block();
 
         if block.is_empty() {
-            self.raw_push_str(" do end");
+            self.raw_push_str(" end");
         } else {
-            self.raw_push_str(" do");
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(
+                block,
+                while_statement.get_tokens().map(|tokens| &tokens.end),
+            );
             self.push_str("end");
         }
     }
@@ -888,25 +1347,27 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_binary_expression(&mut self, binary: &nodes::BinaryExpression) {
-        let operator = binary.operator();
-        let left = binary.left();
-        let right = binary.right();
+        let node = MeasuredNode::binary(binary);
 
-        if operator.left_needs_parentheses(left) {
-            self.write_expression_in_parentheses(left);
-        } else {
-            self.write_expression(left);
+        if self.is_measuring() {
+            self.write_inline(node, |generator| {
+                generator.write_binary_expression_inline(binary)
+            });
+            return;
         }
 
-        self.push_space();
-        self.push_str(binary.operator().to_str());
-        self.push_space();
+        let column_space = self.column_span.saturating_sub(self.current_line_length);
+        let (width, measured) = self.inline_width(node, |generator| {
+            generator.write_binary_expression_inline(binary)
+        });
 
-        if operator.right_needs_parentheses(right) {
-            self.write_expression_in_parentheses(right);
+        if width < column_space {
+            self.write_binary_expression_inline(binary);
         } else {
-            self.write_expression(right);
+            self.write_binary_chain(binary);
         }
+
+        self.forget_inline_widths(measured);
     }
 
     fn write_unary_expression(&mut self, unary: &nodes::UnaryExpression) {
@@ -931,7 +1392,10 @@ impl LuaGenerator for ReadableLuaGenerator {
     fn write_function(&mut self, function: &nodes::FunctionExpression) {
         self.push_str("function");
 
-        if let Some(generics) = function.get_generic_parameters() {
+        if let Some(generics) = function
+            .get_generic_parameters()
+            .filter(|_| self.include_types)
+        {
             self.write_function_generics(generics);
         }
 
@@ -945,7 +1409,7 @@ impl LuaGenerator for ReadableLuaGenerator {
         );
         self.raw_push_char(')');
 
-        if let Some(return_type) = function.get_return_type() {
+        if let Some(return_type) = function.get_return_type().filter(|_| self.include_types) {
             self.write_function_return_type_suffix(return_type);
         }
 
@@ -955,7 +1419,7 @@ impl LuaGenerator for ReadableLuaGenerator {
             self.raw_push_str(" end");
         } else {
             self.push_new_line();
-            self.indent_and_write_block(block);
+            self.indent_and_write_block(block, function.get_tokens().map(|tokens| &tokens.end));
             self.push_str("end");
         }
     }
@@ -977,19 +1441,46 @@ impl LuaGenerator for ReadableLuaGenerator {
     fn write_tuple_arguments(&mut self, arguments: &nodes::TupleArguments) {
         self.raw_push_char('(');
 
-        let last_index = arguments.len().saturating_sub(1);
-        arguments
-            .iter_values()
-            .enumerate()
-            .for_each(|(index, expression)| {
-                self.write_expression(expression);
+        let node = MeasuredNode::tuple_arguments(arguments);
+        let column_space = self.column_span.saturating_sub(self.current_line_length);
+        let mut measured = false;
 
-                if index != last_index {
-                    self.raw_push_char(',');
-                    self.raw_push_char(' ');
-                }
+        let fits_on_line = arguments.is_empty() || self.is_measuring() || {
+            let (width, newly_measured) = self.inline_width(node, |generator| {
+                generator.write_tuple_arguments_inline(arguments)
             });
+            measured = newly_measured;
+            width < column_space
+        };
 
+        if fits_on_line {
+            self.write_inline(node, |generator| {
+                generator.write_tuple_arguments_inline(arguments)
+            });
+        } else {
+            self.push_indentation();
+            self.push_can_add_new_line(true);
+
+            let last_index = arguments.len().saturating_sub(1);
+            arguments
+                .iter_values()
+                .enumerate()
+                .for_each(|(index, expression)| {
+                    self.push_new_line();
+                    self.write_indentation();
+                    self.write_expression(expression);
+
+                    if index != last_index {
+                        self.raw_push_char(',');
+                    }
+                });
+
+            self.pop_can_add_new_line();
+            self.pop_indentation();
+            self.push_new_line();
+        }
+
+        self.forget_inline_widths(measured);
         self.push_char(')');
     }
 
@@ -1062,17 +1553,22 @@ impl LuaGenerator for ReadableLuaGenerator {
         if table_len == 0 {
             self.raw_push_char('}');
         } else {
+            let node = MeasuredNode::table_entries(entries);
             let column_space = self.column_span.saturating_sub(self.current_line_length);
-            if self.table_fits_on_line(entries, column_space) {
-                let last_index = table_len.saturating_sub(1);
+            let mut measured = false;
 
-                entries.iter().enumerate().for_each(|(index, entry)| {
-                    self.write_table_entry(entry);
+            let fits_on_line = self.is_small_table(entries)
+                && (self.is_measuring() || {
+                    let (width, newly_measured) = self.inline_width(node, |generator| {
+                        generator.write_table_entries_inline(entries)
+                    });
+                    measured = newly_measured;
+                    width < column_space
+                });
 
-                    if index != last_index {
-                        self.raw_push_char(',');
-                        self.raw_push_char(' ');
-                    }
+            if fits_on_line {
+                self.write_inline(node, |generator| {
+                    generator.write_table_entries_inline(entries)
                 });
             } else {
                 self.push_indentation();
@@ -1089,6 +1585,7 @@ impl LuaGenerator for ReadableLuaGenerator {
                 self.push_new_line();
             }
 
+            self.forget_inline_widths(measured);
             self.push_char('}');
         }
     }
@@ -1113,11 +1610,16 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_number(&mut self, number: &nodes::NumberExpression) {
-        self.push_str(&utils::write_number(number));
+        self.push_str(&utils::write_number(number, self.prefer_hex_above));
     }
 
     fn write_string(&mut self, string: &nodes::StringExpression) {
-        let result = utils::write_string(string.get_value());
+        let result = utils::write_string(
+            string.as_bytes(),
+            self.quote_style,
+            self.long_string_threshold,
+            self.unicode_escape,
+        );
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
@@ -1160,7 +1662,12 @@ impl LuaGenerator for ReadableLuaGenerator {
         self.push_char('(');
         self.push_can_add_new_line(false);
 
-        self.write_expression(parenthese.inner_expression());
+        let inner_expression = parenthese.inner_expression();
+        if self.include_types {
+            self.write_expression(inner_expression);
+        } else {
+            self.write_expression(utils::skip_type_casts(inner_expression));
+        }
 
         self.pop_can_add_new_line();
         self.push_char(')');
@@ -1169,6 +1676,23 @@ impl LuaGenerator for ReadableLuaGenerator {
     fn write_type_cast(&mut self, type_cast: &nodes::TypeCastExpression) {
         let inner_expression = type_cast.get_expression();
 
+        if !self.include_types {
+            let inner_expression = utils::skip_type_casts(inner_expression);
+            // keep parentheses to preserve the precedence and the truncation of
+            // multiple values done by the type cast
+            if nodes::TypeCastExpression::needs_parentheses(inner_expression)
+                || matches!(
+                    inner_expression,
+                    nodes::Expression::Call(_) | nodes::Expression::VariableArguments(_)
+                )
+            {
+                self.write_expression_in_parentheses(inner_expression);
+            } else {
+                self.write_expression(inner_expression);
+            }
+            return;
+        }
+
         if nodes::TypeCastExpression::needs_parentheses(inner_expression) {
             self.push_char('(');
             self.push_can_add_new_line(false);
@@ -1224,7 +1748,12 @@ impl LuaGenerator for ReadableLuaGenerator {
     }
 
     fn write_string_type(&mut self, string_type: &nodes::StringType) {
-        let result = utils::write_string(string_type.get_value());
+        let result = utils::write_string(
+            string_type.as_bytes(),
+            self.quote_style,
+            self.long_string_threshold,
+            self.unicode_escape,
+        );
         if result.starts_with('[') {
             self.push_str_and_break_if(&result, utils::break_long_string);
         } else {
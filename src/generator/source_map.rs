@@ -0,0 +1,347 @@
+use serde::{Deserialize, Serialize};
+
+use crate::nodes::Token;
+
+/// A mapping from a position in the generated code to a position in the original
+/// code. Lines start at 1 and columns (in bytes) start at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceMapping {
+    generated_line: usize,
+    generated_column: usize,
+    original_line: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_column: Option<usize>,
+}
+
+impl SourceMapping {
+    pub fn new(
+        generated_line: usize,
+        generated_column: usize,
+        original_line: usize,
+        original_column: Option<usize>,
+    ) -> Self {
+        Self {
+            generated_line,
+            generated_column,
+            original_line,
+            original_column,
+        }
+    }
+
+    #[inline]
+    pub fn get_generated_line(&self) -> usize {
+        self.generated_line
+    }
+
+    #[inline]
+    pub fn get_generated_column(&self) -> usize {
+        self.generated_column
+    }
+
+    #[inline]
+    pub fn get_original_line(&self) -> usize {
+        self.original_line
+    }
+
+    /// The original column is not known when the token was created with only a
+    /// line number.
+    #[inline]
+    pub fn get_original_column(&self) -> Option<usize> {
+        self.original_column
+    }
+}
+
+/// A list of mappings from the generated code to the original code, ordered by their
+/// generated position. Mappings are recorded for each statement: statements created by
+/// rules (without tokens) are mapped to the position of their closest enclosing statement
+/// that comes from the original code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceMap {
+    mappings: Vec<SourceMapping>,
+}
+
+impl SourceMap {
+    #[inline]
+    pub fn iter_mappings(&self) -> impl Iterator<Item = &SourceMapping> {
+        self.mappings.iter()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Finds the mapping that covers the given generated position, which is the last
+    /// mapping that starts before or at that position.
+    pub fn find_mapping(
+        &self,
+        generated_line: usize,
+        generated_column: usize,
+    ) -> Option<&SourceMapping> {
+        self.mappings.iter().rev().find(|mapping| {
+            (mapping.generated_line, mapping.generated_column) <= (generated_line, generated_column)
+        })
+    }
+
+    /// Serializes the source map to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("unable to serialize source map")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OriginalPosition {
+    line: usize,
+    column: Option<usize>,
+}
+
+/// Records the original positions of nodes while a generator writes them, so that
+/// the generated code can be mapped back once complete.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceMapBuilder {
+    original_code: String,
+    recorded: Vec<(usize, OriginalPosition)>,
+    current: Option<OriginalPosition>,
+}
+
+impl SourceMapBuilder {
+    pub(crate) fn new(original_code: impl Into<String>) -> Self {
+        Self {
+            original_code: original_code.into(),
+            recorded: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// Records the position of a node that starts being written at the given output
+    /// offset. When the token does not have a position, the position of the enclosing
+    /// node is used. The returned value must be given to `exit` once the node is written.
+    pub(crate) fn enter(
+        &mut self,
+        output_offset: usize,
+        token: Option<&Token>,
+    ) -> Option<OriginalPosition> {
+        let previous = self.current;
+
+        if let Some(position) = token.and_then(|token| self.get_position(token)) {
+            self.current = Some(position);
+        }
+
+        if let Some(position) = self.current {
+            self.recorded.push((output_offset, position));
+        }
+
+        previous
+    }
+
+    pub(crate) fn exit(&mut self, previous: Option<OriginalPosition>) {
+        self.current = previous;
+    }
+
+    fn get_position(&self, token: &Token) -> Option<OriginalPosition> {
+        let line = token.get_line_number()?;
+        let column = token.get_start().and_then(|start| {
+            self.original_code
+                .get(..start)
+                .map(|before| start - before.rfind('\n').map(|i| i + 1).unwrap_or(0))
+        });
+
+        Some(OriginalPosition { line, column })
+    }
+
+    /// Converts the recorded output offsets into lines and columns. Since generators can
+    /// write indentation or new lines lazily, mappings point to the first character
+    /// that is not a whitespace at or after the recorded offset.
+    pub(crate) fn build(self, output: &str) -> SourceMap {
+        let mut mappings: Vec<SourceMapping> = Vec::with_capacity(self.recorded.len());
+
+        let mut line = 1;
+        let mut line_start = 0;
+        let mut scanned = 0;
+
+        for (offset, position) in self.recorded {
+            let offset = match output
+                .get(offset..)
+                .and_then(|rest| rest.find(|c: char| !c.is_whitespace()))
+            {
+                Some(skipped) => offset + skipped,
+                None => continue,
+            };
+
+            if offset < scanned {
+                continue;
+            }
+
+            for (index, character) in output[scanned..offset].char_indices() {
+                if character == '\n' {
+                    line += 1;
+                    line_start = scanned + index + 1;
+                }
+            }
+            scanned = offset;
+
+            let mapping =
+                SourceMapping::new(line, offset - line_start, position.line, position.column);
+
+            // nodes starting at the same place are mapped to the innermost one
+            match mappings.last_mut() {
+                Some(last)
+                    if last.generated_line == mapping.generated_line
+                        && last.generated_column == mapping.generated_column =>
+                {
+                    *last = mapping;
+                }
+                _ => mappings.push(mapping),
+            }
+        }
+
+        SourceMap { mappings }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{
+        generator::{DenseLuaGenerator, LuaGenerator, ReadableLuaGenerator},
+        nodes::Block,
+        rules::{ContextBuilder, RemoveContinue, Rule},
+        Parser, Resources,
+    };
+
+    const CODE: &str = "local total = 0\n\
+        for i = 1, 10 do\n    \
+            if i % 2 == 0 then\n        \
+                continue\n    \
+            end\n    \
+            total += i\n\
+        end\n\
+        return total\n";
+
+    fn process_code() -> Block {
+        let mut block = Parser::default().preserve_tokens().parse(CODE).unwrap();
+
+        RemoveContinue::default()
+            .process(
+                &mut block,
+                &ContextBuilder::new(".", &Resources::from_memory(), CODE).build(),
+            )
+            .unwrap();
+
+        block
+    }
+
+    #[test]
+    fn readable_generator_maps_statements_after_remove_continue() {
+        let mut generator = ReadableLuaGenerator::default().with_source_map(CODE);
+        generator.write_block(&process_code());
+        let (code, source_map) = generator.into_string_with_source_map();
+
+        pretty_assertions::assert_eq!(
+            code,
+            "local total = 0\n\
+            \n\
+            for i = 1, 10 do\n    \
+                repeat\n        \
+                    if i % 2 == 0 then\n            \
+                        break\n        \
+                    end\n\
+                    \n        \
+                    total += i\n    \
+                until true\n\
+            end\n\
+            \n\
+            return total\n"
+        );
+        pretty_assertions::assert_eq!(
+            source_map.iter_mappings().copied().collect::<Vec<_>>(),
+            vec![
+                SourceMapping::new(1, 0, 1, Some(0)),
+                SourceMapping::new(3, 0, 2, Some(0)),
+                // the repeat loop created by the rule is placed on the line of the
+                // original loop, without any column
+                SourceMapping::new(4, 4, 2, None),
+                SourceMapping::new(5, 8, 3, Some(4)),
+                // the break statement replaces the continue statement
+                SourceMapping::new(6, 12, 4, None),
+                SourceMapping::new(9, 8, 6, Some(4)),
+                SourceMapping::new(13, 0, 8, Some(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dense_generator_maps_statements_after_remove_continue() {
+        let mut generator = DenseLuaGenerator::default().with_source_map(CODE);
+        generator.write_block(&process_code());
+        let (code, source_map) = generator.into_string_with_source_map();
+
+        let lines: Vec<_> = code.lines().collect();
+        pretty_assertions::assert_eq!(lines.len(), 2);
+        pretty_assertions::assert_eq!(lines[1], "end return total");
+
+        pretty_assertions::assert_eq!(
+            source_map.find_mapping(1, 0),
+            Some(&SourceMapping::new(1, 0, 1, Some(0)))
+        );
+        pretty_assertions::assert_eq!(
+            source_map
+                .find_mapping(1, 59)
+                .map(SourceMapping::get_original_line),
+            Some(4)
+        );
+        pretty_assertions::assert_eq!(
+            source_map.find_mapping(1, 60),
+            Some(&SourceMapping::new(1, 60, 6, Some(4)))
+        );
+        pretty_assertions::assert_eq!(
+            source_map.find_mapping(2, 4),
+            Some(&SourceMapping::new(2, 4, 8, Some(0)))
+        );
+        pretty_assertions::assert_eq!(source_map.len(), 7);
+    }
+
+    #[test]
+    fn generator_without_source_map_returns_empty_source_map() {
+        let mut generator = ReadableLuaGenerator::default();
+        generator.write_block(&process_code());
+        let (_, source_map) = generator.into_string_with_source_map();
+
+        assert!(source_map.is_empty());
+    }
+
+    #[test]
+    fn serialize_source_map_to_json() {
+        let source_map = SourceMap {
+            mappings: vec![
+                SourceMapping::new(1, 0, 1, Some(0)),
+                SourceMapping::new(2, 4, 3, None),
+            ],
+        };
+
+        pretty_assertions::assert_eq!(
+            source_map.to_json(),
+            r#"{
+  "mappings": [
+    {
+      "generated_line": 1,
+      "generated_column": 0,
+      "original_line": 1,
+      "original_column": 0
+    },
+    {
+      "generated_line": 2,
+      "generated_column": 4,
+      "original_line": 3
+    }
+  ]
+}"#
+        );
+    }
+}
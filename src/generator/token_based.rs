@@ -1,7 +1,7 @@
 use std::iter;
 
 use crate::{
-    generator::{utils, LuaGenerator},
+    generator::{utils, LongStringThreshold, LuaGenerator, QuoteStyle, UnicodeEscape},
     nodes::*,
 };
 
@@ -169,6 +169,17 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         self.write_token(&tokens.end);
     }
 
+    fn write_goto_with_tokens(&mut self, goto: &GotoStatement, tokens: &GotoTokens) {
+        self.write_token(&tokens.goto);
+        self.write_identifier(goto.get_label());
+    }
+
+    fn write_label_with_tokens(&mut self, label: &LabelStatement, tokens: &LabelTokens) {
+        self.write_token(&tokens.left_colons);
+        self.write_identifier(label.get_name());
+        self.write_token(&tokens.right_colons);
+    }
+
     fn write_function_call_with_tokens(
         &mut self,
         call: &FunctionCall,
@@ -1186,6 +1197,19 @@ impl<'a> TokenBasedLuaGenerator<'a> {
         }
     }
 
+    fn generate_goto_tokens(&self, _goto: &GotoStatement) -> GotoTokens {
+        GotoTokens {
+            goto: Token::from_content("goto"),
+        }
+    }
+
+    fn generate_label_tokens(&self, _label: &LabelStatement) -> LabelTokens {
+        LabelTokens {
+            left_colons: Token::from_content("::"),
+            right_colons: Token::from_content("::"),
+        }
+    }
+
     fn generate_compound_assign_tokens(
         &self,
         assign: &CompoundAssignStatement,
@@ -1639,6 +1663,18 @@ impl<'a> TokenBasedLuaGenerator<'a> {
             self.write_symbol(name);
         }
 
+        if let Some(attribute) = typed_identifier.get_attribute() {
+            if let Some(tokens) = typed_identifier.get_attribute_tokens() {
+                self.write_token(&tokens.opening_chevron);
+                self.write_token(&tokens.name);
+                self.write_token(&tokens.closing_chevron);
+            } else {
+                self.write_symbol("<");
+                self.write_symbol(attribute.as_str());
+                self.write_symbol(">");
+            }
+        }
+
         if let Some(r#type) = typed_identifier.get_type() {
             if let Some(colon) = typed_identifier.get_colon_token() {
                 self.write_token(colon);
@@ -1694,6 +1730,11 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
     }
 
     fn write_block(&mut self, block: &Block) {
+        if let Some(shebang) = block.get_shebang() {
+            self.push_str(shebang);
+            self.push_str(block.get_shebang_new_line().unwrap_or("\n"));
+        }
+
         if let Some(tokens) = block.get_tokens() {
             self.write_block_with_tokens(block, tokens);
         } else {
@@ -1717,6 +1758,22 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
         }
     }
 
+    fn write_goto_statement(&mut self, goto: &GotoStatement) {
+        if let Some(tokens) = goto.get_tokens() {
+            self.write_goto_with_tokens(goto, tokens);
+        } else {
+            self.write_goto_with_tokens(goto, &self.generate_goto_tokens(goto));
+        }
+    }
+
+    fn write_label_statement(&mut self, label: &LabelStatement) {
+        if let Some(tokens) = label.get_tokens() {
+            self.write_label_with_tokens(label, tokens);
+        } else {
+            self.write_label_with_tokens(label, &self.generate_label_tokens(label));
+        }
+    }
+
     fn write_compound_assign(&mut self, assign: &CompoundAssignStatement) {
         if let Some(tokens) = assign.get_tokens() {
             self.write_compound_assign_with_tokens(assign, tokens);
@@ -2009,7 +2066,7 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
         if let Some(token) = number.get_token() {
             self.write_token(token);
         } else {
-            self.write_token(&Token::from_content(utils::write_number(number)));
+            self.write_token(&Token::from_content(utils::write_number(number, None)));
         }
     }
 
@@ -2028,7 +2085,12 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
         if let Some(token) = string.get_token() {
             self.write_token(token);
         } else {
-            self.write_symbol(&utils::write_string(string.get_value()));
+            self.write_symbol(&utils::write_string(
+                string.as_bytes(),
+                QuoteStyle::default(),
+                LongStringThreshold::default(),
+                UnicodeEscape::default(),
+            ));
         }
     }
 
@@ -2132,7 +2194,12 @@ impl LuaGenerator for TokenBasedLuaGenerator<'_> {
         if let Some(token) = string_type.get_token() {
             self.write_token(token);
         } else {
-            self.write_symbol(&utils::write_string(string_type.get_value()));
+            self.write_symbol(&utils::write_string(
+                string_type.as_bytes(),
+                QuoteStyle::default(),
+                LongStringThreshold::default(),
+                UnicodeEscape::default(),
+            ));
         }
     }
 
@@ -2307,6 +2374,9 @@ mod test {
         call_math_floor => "math.floor(value)",
         call_with_index => "object[ key ](i)",
         compound_increment => "i += 1\n",
+        compound_floor_division => "i //= 2\n",
+        return_floor_division => "return a // b",
+        return_floor_division_without_spaces => "return a//-b",
         empty_function_declaration => "function process()\nend",
         empty_static_function_declaration => "function Class .new()\nend",
         empty_method_function_declaration => "function Class : process()\nend",
@@ -2347,6 +2417,15 @@ mod test {
         continue_with_comment => "continue -- skip to next iteration",
         empty_return => "return\n",
 
+        // shebang and directives
+        shebang_only => "#!/usr/bin/env lua\n",
+        shebang_with_statement => "#!/usr/bin/env lua\nprint('hi')\n",
+        shebang_followed_by_empty_line => "#!/usr/bin/env lua\n\nreturn 1\n",
+        shebang_with_crlf => "#!/usr/bin/env lua\r\nprint('hi')\r\nreturn 1\r\n",
+        shebang_without_new_line => "#!/usr/bin/env lua",
+        shebang_with_strict_directive => "#!/usr/bin/env luau\n--!strict\nlocal x = 1\n",
+        strict_directive => "--!strict\nlocal x = 1\n",
+
         // expressions
         return_true => "return true",
         return_false => "return false",
@@ -2355,6 +2434,9 @@ mod test {
         return_double_quote_string => "return \"ok\"",
         return_identifier => "return var",
         return_bracket_string => "return [[   [ok]   ]]",
+        return_bracket_string_with_equal_signs => "return [==[\n  ]] ok\n]==]",
+        return_string_with_decimal_escape => "return \"\\65\"",
+        return_string_with_hexadecimal_escape => "return '\\x41\\z\n  '",
         return_empty_interpolated_string => "return ``",
         return_interpolated_string_escape_curly_brace => "return `Open: \\{`",
         return_interpolated_string_followed_by_comment => "return `ok` -- comment",
@@ -2452,4 +2534,55 @@ mod test {
 
         insta::assert_snapshot!("inserts_a_new_line_after_custom_added_comments", output);
     }
+
+    #[test]
+    fn writes_goto_and_label_statements_from_lua52_code() {
+        let code = "for i = 1, 3 do\n\tif i == 2 then goto continue end\n\tprint(i)\n\t:: continue ::\nend\n";
+        let block = crate::Parser::default()
+            .with_dialect(crate::LuaDialect::Lua52)
+            .preserve_tokens()
+            .parse(code)
+            .unwrap();
+
+        let mut generator = TokenBasedLuaGenerator::new(code);
+
+        generator.write_block(&block);
+
+        pretty_assertions::assert_eq!(generator.into_string(), code);
+    }
+
+    #[test]
+    fn writes_local_attributes_from_lua54_code() {
+        let code = "local a <const>, b <close> = 1, f()\nlocal c < const >\n";
+        let block = crate::Parser::default()
+            .with_dialect(crate::LuaDialect::Lua54)
+            .preserve_tokens()
+            .parse(code)
+            .unwrap();
+
+        let mut generator = TokenBasedLuaGenerator::new(code);
+
+        generator.write_block(&block);
+
+        pretty_assertions::assert_eq!(generator.into_string(), code);
+    }
+
+    #[test]
+    fn writes_statements_created_with_a_line_on_that_line() {
+        let code = "local a = 1";
+        let mut block = crate::Parser::default()
+            .preserve_tokens()
+            .parse(code)
+            .unwrap();
+
+        block.push_statement(
+            Statement::from(LocalAssignStatement::from_variable("b").with_value(true)).with_line(3),
+        );
+
+        let mut generator = TokenBasedLuaGenerator::new(code);
+
+        generator.write_block(&block);
+
+        pretty_assertions::assert_eq!(generator.into_string(), "local a = 1\n\nlocal b=true");
+    }
 }
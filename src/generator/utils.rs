@@ -3,15 +3,237 @@
 
 use std::convert::TryInto;
 
+use serde::{Deserialize, Serialize};
+
 use crate::nodes::{
-    Expression, FieldExpression, FunctionCall, IndexExpression, NumberExpression, Prefix,
-    Statement, StringSegment, TableExpression, Variable,
+    Arguments, DecimalNumber, Expression, FieldExpression, FunctionCall, IndexExpression,
+    LastStatement, NumberExpression, Prefix, Statement, StringSegment, TableExpression, Token,
+    Variable,
 };
 
 const QUOTED_STRING_MAX_LENGTH: usize = 60;
 const LONG_STRING_MIN_LENGTH: usize = 20;
 const FORCE_LONG_STRING_NEW_LINE_THRESHOLD: usize = 6;
 
+/// The quote character preferred by generators when writing quoted strings. If the
+/// string contains the preferred quote but not the other one, the other quote is used
+/// to avoid escaping.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStyle {
+    #[default]
+    Single,
+    Double,
+}
+
+impl QuoteStyle {
+    fn symbol(&self) -> char {
+        match self {
+            Self::Single => '\'',
+            Self::Double => '"',
+        }
+    }
+
+    fn alternate_symbol(&self) -> char {
+        match self {
+            Self::Single => '"',
+            Self::Double => '\'',
+        }
+    }
+}
+
+/// Controls when generators write strings using long brackets (like `[[...]]`) instead
+/// of quotes. Long brackets are only used when the string does not contain characters
+/// that would need to be escaped (new lines excepted).
+///
+/// When deserialized, the value can be `"auto"`, `"never"`, `"always"` or a number
+/// that defines the minimum string length.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "LongStringThresholdValue", into = "LongStringThresholdValue")]
+pub enum LongStringThreshold {
+    /// Use long brackets for long strings or strings with many lines.
+    #[default]
+    Auto,
+    /// Always use quoted strings, escaping new lines.
+    Never,
+    /// Use long brackets whenever possible.
+    Always,
+    /// Use long brackets for strings that have at least the given length.
+    MinLength(usize),
+}
+
+impl LongStringThreshold {
+    fn use_long_bracket(&self, value: &str) -> bool {
+        match self {
+            Self::Auto => {
+                value.len() >= LONG_STRING_MIN_LENGTH
+                    && (value.len() >= QUOTED_STRING_MAX_LENGTH
+                        || count_new_lines(value) >= FORCE_LONG_STRING_NEW_LINE_THRESHOLD)
+            }
+            Self::Never => false,
+            Self::Always => true,
+            Self::MinLength(length) => value.len() >= *length,
+        }
+    }
+}
+
+/// Controls how generators escape non-ASCII characters in strings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnicodeEscape {
+    /// Write the code point of the character (like `\u{e9}`). This form is supported
+    /// by Luau and Lua 5.3+.
+    #[default]
+    CodePoint,
+    /// Write each byte of the UTF-8 encoding of the character as a decimal escape (like
+    /// `\195\169`). Use this form when targeting Lua 5.1 or LuaJIT.
+    Bytes,
+}
+
+/// Controls when the dense generator writes semicolons between statements.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SemicolonStyle {
+    /// Only write a semicolon when the next statement could be parsed as a call on the
+    /// previous one.
+    #[default]
+    AmbiguousOnly,
+    /// Terminate every statement with a semicolon.
+    Always,
+}
+
+/// The indentation unit used by the readable generator for each nested level. By
+/// default, it uses 4 spaces.
+///
+/// When deserialized, the width defaults to 4 for spaces and to 1 for tabs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "IndentationValue", into = "IndentationValue")]
+pub struct Indentation {
+    character: IndentationCharacter,
+    width: usize,
+}
+
+/// The character used to indent code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndentationCharacter {
+    Space,
+    Tab,
+}
+
+impl IndentationCharacter {
+    fn as_char(&self) -> char {
+        match self {
+            Self::Space => ' ',
+            Self::Tab => '\t',
+        }
+    }
+
+    fn default_width(&self) -> usize {
+        match self {
+            Self::Space => 4,
+            Self::Tab => 1,
+        }
+    }
+}
+
+impl Indentation {
+    pub fn new(character: IndentationCharacter, width: usize) -> Self {
+        Self { character, width }
+    }
+
+    pub fn spaces(width: usize) -> Self {
+        Self::new(IndentationCharacter::Space, width)
+    }
+
+    pub fn tabs(width: usize) -> Self {
+        Self::new(IndentationCharacter::Tab, width)
+    }
+
+    pub fn get_character(&self) -> IndentationCharacter {
+        self.character
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub(crate) fn to_unit_string(self) -> String {
+        self.character.as_char().to_string().repeat(self.width)
+    }
+}
+
+impl Default for Indentation {
+    fn default() -> Self {
+        Self::spaces(4)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IndentationValue {
+    character: IndentationCharacter,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    width: Option<usize>,
+}
+
+impl From<IndentationValue> for Indentation {
+    fn from(value: IndentationValue) -> Self {
+        Self::new(
+            value.character,
+            value
+                .width
+                .unwrap_or_else(|| value.character.default_width()),
+        )
+    }
+}
+
+impl From<Indentation> for IndentationValue {
+    fn from(value: Indentation) -> Self {
+        Self {
+            character: value.character,
+            width: Some(value.width),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LongStringMode {
+    Auto,
+    Never,
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(untagged)]
+enum LongStringThresholdValue {
+    Mode(LongStringMode),
+    MinLength(usize),
+}
+
+impl From<LongStringThresholdValue> for LongStringThreshold {
+    fn from(value: LongStringThresholdValue) -> Self {
+        match value {
+            LongStringThresholdValue::Mode(LongStringMode::Auto) => Self::Auto,
+            LongStringThresholdValue::Mode(LongStringMode::Never) => Self::Never,
+            LongStringThresholdValue::Mode(LongStringMode::Always) => Self::Always,
+            LongStringThresholdValue::MinLength(length) => Self::MinLength(length),
+        }
+    }
+}
+
+impl From<LongStringThreshold> for LongStringThresholdValue {
+    fn from(value: LongStringThreshold) -> Self {
+        match value {
+            LongStringThreshold::Auto => Self::Mode(LongStringMode::Auto),
+            LongStringThreshold::Never => Self::Mode(LongStringMode::Never),
+            LongStringThreshold::Always => Self::Mode(LongStringMode::Always),
+            LongStringThreshold::MinLength(length) => Self::MinLength(length),
+        }
+    }
+}
+
 #[inline]
 pub fn should_break_with_space(ending_character: char, next_character: char) -> bool {
     match ending_character {
@@ -36,16 +258,22 @@ pub fn break_long_string(last_str: &str) -> bool {
     }
 }
 
-pub fn break_variable_arguments(last_string: &str) -> bool {
-    if let Some('.') = last_string.chars().last() {
-        true
-    } else if let Some(first_char) = last_string.chars().next() {
-        first_char == '.' || first_char.is_ascii_digit()
-    } else {
-        false
+/// Returns true if the given string is a number literal (like `1`, `1.`, `.5` or `0x2`).
+/// A number literal absorbs a following `.` when read back, so it has to be separated
+/// from operators starting with a dot.
+fn is_number_literal(value: &str) -> bool {
+    let mut characters = value.chars();
+    match characters.next() {
+        Some('0'..='9') => true,
+        Some('.') => matches!(characters.next(), Some('0'..='9')),
+        _ => false,
     }
 }
 
+pub fn break_variable_arguments(last_string: &str) -> bool {
+    last_string.ends_with('.') || is_number_literal(last_string)
+}
+
 pub fn break_minus(last_string: &str) -> bool {
     if let Some(last_char) = last_string.chars().last() {
         last_char == '-'
@@ -63,13 +291,7 @@ pub fn break_equal(last_string: &str) -> bool {
 }
 
 pub fn break_concat(last_string: &str) -> bool {
-    if let Some('.') = last_string.chars().last() {
-        true
-    } else if let Some(first_char) = last_string.chars().next() {
-        first_char == '.' || first_char.is_ascii_digit()
-    } else {
-        false
-    }
+    last_string.ends_with('.') || is_number_literal(last_string)
 }
 
 pub fn ends_with_prefix(statement: &Statement) -> bool {
@@ -95,6 +317,14 @@ pub fn ends_with_prefix(statement: &Statement) -> bool {
     }
 }
 
+/// Returns the expression inside any number of nested type casts.
+pub fn skip_type_casts(mut expression: &Expression) -> &Expression {
+    while let Expression::TypeCast(type_cast) = expression {
+        expression = type_cast.get_expression();
+    }
+    expression
+}
+
 pub fn starts_with_table(mut expression: &Expression) -> Option<&TableExpression> {
     loop {
         match expression {
@@ -147,6 +377,101 @@ pub fn starts_with_parenthese(statement: &Statement) -> bool {
     }
 }
 
+/// Returns the last token of a statement, if the statement has tokens. Statements
+/// ending with a type do not return any token.
+pub fn statement_last_token(statement: &Statement) -> Option<&Token> {
+    match statement {
+        Statement::Assign(assign) => assign.last_value().and_then(expression_last_token),
+        Statement::Do(do_statement) => do_statement.get_tokens().map(|tokens| &tokens.end),
+        Statement::Call(call) => arguments_last_token(call.get_arguments()),
+        Statement::CompoundAssign(assign) => expression_last_token(assign.get_value()),
+        Statement::Function(function) => function.get_tokens().map(|tokens| &tokens.end),
+        Statement::GenericFor(generic_for) => generic_for.get_tokens().map(|tokens| &tokens.end),
+        Statement::Goto(goto) => goto.get_label().get_token(),
+        Statement::If(if_statement) => if_statement.get_tokens().map(|tokens| &tokens.end),
+        Statement::Label(label) => label.get_tokens().map(|tokens| &tokens.right_colons),
+        Statement::LocalAssign(assign) => {
+            if let Some(value) = assign.last_value() {
+                expression_last_token(value)
+            } else {
+                assign.get_variables().last().and_then(|variable| {
+                    if variable.get_type().is_some() {
+                        None
+                    } else {
+                        variable.get_identifier().get_token()
+                    }
+                })
+            }
+        }
+        Statement::LocalFunction(function) => function
+            .get_tokens()
+            .map(|tokens| &tokens.function_body.end),
+        Statement::NumericFor(numeric_for) => numeric_for.get_tokens().map(|tokens| &tokens.end),
+        Statement::Repeat(repeat) => expression_last_token(repeat.get_condition()),
+        Statement::While(while_statement) => while_statement.get_tokens().map(|tokens| &tokens.end),
+        Statement::TypeDeclaration(_) => None,
+    }
+}
+
+/// Returns the last token of a last statement, if the statement has tokens.
+pub fn last_statement_last_token(statement: &LastStatement) -> Option<&Token> {
+    match statement {
+        LastStatement::Break(token) | LastStatement::Continue(token) => token.as_ref(),
+        LastStatement::Return(statement) => {
+            if let Some(expression) = statement.iter_expressions().last() {
+                expression_last_token(expression)
+            } else {
+                statement.get_tokens().map(|tokens| &tokens.r#return)
+            }
+        }
+    }
+}
+
+fn arguments_last_token(arguments: &Arguments) -> Option<&Token> {
+    match arguments {
+        Arguments::Tuple(tuple) => tuple.get_tokens().map(|tokens| &tokens.closing_parenthese),
+        Arguments::String(string) => string.get_token(),
+        Arguments::Table(table) => table.get_tokens().map(|tokens| &tokens.closing_brace),
+    }
+}
+
+fn expression_last_token(mut expression: &Expression) -> Option<&Token> {
+    loop {
+        match expression {
+            Expression::Binary(binary) => expression = binary.right(),
+            Expression::Call(call) => break arguments_last_token(call.get_arguments()),
+            Expression::False(token)
+            | Expression::Nil(token)
+            | Expression::True(token)
+            | Expression::VariableArguments(token) => break token.as_ref(),
+            Expression::Field(field) => break field.get_field().get_token(),
+            Expression::Function(function) => {
+                break function.get_tokens().map(|tokens| &tokens.end)
+            }
+            Expression::Identifier(identifier) => break identifier.get_token(),
+            Expression::If(if_expression) => expression = if_expression.get_else_result(),
+            Expression::Index(index) => {
+                break index.get_tokens().map(|tokens| &tokens.closing_bracket)
+            }
+            Expression::Number(number) => break number.get_token(),
+            Expression::Parenthese(parenthese) => {
+                break parenthese
+                    .get_tokens()
+                    .map(|tokens| &tokens.right_parenthese)
+            }
+            Expression::String(string) => break string.get_token(),
+            Expression::InterpolatedString(string) => {
+                break string.get_tokens().map(|tokens| &tokens.closing_tick)
+            }
+            Expression::Table(table) => {
+                break table.get_tokens().map(|tokens| &tokens.closing_brace)
+            }
+            Expression::Unary(unary) => expression = unary.get_expression(),
+            Expression::TypeCast(_) => break None,
+        }
+    }
+}
+
 fn expression_ends_with_prefix(expression: &Expression) -> bool {
     match expression {
         Expression::Binary(binary) => expression_ends_with_prefix(binary.right()),
@@ -197,7 +522,66 @@ fn index_starts_with_parenthese(index: &IndexExpression) -> bool {
     prefix_starts_with_parenthese(index.get_prefix())
 }
 
-pub fn write_number(number: &NumberExpression) -> String {
+// the largest integer that a double can represent without losing precision
+const MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+fn as_hex_integer(number: &DecimalNumber, prefer_hex_above: Option<u64>) -> Option<u64> {
+    let threshold = prefer_hex_above?;
+    let float = number.get_raw_float();
+
+    if number.get_exponent().is_none()
+        && float.is_sign_positive()
+        && float.fract() == 0.0
+        && float <= MAX_EXACT_INTEGER
+    {
+        let integer = float as u64;
+        (integer > threshold).then_some(integer)
+    } else {
+        None
+    }
+}
+
+/// Writes the mantissa of a value for the given decimal exponent. The digits come from
+/// the shortest representation of the value, which are shifted instead of dividing the
+/// value (a division can add digits like in `1.4999999999999998e-7`).
+fn write_mantissa(float: f64, exponent: i32) -> String {
+    if float == 0.0 {
+        return "0".to_owned();
+    }
+
+    let scientific = format!("{:e}", float.abs());
+    let (mantissa, value_exponent) = scientific
+        .split_once('e')
+        .expect("scientific notation should have an exponent");
+    let value_exponent: i32 = value_exponent
+        .parse()
+        .expect("exponent should be an integer");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let digits_length = digits.len() as i32;
+
+    // the amount of digits before the decimal point
+    let point = value_exponent + 1 - exponent;
+
+    let mantissa = if point <= 0 {
+        format!("0.{}{}", "0".repeat((-point) as usize), digits)
+    } else if point >= digits_length {
+        format!("{}{}", digits, "0".repeat((point - digits_length) as usize))
+    } else {
+        let (integer, fraction) = digits.split_at(point as usize);
+        format!("{}.{}", integer, fraction)
+    };
+
+    if float.is_sign_negative() {
+        format!("-{}", mantissa)
+    } else {
+        mantissa
+    }
+}
+
+/// Writes a number expression. When `prefer_hex_above` is defined, decimal integers
+/// (without exponents) that are greater than the given value are written using the
+/// hexadecimal notation.
+pub fn write_number(number: &NumberExpression, prefer_hex_above: Option<u64>) -> String {
     match number {
         NumberExpression::Decimal(number) => {
             let float = number.get_raw_float();
@@ -206,16 +590,16 @@ pub fn write_number(number: &NumberExpression) -> String {
                 "(0/0)".to_owned()
             } else if float.is_infinite() {
                 format!("({}1/0)", if float.is_sign_negative() { "-" } else { "" })
+            } else if let Some(integer) = as_hex_integer(number, prefer_hex_above) {
+                format!("0x{:x}", integer)
             } else if let Some(exponent) = number
                 .get_exponent()
                 .map(TryInto::try_into)
                 .and_then(Result::ok)
             {
-                let mantissa: f64 = float / 10.0_f64.powi(exponent);
-
                 let formatted = format!(
                     "{}{}{}",
-                    mantissa,
+                    write_mantissa(float, exponent),
                     if number.is_uppercase().unwrap_or_default() {
                         "E"
                     } else {
@@ -269,11 +653,17 @@ fn needs_escaping(character: char) -> bool {
     !(character.is_ascii_graphic() || character == ' ') || character == '\\'
 }
 
+/// Long brackets can only contain printable characters and `\n`. In particular, a `\r`
+/// would not be read back as-is, because Lua converts new line sequences (`\r`,
+/// `\r\n` and `\n\r`) to `\n` inside long brackets.
 fn needs_quoted_string(character: char) -> bool {
     !(character.is_ascii_graphic() || character == ' ' || character == '\n')
 }
 
-fn escape(character: char) -> String {
+/// Escapes a character. The next character is needed because a decimal escape
+/// sequence reads up to three digits: when a digit follows, the sequence is padded
+/// with zeros so that the digit is not read as part of the escape.
+fn escape(character: char, next_character: Option<char>, unicode_escape: UnicodeEscape) -> String {
     match character {
         '\n' => "\\n".to_owned(),
         '\t' => "\\t".to_owned(),
@@ -285,9 +675,22 @@ fn escape(character: char) -> String {
         '\u{C}' => "\\f".to_owned(),
         _ => {
             if character.len_utf8() == 1 {
-                format!("\\{}", character as u8)
+                if next_character.filter(char::is_ascii_digit).is_some() {
+                    format!("\\{:03}", character as u8)
+                } else {
+                    format!("\\{}", character as u8)
+                }
             } else {
-                format!("\\u{{{:x}}}", character as u32)
+                match unicode_escape {
+                    UnicodeEscape::CodePoint => format!("\\u{{{:x}}}", character as u32),
+                    // bytes of multi-byte UTF-8 sequences are all above 127, so their
+                    // decimal escapes always have three digits and never need padding
+                    UnicodeEscape::Bytes => character
+                        .encode_utf8(&mut [0; 4])
+                        .bytes()
+                        .map(|byte| format!("\\{}", byte))
+                        .collect(),
+                }
             }
         }
     }
@@ -298,9 +701,32 @@ pub fn count_new_lines(string: &str) -> usize {
     string.chars().filter(|c| *c == '\n').count()
 }
 
-pub fn write_string(value: &str) -> String {
+pub fn write_string(
+    value: &[u8],
+    quote_style: QuoteStyle,
+    long_string_threshold: LongStringThreshold,
+    unicode_escape: UnicodeEscape,
+) -> String {
+    match std::str::from_utf8(value) {
+        Ok(value) => write_text_string(value, quote_style, long_string_threshold, unicode_escape),
+        Err(_) => write_quoted_bytes(value, quote_style, unicode_escape),
+    }
+}
+
+fn write_text_string(
+    value: &str,
+    quote_style: QuoteStyle,
+    long_string_threshold: LongStringThreshold,
+    unicode_escape: UnicodeEscape,
+) -> String {
+    let quote = quote_style.symbol();
+
     if value.is_empty() {
-        return "''".to_owned();
+        return format!("{}{}", quote, quote);
+    }
+
+    if !value.contains(needs_quoted_string) && long_string_threshold.use_long_bracket(value) {
+        return write_long_bracket(value);
     }
 
     if value.len() == 1 {
@@ -313,23 +739,20 @@ pub fn write_string(value: &str) -> String {
             '"' => return "'\"'".to_owned(),
             _ => {
                 if needs_escaping(character) {
-                    return format!("'{}'", escape(character));
+                    return format!(
+                        "{}{}{}",
+                        quote,
+                        escape(character, None, unicode_escape),
+                        quote
+                    );
                 } else {
-                    return format!("'{}'", character);
+                    return format!("{}{}{}", quote, character, quote);
                 }
             }
         }
     }
 
-    if !value.contains(needs_quoted_string)
-        && value.len() >= LONG_STRING_MIN_LENGTH
-        && (value.len() >= QUOTED_STRING_MAX_LENGTH
-            || count_new_lines(value) >= FORCE_LONG_STRING_NEW_LINE_THRESHOLD)
-    {
-        write_long_bracket(value)
-    } else {
-        write_quoted(value)
-    }
+    write_quoted(value, quote_style, unicode_escape)
 }
 
 pub fn write_interpolated_string_segment(segment: &StringSegment) -> String {
@@ -343,14 +766,20 @@ pub fn write_interpolated_string_segment(segment: &StringSegment) -> String {
 
     result.reserve(value.len());
 
-    for character in value.chars() {
+    let mut characters = value.chars().peekable();
+
+    while let Some(character) = characters.next() {
         match character {
             '`' | '{' => {
                 result.push('\\');
                 result.push(character);
             }
             _ if needs_escaping(character) => {
-                result.push_str(&escape(character));
+                result.push_str(&escape(
+                    character,
+                    characters.peek().copied(),
+                    UnicodeEscape::CodePoint,
+                ));
             }
             _ => {
                 result.push(character);
@@ -376,36 +805,99 @@ fn write_long_bracket(value: &str) -> String {
     format!("[{}[{}{}]{}]", equals, needs_extra_new_line, value, equals)
 }
 
-fn write_quoted(value: &str) -> String {
+fn write_quoted(value: &str, quote_style: QuoteStyle, unicode_escape: UnicodeEscape) -> String {
     let mut quoted = String::new();
     quoted.reserve(value.len() + 2);
 
-    let quote_symbol = get_quote_symbol(value);
+    let quote_symbol = get_quote_symbol(value, quote_style);
+    quoted.push(quote_symbol);
+
+    push_quoted_characters(&mut quoted, value, quote_symbol, unicode_escape);
+
+    quoted.push(quote_symbol);
+    quoted.shrink_to_fit();
+    quoted
+}
+
+/// Writes a string that is not valid UTF-8. Valid sequences are written like any
+/// other string, and the remaining bytes are written with decimal escapes.
+fn write_quoted_bytes(
+    value: &[u8],
+    quote_style: QuoteStyle,
+    unicode_escape: UnicodeEscape,
+) -> String {
+    let mut quoted = String::new();
+    quoted.reserve(value.len() + 2);
+
+    let quote_symbol = get_quote_symbol(&String::from_utf8_lossy(value), quote_style);
+    quoted.push(quote_symbol);
+
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                push_quoted_characters(&mut quoted, valid, quote_symbol, unicode_escape);
+                break;
+            }
+            Err(error) => {
+                let (valid, invalid) = rest.split_at(error.valid_up_to());
+                push_quoted_characters(
+                    &mut quoted,
+                    std::str::from_utf8(valid).expect("bytes should be valid UTF-8"),
+                    quote_symbol,
+                    unicode_escape,
+                );
+
+                let invalid_length = error.error_len().unwrap_or(invalid.len());
+                // bytes that are not valid UTF-8 are all above 127, so their decimal
+                // escapes always have three digits and never need padding
+                for byte in &invalid[..invalid_length] {
+                    quoted.push_str(&format!("\\{}", byte));
+                }
+
+                rest = &invalid[invalid_length..];
+            }
+        }
+    }
+
     quoted.push(quote_symbol);
+    quoted.shrink_to_fit();
+    quoted
+}
+
+fn push_quoted_characters(
+    quoted: &mut String,
+    value: &str,
+    quote_symbol: char,
+    unicode_escape: UnicodeEscape,
+) {
+    let mut characters = value.chars().peekable();
 
-    for character in value.chars() {
+    while let Some(character) = characters.next() {
         if character == quote_symbol {
             quoted.push('\\');
             quoted.push(quote_symbol);
         } else if needs_escaping(character) {
-            quoted.push_str(&escape(character));
+            quoted.push_str(&escape(
+                character,
+                characters.peek().copied(),
+                unicode_escape,
+            ));
         } else {
             quoted.push(character);
         }
     }
-
-    quoted.push(quote_symbol);
-    quoted.shrink_to_fit();
-    quoted
 }
 
-fn get_quote_symbol(value: &str) -> char {
-    if value.contains('"') {
-        '\''
-    } else if value.contains('\'') {
-        '"'
+fn get_quote_symbol(value: &str, quote_style: QuoteStyle) -> char {
+    let preferred = quote_style.symbol();
+    let alternate = quote_style.alternate_symbol();
+
+    if value.contains(preferred) && !value.contains(alternate) {
+        alternate
     } else {
-        '\''
+        preferred
     }
 }
 
@@ -421,7 +913,7 @@ mod test {
                 $(
                     #[test]
                     fn $name() {
-                        assert_eq!($value, write_string(&$input));
+                        assert_eq!($value, write_string($input.as_bytes(), QuoteStyle::Single, LongStringThreshold::Auto, UnicodeEscape::CodePoint));
                     }
                 )*
             };
@@ -447,6 +939,14 @@ mod test {
             double_quote("\"") => "'\"'",
             null("\0") => "'\\0'",
             escape("\u{1B}") => "'\\27'",
+            null_followed_by_digit("\u{0}5") => "'\\0005'",
+            null_followed_by_letter("\u{0}a") => "'\\0a'",
+            null_between_digits("1\u{0}2") => "'1\\0002'",
+            two_nulls_followed_by_digit("\u{0}\u{0}1") => "'\\0\\0001'",
+            start_of_heading_followed_by_digits("\u{1}23") => "'\\00123'",
+            escape_followed_by_digit("\u{1B}0") => "'\\0270'",
+            delete_followed_by_digit("\u{7F}9") => "'\\1279'",
+            bell_followed_by_digit("\u{7}1") => "'\\a1'",
             extended_ascii("\u{C3}") => "'\\u{c3}'",
             unicode("\u{25C1}") => "'\\u{25c1}'",
             escape_degree_symbol("°") => "'\\u{b0}'",
@@ -464,4 +964,508 @@ mod test {
                 => "'\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof\\u{10ffff}'",
         );
     }
+
+    mod write_string_with_double_quotes {
+        use super::*;
+
+        macro_rules! test_output {
+            ($($name:ident($input:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, write_string($input.as_bytes(), QuoteStyle::Double, LongStringThreshold::Auto, UnicodeEscape::CodePoint));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            empty("") => "\"\"",
+            single_letter("a") => "\"a\"",
+            single_digit("8") => "\"8\"",
+            single_symbol("!") => "\"!\"",
+            single_space(" ") => "\" \"",
+            abc("abc") => "\"abc\"",
+            three_spaces("   ") => "\"   \"",
+            new_line("\n") => "\"\\n\"",
+            bell("\u{7}") => "\"\\a\"",
+            backspace("\u{8}") => "\"\\b\"",
+            form_feed("\u{c}") => "\"\\f\"",
+            tab("\t") => "\"\\t\"",
+            carriage_return("\u{D}") => "\"\\r\"",
+            vertical_tab("\u{B}") => "\"\\v\"",
+            backslash("\\") => "\"\\\\\"",
+            single_quote("'") => "\"'\"",
+            double_quote("\"") => "'\"'",
+            null("\0") => "\"\\0\"",
+            escape("\u{1B}") => "\"\\27\"",
+            extended_ascii("\u{C3}") => "\"\\u{c3}\"",
+            unicode("\u{25C1}") => "\"\\u{25c1}\"",
+            escape_degree_symbol("°") => "\"\\u{b0}\"",
+            im_cool("I'm cool") => "\"I'm cool\"",
+            quoted_word("say \"hi\"") => "'say \"hi\"'",
+            both_quotes("I'm \"cool\"") => "\"I'm \\\"cool\\\"\"",
+            ends_with_closing_bracket("oof]") => "\"oof]\"",
+            multiline_ends_with_closing_bracket("oof\noof]") => "\"oof\\noof]\"",
+            large_multiline_does_not_end_with_closing_bracket("ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof")
+                => "[[ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]",
+            large_multiline_ends_with_closing_bracket("ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]")
+                => "[=[ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]=]",
+            large_multiline_starts_with_new_line("\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof")
+                => "[[\n\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]",
+
+            large_multiline_with_unicode("\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof\u{10FFFF}")
+                => "\"\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof\\u{10ffff}\"",
+        );
+    }
+
+    mod write_string_with_bytes {
+        use super::*;
+
+        macro_rules! test_output {
+            ($($name:ident($input:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, write_string($input, QuoteStyle::Single, LongStringThreshold::Always, UnicodeEscape::CodePoint));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            single_invalid_byte(b"\xFF") => "'\\255'",
+            invalid_bytes_with_null(b"\xFF\x00\x01") => "'\\255\\0\\1'",
+            invalid_byte_followed_by_digit(b"\xFF1") => "'\\2551'",
+            null_followed_by_invalid_byte(b"\x00\xFE") => "'\\0\\254'",
+            text_around_invalid_byte(b"abc\x80def") => "'abc\\128def'",
+            unicode_and_invalid_bytes(b"\xC3\xA9\xC3") => "'\\u{e9}\\195'",
+            single_quote_and_invalid_byte(b"'\xFF") => "\"'\\255\"",
+            new_line_and_invalid_byte(b"a\nb\xFF") => "'a\\nb\\255'",
+        );
+    }
+
+    mod write_string_with_long_string_threshold {
+        use super::*;
+
+        const LARGE_MULTILINE: &str = "ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof";
+        const LARGE_MULTILINE_ENDS_WITH_CLOSING_BRACKET: &str =
+            "ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]";
+        const LARGE_MULTILINE_STARTS_WITH_NEW_LINE: &str =
+            "\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof";
+        const LARGE_MULTILINE_WITH_UNICODE: &str =
+            "\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof\u{10FFFF}";
+
+        fn write(value: &str, threshold: LongStringThreshold) -> String {
+            write_string(
+                value.as_bytes(),
+                QuoteStyle::Single,
+                threshold,
+                UnicodeEscape::CodePoint,
+            )
+        }
+
+        #[test]
+        fn never_escapes_new_lines() {
+            assert_eq!(
+                write(LARGE_MULTILINE, LongStringThreshold::Never),
+                "'ooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof'"
+            );
+        }
+
+        #[test]
+        fn never_with_string_starting_with_new_line() {
+            assert_eq!(
+                write(
+                    LARGE_MULTILINE_STARTS_WITH_NEW_LINE,
+                    LongStringThreshold::Never
+                ),
+                "'\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof'"
+            );
+        }
+
+        #[test]
+        fn always_uses_long_bracket() {
+            assert_eq!(
+                write(LARGE_MULTILINE, LongStringThreshold::Always),
+                "[[ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]"
+            );
+        }
+
+        #[test]
+        fn always_with_short_string() {
+            assert_eq!(write("a\nb", LongStringThreshold::Always), "[[a\nb]]");
+        }
+
+        #[test]
+        fn always_with_single_character() {
+            assert_eq!(write("a", LongStringThreshold::Always), "[[a]]");
+        }
+
+        #[test]
+        fn always_with_empty_string() {
+            assert_eq!(write("", LongStringThreshold::Always), "''");
+        }
+
+        #[test]
+        fn always_with_string_ending_with_closing_bracket() {
+            assert_eq!(
+                write(
+                    LARGE_MULTILINE_ENDS_WITH_CLOSING_BRACKET,
+                    LongStringThreshold::Always
+                ),
+                "[=[ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]=]"
+            );
+        }
+
+        #[test]
+        fn always_with_unicode_uses_quotes() {
+            assert_eq!(
+                write(LARGE_MULTILINE_WITH_UNICODE, LongStringThreshold::Always),
+                "'\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof\\u{10ffff}'"
+            );
+        }
+
+        #[test]
+        fn min_length_below_threshold() {
+            assert_eq!(
+                write(LARGE_MULTILINE, LongStringThreshold::MinLength(50)),
+                "'ooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\noof'"
+            );
+        }
+
+        #[test]
+        fn min_length_at_threshold() {
+            assert_eq!(
+                write(
+                    LARGE_MULTILINE,
+                    LongStringThreshold::MinLength(LARGE_MULTILINE.len())
+                ),
+                "[[ooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]"
+            );
+        }
+
+        #[test]
+        fn min_length_with_string_starting_with_new_line() {
+            assert_eq!(
+                write(
+                    LARGE_MULTILINE_STARTS_WITH_NEW_LINE,
+                    LongStringThreshold::MinLength(10)
+                ),
+                "[[\n\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof\noof]]"
+            );
+        }
+
+        #[test]
+        fn min_length_with_short_single_line_string() {
+            assert_eq!(write("abc", LongStringThreshold::MinLength(3)), "[[abc]]");
+        }
+
+        // Lua converts any new line sequence (`\r\n`, `\n\r` or a lone `\r`) to `\n`
+        // inside long brackets and skips the first one, so carriage returns can only be
+        // preserved with quoted strings
+        fn read_back(code: &str) -> String {
+            let block = crate::Parser::default()
+                .parse(&format!("return {}", code))
+                .unwrap_or_else(|_| panic!("unable to parse `{}`", code));
+
+            match block.get_last_statement() {
+                Some(LastStatement::Return(statement)) => {
+                    match statement.iter_expressions().next() {
+                        Some(Expression::String(string)) => {
+                            string.get_string_value().unwrap().to_owned()
+                        }
+                        _ => panic!("string expression expected"),
+                    }
+                }
+                _ => panic!("return statement expected"),
+            }
+        }
+
+        macro_rules! test_carriage_returns {
+            ($($name:ident($input:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let code = write($input, LongStringThreshold::Always);
+                        assert_eq!(code, $value);
+                        assert_eq!(read_back(&code), $input);
+                    }
+                )*
+            };
+        }
+
+        test_carriage_returns!(
+            starts_with_carriage_return_new_line("\r\nooof\nooof") => "'\\r\\nooof\\nooof'",
+            starts_with_carriage_return("\rooof\nooof") => "'\\rooof\\nooof'",
+            starts_with_new_line_carriage_return("\n\rooof") => "'\\n\\rooof'",
+            embedded_carriage_return("ooof\rooof\nooof") => "'ooof\\rooof\\nooof'",
+            embedded_carriage_return_new_line("ooof\r\nooof") => "'ooof\\r\\nooof'",
+            ends_with_carriage_return("ooof\nooof\r") => "'ooof\\nooof\\r'",
+            starts_with_new_line("\nooof\nooof") => "[[\n\nooof\nooof]]",
+            starts_with_two_new_lines("\n\nooof") => "[[\n\n\nooof]]",
+        );
+    }
+
+    mod write_string_with_unicode_escape {
+        use super::*;
+
+        fn write(value: &str, unicode_escape: UnicodeEscape) -> String {
+            write_string(
+                value.as_bytes(),
+                QuoteStyle::Single,
+                LongStringThreshold::Auto,
+                unicode_escape,
+            )
+        }
+
+        // decodes the quoted strings produced by these tests the way Lua reads them
+        fn decode(quoted: &str) -> Vec<u8> {
+            let content = &quoted[1..quoted.len() - 1];
+            let mut bytes = Vec::new();
+            let mut characters = content.chars().peekable();
+
+            while let Some(character) = characters.next() {
+                if character != '\\' {
+                    bytes.extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+                } else if characters.next_if_eq(&'u').is_some() {
+                    characters.next();
+                    let code_point: String =
+                        characters.by_ref().take_while(|c| *c != '}').collect();
+                    let character = u32::from_str_radix(&code_point, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .expect("invalid code point");
+                    bytes.extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+                } else {
+                    let mut digits = String::new();
+                    while digits.len() < 3 {
+                        match characters.next_if(char::is_ascii_digit) {
+                            Some(digit) => digits.push(digit),
+                            None => break,
+                        }
+                    }
+                    bytes.push(digits.parse().expect("invalid decimal escape"));
+                }
+            }
+
+            bytes
+        }
+
+        macro_rules! test_output {
+            ($($name:ident($input:literal) => ($code_point:literal, $bytes:literal)),* $(,)?) => {
+                $(
+                    mod $name {
+                        use super::*;
+
+                        #[test]
+                        fn code_point() {
+                            assert_eq!(write($input, UnicodeEscape::CodePoint), $code_point);
+                        }
+
+                        #[test]
+                        fn bytes() {
+                            assert_eq!(write($input, UnicodeEscape::Bytes), $bytes);
+                        }
+
+                        #[test]
+                        fn both_decode_to_the_same_bytes() {
+                            assert_eq!(decode($code_point), $input.as_bytes());
+                            assert_eq!(decode($bytes), $input.as_bytes());
+                        }
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            accented_letter("é") => ("'\\u{e9}'", "'\\195\\169'"),
+            accented_word("café crème") => ("'caf\\u{e9} cr\\u{e8}me'", "'caf\\195\\169 cr\\195\\168me'"),
+            accented_letter_followed_by_digit("é1") => ("'\\u{e9}1'", "'\\195\\1691'"),
+            astral_plane("\u{1F600}") => ("'\\u{1f600}'", "'\\240\\159\\152\\128'"),
+            astral_plane_between_letters("a\u{10FFFF}b") => ("'a\\u{10ffff}b'", "'a\\244\\143\\191\\191b'"),
+            ascii_escape_is_unchanged("\u{0}5") => ("'\\0005'", "'\\0005'"),
+        );
+    }
+
+    mod long_string_threshold {
+        use super::*;
+
+        #[test]
+        fn deserialize_auto() {
+            let threshold: LongStringThreshold = serde_json::from_str("\"auto\"").unwrap();
+            assert_eq!(threshold, LongStringThreshold::Auto);
+        }
+
+        #[test]
+        fn deserialize_never() {
+            let threshold: LongStringThreshold = serde_json::from_str("\"never\"").unwrap();
+            assert_eq!(threshold, LongStringThreshold::Never);
+        }
+
+        #[test]
+        fn deserialize_always() {
+            let threshold: LongStringThreshold = serde_json::from_str("\"always\"").unwrap();
+            assert_eq!(threshold, LongStringThreshold::Always);
+        }
+
+        #[test]
+        fn deserialize_number() {
+            let threshold: LongStringThreshold = serde_json::from_str("40").unwrap();
+            assert_eq!(threshold, LongStringThreshold::MinLength(40));
+        }
+
+        #[test]
+        fn deserialize_unknown_mode_errors() {
+            assert!(serde_json::from_str::<LongStringThreshold>("\"sometimes\"").is_err());
+        }
+
+        #[test]
+        fn serialize_never() {
+            assert_eq!(
+                serde_json::to_string(&LongStringThreshold::Never).unwrap(),
+                "\"never\""
+            );
+        }
+
+        #[test]
+        fn serialize_number() {
+            assert_eq!(
+                serde_json::to_string(&LongStringThreshold::MinLength(40)).unwrap(),
+                "40"
+            );
+        }
+    }
+
+    mod indentation {
+        use super::*;
+
+        #[test]
+        fn default_is_four_spaces() {
+            assert_eq!(Indentation::default().to_unit_string(), "    ");
+        }
+
+        #[test]
+        fn two_tabs_unit_string() {
+            assert_eq!(Indentation::tabs(2).to_unit_string(), "\t\t");
+        }
+
+        #[test]
+        fn deserialize_spaces_with_default_width() {
+            let indentation: Indentation =
+                serde_json::from_str("{\"character\":\"space\"}").unwrap();
+            assert_eq!(indentation, Indentation::spaces(4));
+        }
+
+        #[test]
+        fn deserialize_tabs_with_default_width() {
+            let indentation: Indentation = serde_json::from_str("{\"character\":\"tab\"}").unwrap();
+            assert_eq!(indentation, Indentation::tabs(1));
+        }
+
+        #[test]
+        fn deserialize_spaces_with_width() {
+            let indentation: Indentation =
+                serde_json::from_str("{\"character\":\"space\",\"width\":2}").unwrap();
+            assert_eq!(indentation, Indentation::spaces(2));
+        }
+
+        #[test]
+        fn serialize_tabs() {
+            assert_eq!(
+                serde_json::to_string(&Indentation::tabs(1)).unwrap(),
+                "{\"character\":\"tab\",\"width\":1}"
+            );
+        }
+    }
+
+    mod break_concat {
+        use super::*;
+
+        macro_rules! test_break {
+            ($($name:ident($input:literal) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, break_concat($input));
+                        assert_eq!($value, break_variable_arguments($input));
+                    }
+                )*
+            };
+        }
+
+        test_break!(
+            integer("1") => true,
+            float("1.5") => true,
+            float_ending_with_dot("1.") => true,
+            float_starting_with_dot(".5") => true,
+            hex("0x2") => true,
+            hex_ending_with_letter("0xA") => true,
+            exponent("1e5") => true,
+            concat("..") => true,
+            identifier("a") => false,
+            identifier_ending_with_digit("a2") => false,
+            string("'1.'") => false,
+            closing_parenthese(")") => false,
+            empty("") => false,
+        );
+    }
+
+    mod write_number_with_exponent {
+        use super::*;
+
+        macro_rules! test_output {
+            ($($name:ident($value:expr, $exponent:expr) => $expected:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let number = DecimalNumber::new($value).with_exponent($exponent, false);
+                        assert_eq!($expected, write_number(&number.into(), None));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            shortest_digits(1.5e-7, -7) => "1.5e-7",
+            shifted_digits(1.5e-7, -8) => "15e-8",
+            digits_after_point(1.5e-7, -6) => "0.15e-6",
+            padded_with_zeros(1.5e-7, -10) => "1500e-10",
+            leading_zeros(1.5e-7, -4) => "0.0015e-4",
+            large_exponent(1e300, 300) => "1e300",
+            many_digits(1.2345e50, 46) => "12345e46",
+            zero(0.0, 2) => "0e2",
+        );
+    }
+
+    mod write_number_with_prefer_hex_above {
+        use super::*;
+        use crate::nodes::{BinaryNumber, HexNumber};
+
+        macro_rules! test_output {
+            ($($name:ident($input:expr, $threshold:expr) => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($value, write_number(&$input.into(), $threshold));
+                    }
+                )*
+            };
+        }
+
+        test_output!(
+            integer_without_threshold(DecimalNumber::new(255.0), None) => "255",
+            integer_above_threshold(DecimalNumber::new(255.0), Some(15)) => "0xff",
+            integer_equal_to_threshold(DecimalNumber::new(255.0), Some(255)) => "255",
+            zero_above_threshold(DecimalNumber::new(0.0), Some(0)) => "0",
+            large_mask(DecimalNumber::new(4294967295.0), Some(255)) => "0xffffffff",
+            float_stays_decimal(DecimalNumber::new(255.5), Some(15)) => "255.5",
+            negative_stays_decimal(DecimalNumber::new(-255.0), Some(15)) => "-255",
+            inexact_integer_stays_decimal(DecimalNumber::new(1152921504606846976.0), Some(15))
+                => "1152921504606847000",
+            exponent_stays_decimal(DecimalNumber::new(1e3).with_exponent(3, false), Some(15)) => "1e3",
+            hex_number(HexNumber::new(255, false), Some(15)) => "0xff",
+            binary_number(BinaryNumber::new(10, false), Some(1)) => "0b1010",
+        );
+    }
 }
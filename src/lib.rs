@@ -11,6 +11,6 @@ mod utils;
 
 pub use frontend::{
     convert_data, process, BundleConfiguration, Configuration, DarkluaError, GeneratorParameters,
-    Options, Resources, WorkerTree,
+    LineEndings, Options, Resources, WorkerTree,
 };
-pub use parser::{Parser, ParserError};
+pub use parser::{LuaDialect, Parser, ParserError};
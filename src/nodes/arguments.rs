@@ -176,3 +176,15 @@ impl From<StringExpression> for Arguments {
         Self::String(string)
     }
 }
+
+impl From<Vec<Expression>> for Arguments {
+    fn from(values: Vec<Expression>) -> Self {
+        Self::Tuple(TupleArguments::new(values))
+    }
+}
+
+impl<const N: usize> From<[Expression; N]> for Arguments {
+    fn from(values: [Expression; N]) -> Self {
+        Self::Tuple(TupleArguments::new(values.into()))
+    }
+}
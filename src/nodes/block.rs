@@ -1,4 +1,9 @@
-use crate::nodes::{LastStatement, ReturnStatement, Statement, Token};
+use std::ops::{Bound, RangeBounds};
+
+use crate::nodes::{
+    DoStatement, LastStatement, ReturnStatement, Statement, Token, TriviaKind,
+    TypeDeclarationStatement,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BlockTokens {
@@ -14,11 +19,18 @@ impl BlockTokens {
     );
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Shebang {
+    line: String,
+    new_line: String,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Block {
     statements: Vec<Statement>,
     last_statement: Option<LastStatement>,
     tokens: Option<Box<BlockTokens>>,
+    shebang: Option<Shebang>,
 }
 
 impl Block {
@@ -27,6 +39,72 @@ impl Block {
             statements,
             last_statement,
             tokens: None,
+            shebang: None,
+        }
+    }
+
+    /// Sets the shebang line (like `#!/usr/bin/env lua`) that generators write before
+    /// the content of the block. Only the root block of a file can have a shebang.
+    pub fn with_shebang(mut self, shebang: impl Into<String>) -> Self {
+        self.set_shebang(shebang);
+        self
+    }
+
+    #[inline]
+    pub fn set_shebang(&mut self, shebang: impl Into<String>) {
+        self.shebang = Some(Shebang {
+            line: shebang.into(),
+            new_line: "\n".to_owned(),
+        });
+    }
+
+    #[inline]
+    pub fn get_shebang(&self) -> Option<&str> {
+        self.shebang.as_ref().map(|shebang| shebang.line.as_str())
+    }
+
+    #[inline]
+    pub fn take_shebang(&mut self) -> Option<String> {
+        self.shebang.take().map(|shebang| shebang.line)
+    }
+
+    /// Sets the new line that ends the shebang line (`\n` by default), so that the
+    /// line endings of the original code can be kept. Does nothing when the block
+    /// does not have a shebang.
+    pub fn set_shebang_new_line(&mut self, new_line: impl Into<String>) {
+        if let Some(shebang) = &mut self.shebang {
+            shebang.new_line = new_line.into();
+        }
+    }
+
+    #[inline]
+    pub fn get_shebang_new_line(&self) -> Option<&str> {
+        self.shebang
+            .as_ref()
+            .map(|shebang| shebang.new_line.as_str())
+    }
+
+    /// Iterates over the Luau directives (like `--!strict` or `--!native`) found in the
+    /// comments before the first statement of the block, without the `--!` prefix. Since
+    /// comments are only available when tokens are preserved, the original code is needed
+    /// to read them.
+    pub fn luau_directives<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a str> {
+        self.first_token()
+            .into_iter()
+            .flat_map(Token::iter_leading_trivia)
+            .filter(|trivia| trivia.kind() == TriviaKind::Comment)
+            .filter_map(move |trivia| trivia.read(code).strip_prefix("--!"))
+            .map(str::trim_end)
+    }
+
+    fn first_token(&self) -> Option<&Token> {
+        if let Some(statement) = self.statements.first() {
+            statement.get_first_token()
+        } else if let Some(last_statement) = &self.last_statement {
+            last_statement.get_first_token()
+        } else {
+            self.get_tokens()
+                .and_then(|tokens| tokens.final_token.as_ref())
         }
     }
 
@@ -116,6 +194,28 @@ impl Block {
         self.statements.iter()
     }
 
+    /// Iterates over the type declarations defined directly in this block (type
+    /// declarations are only allowed at the top level of blocks).
+    pub fn iter_type_declarations(&self) -> impl Iterator<Item = &TypeDeclarationStatement> {
+        self.statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::TypeDeclaration(declaration) => Some(declaration),
+                _ => None,
+            })
+    }
+
+    pub fn iter_mut_type_declarations(
+        &mut self,
+    ) -> impl Iterator<Item = &mut TypeDeclarationStatement> {
+        self.statements
+            .iter_mut()
+            .filter_map(|statement| match statement {
+                Statement::TypeDeclaration(declaration) => Some(declaration),
+                _ => None,
+            })
+    }
+
     #[inline]
     pub fn reverse_iter_statements(&self) -> impl Iterator<Item = &Statement> {
         self.statements.iter().rev()
@@ -126,46 +226,115 @@ impl Block {
         self.last_statement.as_ref()
     }
 
-    pub fn filter_statements<F>(&mut self, mut f: F)
+    pub fn filter_statements<F>(&mut self, f: F)
     where
         F: FnMut(&Statement) -> bool,
     {
-        let mut i = 0;
-
-        while i != self.statements.len() {
-            if f(&self.statements[i]) {
-                i += 1;
-            } else {
-                self.statements.remove(i);
-
-                if let Some(tokens) = &mut self.tokens {
-                    if i < tokens.semicolons.len() {
-                        tokens.semicolons.remove(i);
-                    }
-                }
-            }
+        self.retain_statements(f);
+    }
+
+    /// Keeps only the statements for which the predicate returns true. The semicolon
+    /// tokens of the removed statements are removed with them, so that the remaining
+    /// statements keep their own semicolons.
+    pub fn retain_statements<F>(&mut self, f: F)
+    where
+        F: FnMut(&Statement) -> bool,
+    {
+        let keep: Vec<bool> = self.statements.iter().map(f).collect();
+        self.retain_from_flags(&keep);
+    }
+
+    fn retain_from_flags(&mut self, keep: &[bool]) {
+        if let Some(tokens) = &mut self.tokens {
+            let mut keep_semicolons = keep.iter();
+            tokens
+                .semicolons
+                .retain(|_| keep_semicolons.next().copied().unwrap_or(true));
         }
+
+        let mut keep_statements = keep.iter();
+        self.statements
+            .retain(|_| keep_statements.next().copied().unwrap_or(true));
     }
 
-    pub fn filter_mut_statements<F>(&mut self, mut f: F)
+    /// Replaces the statements in the given range with the replacement statements and
+    /// returns the removed statements. The last statement of the block is not modified.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds of the block statements.
+    pub fn splice<R>(&mut self, range: R, replacement: Vec<Statement>) -> Vec<Statement>
     where
-        F: FnMut(&mut Statement) -> bool,
+        R: RangeBounds<usize>,
     {
-        let mut i = 0;
-
-        while i != self.statements.len() {
-            if f(&mut self.statements[i]) {
-                i += 1;
-            } else {
-                self.statements.remove(i);
-
-                if let Some(tokens) = &mut self.tokens {
-                    if i < tokens.semicolons.len() {
-                        tokens.semicolons.remove(i);
-                    }
-                }
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end + 1,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.statements.len(),
+        };
+
+        if let Some(tokens) = &mut self.tokens {
+            let semicolons_len = tokens.semicolons.len();
+            if start <= semicolons_len {
+                let semicolons_end = end.min(semicolons_len);
+                tokens
+                    .semicolons
+                    .splice(start..semicolons_end, replacement.iter().map(|_| None));
             }
         }
+
+        self.statements.splice(start..end, replacement).collect()
+    }
+
+    /// Appends the statements of another block. When this block has a last statement,
+    /// it gets wrapped into a `do` statement so that the result is still a valid block.
+    /// The last statement of the other block becomes the last statement of this block.
+    pub fn extend(&mut self, mut other: Block) {
+        if other.is_empty() {
+            return;
+        }
+
+        if let Some(last_statement) = self.take_last_statement() {
+            self.push_statement(DoStatement::new(Block::from(last_statement)));
+        }
+
+        let other_semicolons = other
+            .tokens
+            .as_mut()
+            .map(|tokens| std::mem::take(&mut tokens.semicolons))
+            .unwrap_or_default();
+        let other_last_semicolon = other
+            .tokens
+            .as_mut()
+            .and_then(|tokens| tokens.last_semicolon.take());
+
+        if let Some(tokens) = &mut self.tokens {
+            tokens.semicolons.resize(self.statements.len(), None);
+
+            let mut other_semicolons = other_semicolons.into_iter();
+            tokens.semicolons.extend(
+                other
+                    .statements
+                    .iter()
+                    .map(|_| other_semicolons.next().flatten()),
+            );
+            tokens.last_semicolon = other_last_semicolon;
+        }
+
+        self.statements.append(&mut other.statements);
+        self.last_statement = other.last_statement;
+    }
+
+    pub fn filter_mut_statements<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Statement) -> bool,
+    {
+        let keep: Vec<bool> = self.statements.iter_mut().map(f).collect();
+        self.retain_from_flags(&keep);
     }
 
     pub fn truncate(&mut self, length: usize) {
@@ -266,7 +435,7 @@ impl From<ReturnStatement> for Block {
 mod test {
     use super::*;
     use crate::{
-        nodes::{DoStatement, RepeatStatement},
+        nodes::{LocalAssignStatement, RepeatStatement},
         Parser,
     };
 
@@ -275,6 +444,77 @@ mod test {
         parser.parse(lua).expect("code should parse")
     }
 
+    fn luau_directives(lua: &str) -> Vec<String> {
+        parse_block_with_tokens(lua)
+            .luau_directives(lua)
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn luau_directives_from_first_statement() {
+        assert_eq!(
+            luau_directives("--!strict\n--!native\nlocal a = 1"),
+            vec!["strict", "native"]
+        );
+    }
+
+    #[test]
+    fn luau_directives_after_shebang() {
+        assert_eq!(
+            luau_directives("#!/usr/bin/env luau\n--!strict\nreturn 1"),
+            vec!["strict"]
+        );
+    }
+
+    #[test]
+    fn luau_directives_ignore_regular_comments() {
+        assert_eq!(
+            luau_directives("-- module\n--!optimize 2\nprint()"),
+            vec!["optimize 2"]
+        );
+    }
+
+    #[test]
+    fn luau_directives_in_empty_block() {
+        assert_eq!(luau_directives("--!nocheck\n"), vec!["nocheck"]);
+    }
+
+    #[test]
+    fn luau_directives_without_tokens_is_empty() {
+        let code = "--!strict\nlocal a = 1";
+        let block = Parser::default().parse(code).unwrap();
+
+        assert_eq!(block.luau_directives(code).count(), 0);
+    }
+
+    #[test]
+    fn set_shebang_new_line() {
+        let mut block = Block::default().with_shebang("#!/usr/bin/env lua");
+
+        assert_eq!(block.get_shebang_new_line(), Some("\n"));
+
+        block.set_shebang_new_line("\r\n");
+
+        assert_eq!(block.get_shebang_new_line(), Some("\r\n"));
+    }
+
+    #[test]
+    fn set_shebang_new_line_without_shebang() {
+        let mut block = Block::default();
+        block.set_shebang_new_line("\r\n");
+
+        assert_eq!(block.get_shebang_new_line(), None);
+    }
+
+    #[test]
+    fn take_shebang() {
+        let mut block = Block::default().with_shebang("#!/usr/bin/env lua");
+
+        assert_eq!(block.take_shebang().as_deref(), Some("#!/usr/bin/env lua"));
+        assert_eq!(block.get_shebang(), None);
+    }
+
     fn parse_statement_with_tokens(lua: &str) -> Statement {
         let mut block = parse_block_with_tokens(lua);
         assert!(block.get_last_statement().is_none());
@@ -283,6 +523,52 @@ mod test {
         statements.into_iter().next().unwrap()
     }
 
+    #[test]
+    fn iter_type_declarations() {
+        let block = Parser::default()
+            .parse("type A = string local a = 1 export type B = A do type C = B end")
+            .unwrap();
+
+        assert_eq!(
+            block
+                .iter_type_declarations()
+                .map(|declaration| declaration.get_name().get_name().as_str())
+                .collect::<Vec<_>>(),
+            vec!["A", "B"]
+        );
+    }
+
+    #[test]
+    fn iter_exported_type_declarations() {
+        let block = Parser::default()
+            .parse("type A = string export type B = A export type C<T> = { T }")
+            .unwrap();
+
+        assert_eq!(
+            block
+                .iter_type_declarations()
+                .filter(|declaration| declaration.is_exported())
+                .map(|declaration| declaration.get_name().get_name().as_str())
+                .collect::<Vec<_>>(),
+            vec!["B", "C"]
+        );
+    }
+
+    #[test]
+    fn iter_mut_type_declarations_to_remove_exports() {
+        let mut block = Parser::default()
+            .parse("export type A = string export type B = A")
+            .unwrap();
+
+        block
+            .iter_mut_type_declarations()
+            .for_each(TypeDeclarationStatement::remove_exported);
+
+        assert!(block
+            .iter_type_declarations()
+            .all(|declaration| !declaration.is_exported()));
+    }
+
     #[test]
     fn default_block_is_empty() {
         let block = Block::default();
@@ -563,4 +849,229 @@ mod test {
             })
         );
     }
+
+    fn local(name: &str) -> Statement {
+        LocalAssignStatement::from_variable(name).into()
+    }
+
+    #[test]
+    fn extend_empty_block() {
+        let mut block = Block::default();
+        let other = Block::new(vec![local("a")], Some(LastStatement::new_break()));
+
+        block.extend(other.clone());
+
+        pretty_assertions::assert_eq!(block, other);
+    }
+
+    #[test]
+    fn extend_with_empty_block_keeps_last_statement() {
+        let mut block = Block::new(vec![local("a")], Some(LastStatement::new_break()));
+        let original = block.clone();
+
+        block.extend(Block::default());
+
+        pretty_assertions::assert_eq!(block, original);
+    }
+
+    #[test]
+    fn extend_appends_statements_and_last_statement() {
+        let mut block = Block::new(vec![local("a")], None);
+
+        block.extend(Block::new(
+            vec![local("b")],
+            Some(LastStatement::new_continue()),
+        ));
+
+        pretty_assertions::assert_eq!(
+            block,
+            Block::new(
+                vec![local("a"), local("b")],
+                Some(LastStatement::new_continue())
+            )
+        );
+    }
+
+    #[test]
+    fn extend_with_last_statements_on_both_sides() {
+        let mut block = Block::new(vec![local("a")], Some(LastStatement::new_break()));
+
+        block.extend(Block::new(
+            vec![local("b")],
+            Some(ReturnStatement::default().into()),
+        ));
+
+        pretty_assertions::assert_eq!(
+            block,
+            Block::new(
+                vec![
+                    local("a"),
+                    DoStatement::new(LastStatement::new_break().into()).into(),
+                    local("b"),
+                ],
+                Some(ReturnStatement::default().into())
+            )
+        );
+    }
+
+    #[test]
+    fn extend_block_with_last_statement_with_statements_only() {
+        let mut block = Block::from(LastStatement::new_break());
+
+        block.extend(Block::new(vec![local("a")], None));
+
+        pretty_assertions::assert_eq!(
+            block,
+            Block::new(
+                vec![
+                    DoStatement::new(LastStatement::new_break().into()).into(),
+                    local("a"),
+                ],
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn extend_with_tokens_keeps_semicolons() {
+        let mut block = parse_block_with_tokens("local a; local b");
+
+        block.extend(parse_block_with_tokens("local c; return;"));
+
+        pretty_assertions::assert_eq!(block.statements_len(), 3);
+        let tokens = block.get_tokens().unwrap();
+        pretty_assertions::assert_eq!(
+            tokens
+                .semicolons
+                .iter()
+                .map(Option::is_some)
+                .collect::<Vec<_>>(),
+            vec![true, false, true]
+        );
+        assert!(tokens.last_semicolon.is_some());
+    }
+
+    #[test]
+    fn splice_replaces_statements_in_range() {
+        let mut block = Block::new(
+            vec![local("a"), local("b"), local("c")],
+            Some(LastStatement::new_break()),
+        );
+
+        let removed = block.splice(1..2, vec![local("d"), local("e")]);
+
+        pretty_assertions::assert_eq!(removed, vec![local("b")]);
+        pretty_assertions::assert_eq!(
+            block,
+            Block::new(
+                vec![local("a"), local("d"), local("e"), local("c")],
+                Some(LastStatement::new_break())
+            )
+        );
+    }
+
+    #[test]
+    fn splice_empty_range_inserts_statements() {
+        let mut block = Block::new(vec![local("a")], None);
+
+        let removed = block.splice(0..0, vec![local("b"), local("c")]);
+
+        assert!(removed.is_empty());
+        pretty_assertions::assert_eq!(
+            block,
+            Block::new(vec![local("b"), local("c"), local("a")], None)
+        );
+    }
+
+    #[test]
+    fn splice_with_empty_replacement_removes_statements() {
+        let mut block = Block::new(
+            vec![local("a"), local("b"), local("c")],
+            Some(LastStatement::new_continue()),
+        );
+
+        let removed = block.splice(..2, Vec::new());
+
+        pretty_assertions::assert_eq!(removed, vec![local("a"), local("b")]);
+        pretty_assertions::assert_eq!(
+            block,
+            Block::new(vec![local("c")], Some(LastStatement::new_continue()))
+        );
+    }
+
+    #[test]
+    fn splice_empty_block() {
+        let mut block = Block::default();
+
+        block.splice(.., vec![local("a")]);
+
+        pretty_assertions::assert_eq!(block, Block::new(vec![local("a")], None));
+    }
+
+    #[test]
+    fn splice_with_tokens_keeps_semicolons_aligned() {
+        let mut block = parse_block_with_tokens("local a; local b; local c;");
+
+        block.splice(1..=1, vec![local("d"), local("e")]);
+
+        pretty_assertions::assert_eq!(block.statements_len(), 4);
+        pretty_assertions::assert_eq!(
+            block
+                .get_tokens()
+                .unwrap()
+                .semicolons
+                .iter()
+                .map(Option::is_some)
+                .collect::<Vec<_>>(),
+            vec![true, false, false, true]
+        );
+    }
+
+    #[test]
+    fn retain_statements_keeps_last_statement() {
+        let mut block = Block::new(
+            vec![local("a"), local("b")],
+            Some(LastStatement::new_break()),
+        );
+
+        block.retain_statements(|statement| statement != &local("a"));
+
+        pretty_assertions::assert_eq!(
+            block,
+            Block::new(vec![local("b")], Some(LastStatement::new_break()))
+        );
+    }
+
+    #[test]
+    fn retain_statements_of_empty_block() {
+        let mut block = Block::default();
+
+        block.retain_statements(|_| false);
+
+        assert!(block.is_empty());
+    }
+
+    #[test]
+    fn retain_statements_removes_semicolons_of_removed_statements() {
+        let mut block = parse_block_with_tokens("local a local b; local c");
+
+        block.retain_statements(|statement| match statement {
+            Statement::LocalAssign(assign) => assign
+                .iter_variables()
+                .all(|variable| variable.get_name() != "a"),
+            _ => true,
+        });
+
+        pretty_assertions::assert_eq!(block.statements_len(), 2);
+        pretty_assertions::assert_eq!(
+            block
+                .get_tokens()
+                .unwrap()
+                .semicolons
+                .iter()
+                .map(Option::is_some)
+                .collect::<Vec<_>>(),
+            vec![true, false]
+        );
+    }
 }
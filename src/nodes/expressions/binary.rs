@@ -1,4 +1,4 @@
-use crate::nodes::{Expression, FunctionReturnType, Token, Type};
+use crate::nodes::{Expression, FunctionReturnType, ParentheseExpression, Token, Type};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BinaryOperator {
@@ -48,6 +48,19 @@ fn ends_with_if_expression(expression: &Expression) -> bool {
     }
 }
 
+fn ends_with_if_expression_or_type_cast(expression: &Expression) -> bool {
+    let mut current = expression;
+
+    loop {
+        match current {
+            Expression::If(_) | Expression::TypeCast(_) => break true,
+            Expression::Binary(binary) => current = binary.right(),
+            Expression::Unary(unary) => current = unary.get_expression(),
+            _ => break false,
+        }
+    }
+}
+
 #[inline]
 fn ends_with_type_cast_to_type_name_without_type_parameters(expression: &Expression) -> bool {
     let mut current = expression;
@@ -164,6 +177,21 @@ impl BinaryOperator {
         }
     }
 
+    /// Returns true if the parentheses around the left operand can be removed without
+    /// changing how the expression is parsed. Operands that end with an if expression
+    /// or a type cast keep their parentheses, because these would extend to the right.
+    pub fn left_parentheses_are_redundant(&self, left: &ParentheseExpression) -> bool {
+        let inner = left.innermost_expression();
+        !self.left_needs_parentheses(inner) && !ends_with_if_expression_or_type_cast(inner)
+    }
+
+    /// Returns true if the parentheses around the right operand can be removed without
+    /// changing how the expression is parsed.
+    pub fn right_parentheses_are_redundant(&self, right: &ParentheseExpression) -> bool {
+        let inner = right.innermost_expression();
+        !self.right_needs_parentheses(inner) && !ends_with_if_expression_or_type_cast(inner)
+    }
+
     pub fn to_str(&self) -> &'static str {
         match self {
             Self::And => "and",
@@ -456,4 +484,89 @@ mod test {
             assert!(!And.precedes_unary_expression());
         }
     }
+
+    mod redundant_parentheses {
+        use super::*;
+
+        use crate::nodes::{
+            IfExpression, TypeCastExpression, TypeName, UnaryExpression, UnaryOperator,
+        };
+
+        fn parentheses<E: Into<Expression>>(expression: E) -> ParentheseExpression {
+            ParentheseExpression::new(expression)
+        }
+
+        fn binary(operator: BinaryOperator) -> BinaryExpression {
+            BinaryExpression::new(
+                operator,
+                Expression::identifier("a"),
+                Expression::identifier("b"),
+            )
+        }
+
+        #[test]
+        fn around_identifier() {
+            let identifier = parentheses(Expression::identifier("a"));
+            assert!(BinaryOperator::Plus.left_parentheses_are_redundant(&identifier));
+            assert!(BinaryOperator::Plus.right_parentheses_are_redundant(&identifier));
+        }
+
+        #[test]
+        fn around_doubled_parentheses() {
+            let doubled = parentheses(parentheses(binary(BinaryOperator::Asterisk)));
+            assert!(BinaryOperator::Plus.left_parentheses_are_redundant(&doubled));
+            assert!(BinaryOperator::Plus.right_parentheses_are_redundant(&doubled));
+        }
+
+        #[test]
+        fn around_lower_precedence_operand() {
+            let sum = parentheses(binary(BinaryOperator::Plus));
+            assert!(!BinaryOperator::Asterisk.left_parentheses_are_redundant(&sum));
+            assert!(!BinaryOperator::Asterisk.right_parentheses_are_redundant(&sum));
+        }
+
+        #[test]
+        fn around_same_precedence_operand() {
+            let difference = parentheses(binary(BinaryOperator::Minus));
+            assert!(BinaryOperator::Minus.left_parentheses_are_redundant(&difference));
+            assert!(!BinaryOperator::Minus.right_parentheses_are_redundant(&difference));
+
+            let power = parentheses(binary(BinaryOperator::Caret));
+            assert!(!BinaryOperator::Caret.left_parentheses_are_redundant(&power));
+            assert!(BinaryOperator::Caret.right_parentheses_are_redundant(&power));
+        }
+
+        #[test]
+        fn around_unary_operand() {
+            let negative = parentheses(UnaryExpression::new(
+                UnaryOperator::Minus,
+                Expression::identifier("a"),
+            ));
+            assert!(BinaryOperator::Plus.left_parentheses_are_redundant(&negative));
+            assert!(!BinaryOperator::Caret.left_parentheses_are_redundant(&negative));
+            assert!(BinaryOperator::Caret.right_parentheses_are_redundant(&negative));
+        }
+
+        #[test]
+        fn around_if_expression() {
+            let if_expression = parentheses(IfExpression::new(
+                Expression::identifier("a"),
+                Expression::identifier("b"),
+                Expression::identifier("c"),
+            ));
+            assert!(!BinaryOperator::Plus.left_parentheses_are_redundant(&if_expression));
+            assert!(!BinaryOperator::Plus.right_parentheses_are_redundant(&if_expression));
+        }
+
+        #[test]
+        fn around_operand_ending_with_type_cast() {
+            let type_cast = parentheses(BinaryExpression::new(
+                BinaryOperator::Asterisk,
+                Expression::identifier("a"),
+                TypeCastExpression::new(Expression::identifier("b"), TypeName::new("T")),
+            ));
+            assert!(!BinaryOperator::Plus.left_parentheses_are_redundant(&type_cast));
+            assert!(!BinaryOperator::Plus.right_parentheses_are_redundant(&type_cast));
+        }
+    }
 }
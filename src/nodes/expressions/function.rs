@@ -80,6 +80,14 @@ impl FunctionExpression {
         self.return_type.as_mut()
     }
 
+    /// Removes the return type annotation of the function and returns it.
+    pub fn remove_return_type(&mut self) -> Option<FunctionReturnType> {
+        if let Some(tokens) = &mut self.tokens {
+            tokens.return_type_colon.take();
+        }
+        self.return_type.take()
+    }
+
     pub fn variadic(mut self) -> Self {
         self.is_variadic = true;
         self
@@ -127,6 +135,11 @@ impl FunctionExpression {
         self.generic_parameters.as_ref()
     }
 
+    #[inline]
+    pub fn mutate_generic_parameters(&mut self) -> Option<&mut GenericParameters> {
+        self.generic_parameters.as_mut()
+    }
+
     #[inline]
     pub fn is_generic(&self) -> bool {
         self.generic_parameters.is_some()
@@ -201,8 +214,78 @@ impl FunctionExpression {
         }
         if let Some(tokens) = &mut self.tokens {
             tokens.variable_arguments_colon.take();
+            tokens.return_type_colon.take();
         }
     }
 
     super::impl_token_fns!(iter = [parameters, generic_parameters, tokens]);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        generator::{LuaGenerator, TokenBasedLuaGenerator},
+        nodes::{Expression, LastStatement, ReturnStatement, TypeName},
+        Parser,
+    };
+
+    fn parse_function(code: &str) -> FunctionExpression {
+        let mut block = Parser::default().preserve_tokens().parse(code).unwrap();
+        let mut statement = match block.take_last_statement() {
+            Some(LastStatement::Return(statement)) => statement,
+            _ => panic!("return statement expected"),
+        };
+        let function = match statement.iter_mut_expressions().next() {
+            Some(Expression::Function(function)) => std::mem::take(function),
+            _ => panic!("function expression expected"),
+        };
+        function
+    }
+
+    fn generate(function: FunctionExpression, code: &str) -> String {
+        let mut generator = TokenBasedLuaGenerator::new(code);
+        generator.write_last_statement(&ReturnStatement::one(function).into());
+        generator.into_string()
+    }
+
+    #[test]
+    fn mutate_return_type_is_generated() {
+        let code = "return function(a: number): string return a end";
+        let mut function = parse_function(code);
+
+        *function.mutate_return_type().unwrap() = TypeName::new("boolean").into();
+
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "return function(a: number): boolean return a end"
+        );
+    }
+
+    #[test]
+    fn remove_return_type() {
+        let code = "return function(a: number): string return a end";
+        let mut function = parse_function(code);
+
+        assert!(function.remove_return_type().is_some());
+        assert!(!function.has_return_type());
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "return function(a: number)return a end"
+        );
+    }
+
+    #[test]
+    fn clear_types_removes_all_annotations() {
+        let code = "return function<T>(a: T, ...: string): T return a end";
+        let mut function = parse_function(code);
+
+        function.clear_types();
+
+        assert!(!function.is_generic());
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "return function(a, ...)return a end"
+        );
+    }
+}
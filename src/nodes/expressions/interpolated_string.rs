@@ -12,7 +12,9 @@ pub struct StringSegment {
 
 impl StringSegment {
     pub fn new(value: impl AsRef<str>) -> Result<Self, StringError> {
-        string_utils::read_escaped_string(value.as_ref().char_indices(), None).map(Self::from_value)
+        // segments are kept as text: bytes that are not valid UTF-8 are replaced
+        string_utils::read_escaped_string(value.as_ref().char_indices(), None)
+            .map(|value| Self::from_value(String::from_utf8_lossy(&value)))
     }
 
     pub fn from_value(value: impl Into<String>) -> Self {
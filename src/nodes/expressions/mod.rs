@@ -29,8 +29,9 @@ pub use type_cast::*;
 pub use unary::*;
 
 use crate::nodes::{FunctionCall, Identifier, Token, Variable};
+use crate::process::Evaluator;
 
-use super::impl_token_fns;
+use super::{impl_token_fns, impl_variant_accessors};
 
 use std::num::FpCategory;
 
@@ -57,6 +58,32 @@ pub enum Expression {
 }
 
 impl Expression {
+    impl_variant_accessors!(Expression {
+        Binary(BinaryExpression) => as_binary, as_binary_mut,
+            BinaryExpression::new(BinaryOperator::And, true, false);
+        Call(FunctionCall) => as_call, as_call_mut, FunctionCall::from_name("print");
+        Field(FieldExpression) => as_field, as_field_mut,
+            FieldExpression::new(Prefix::from_name("object"), "field");
+        Function(FunctionExpression) => as_function, as_function_mut,
+            FunctionExpression::from_block(Block::default());
+        Identifier(Identifier) => as_identifier, as_identifier_mut, Identifier::new("name");
+        If(IfExpression) => as_if, as_if_mut, IfExpression::new(true, 1.0, 2.0);
+        Index(IndexExpression) => as_index, as_index_mut,
+            IndexExpression::new(Prefix::from_name("object"), 1.0);
+        Number(NumberExpression) => as_number, as_number_mut,
+            NumberExpression::from(DecimalNumber::new(1.0));
+        Parenthese(ParentheseExpression) => as_parenthese, as_parenthese_mut,
+            ParentheseExpression::new(true);
+        String(StringExpression) => as_string, as_string_mut, StringExpression::from_value("text");
+        InterpolatedString(InterpolatedStringExpression) => as_interpolated_string,
+            as_interpolated_string_mut, InterpolatedStringExpression::empty();
+        Table(TableExpression) => as_table, as_table_mut, TableExpression::default();
+        Unary(UnaryExpression) => as_unary, as_unary_mut,
+            UnaryExpression::new(UnaryOperator::Not, true);
+        TypeCast(TypeCastExpression) => as_type_cast, as_type_cast_mut,
+            TypeCastExpression::new(true, TypeName::new("any"));
+    });
+
     #[inline]
     pub fn nil() -> Self {
         Self::Nil(None)
@@ -74,6 +101,43 @@ impl Expression {
     pub fn in_parentheses(self) -> Self {
         Self::Parenthese(ParentheseExpression::new(self).into())
     }
+
+    /// Returns true if the expression is a `nil`, boolean, number or string literal.
+    /// Parentheses and type casts around a literal are ignored.
+    pub fn is_literal(&self) -> bool {
+        match self {
+            Self::False(_) | Self::Nil(_) | Self::Number(_) | Self::String(_) | Self::True(_) => {
+                true
+            }
+            Self::Parenthese(parenthese) => parenthese.inner_expression().is_literal(),
+            Self::TypeCast(type_cast) => type_cast.get_expression().is_literal(),
+            Self::Binary(_)
+            | Self::Call(_)
+            | Self::Field(_)
+            | Self::Function(_)
+            | Self::Identifier(_)
+            | Self::If(_)
+            | Self::Index(_)
+            | Self::InterpolatedString(_)
+            | Self::Table(_)
+            | Self::Unary(_)
+            | Self::VariableArguments(_) => false,
+        }
+    }
+
+    /// Returns `Some(true)` if the expression always evaluates to a truthy value,
+    /// `Some(false)` if it always evaluates to `nil` or `false`, and `None` when it
+    /// can not be known. As in Lua, `0` and empty strings are truthy.
+    pub fn truthiness(&self) -> Option<bool> {
+        Evaluator::default().evaluate(self).is_truthy()
+    }
+
+    /// Returns true if evaluating the expression may have side effects. Indexing
+    /// tables is considered to have side effects since metamethods could be called.
+    /// Use an [`Evaluator`] directly to assume that metamethods are pure.
+    pub fn has_side_effects(&self) -> bool {
+        Evaluator::default().has_side_effects(self)
+    }
 }
 
 impl From<bool> for Expression {
@@ -351,4 +415,65 @@ mod test {
             f64_minus_zero => -0.0,
         );
     }
+
+    mod classification {
+        use super::super::*;
+        use crate::nodes::{BinaryOperator, TableExpression, TypeCastExpression, TypeName};
+
+        fn not(expression: impl Into<Expression>) -> Expression {
+            UnaryExpression::new(UnaryOperator::Not, expression).into()
+        }
+
+        macro_rules! test_classification {
+            ($($name:ident ($expression:expr) => ($literal:expr, $truthiness:expr, $side_effects:expr)),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let expression = Expression::from($expression);
+
+                        assert_eq!(expression.is_literal(), $literal, "is_literal");
+                        assert_eq!(expression.truthiness(), $truthiness, "truthiness");
+                        assert_eq!(expression.has_side_effects(), $side_effects, "has_side_effects");
+                    }
+                )*
+            };
+        }
+
+        test_classification!(
+            nil(Expression::nil()) => (true, Some(false), false),
+            false_expression(false) => (true, Some(false), false),
+            true_expression(true) => (true, Some(true), false),
+            zero(0) => (true, Some(true), false),
+            empty_string(StringExpression::empty()) => (true, Some(true), false),
+            string(StringExpression::from_value("hello")) => (true, Some(true), false),
+            table(TableExpression::default()) => (false, Some(true), false),
+            function(FunctionExpression::default()) => (false, Some(true), false),
+            identifier(Expression::identifier("var")) => (false, None, false),
+            variable_arguments(Expression::variable_arguments()) => (false, None, false),
+            call(FunctionCall::from_name("call")) => (false, None, true),
+            field(FieldExpression::new(Prefix::from_name("object"), "field")) => (false, None, true),
+            index(IndexExpression::new(Prefix::from_name("object"), 1)) => (false, None, true),
+            parenthese_nil(Expression::nil().in_parentheses()) => (true, Some(false), false),
+            parenthese_zero(Expression::from(0).in_parentheses()) => (true, Some(true), false),
+            parenthese_call(Expression::from(FunctionCall::from_name("call")).in_parentheses())
+                => (false, None, true),
+            type_cast_true(TypeCastExpression::new(true, TypeName::new("boolean")))
+                => (true, Some(true), false),
+            not_nil(not(Expression::nil())) => (false, Some(true), false),
+            not_zero(not(0)) => (false, Some(false), false),
+            not_empty_string(not(StringExpression::empty())) => (false, Some(false), false),
+            not_identifier(not(Expression::identifier("var"))) => (false, None, false),
+            not_not_false(not(not(false))) => (false, Some(false), false),
+            not_parenthese_true(not(Expression::from(true).in_parentheses()))
+                => (false, Some(false), false),
+            not_call(not(FunctionCall::from_name("call"))) => (false, None, true),
+            false_or_zero(BinaryExpression::new(BinaryOperator::Or, false, 0))
+                => (false, Some(true), false),
+            nil_and_call(BinaryExpression::new(
+                BinaryOperator::And,
+                Expression::nil(),
+                FunctionCall::from_name("call"),
+            )) => (false, Some(false), false),
+        );
+    }
 }
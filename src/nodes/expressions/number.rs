@@ -202,6 +202,51 @@ pub enum NumberExpression {
 }
 
 impl NumberExpression {
+    /// Creates a decimal number from a value, written with the fewest digits that
+    /// parse back to exactly the same value (using an exponent when it is shorter).
+    /// Returns `None` for values that a number literal cannot represent: negative
+    /// values (including `-0.0`), infinities and NaN.
+    pub fn from_f64_shortest(value: f64) -> Option<Self> {
+        if !value.is_finite() || value.is_sign_negative() {
+            return None;
+        }
+
+        let plain = format!("{}", value);
+        let scientific = format!("{:e}", value);
+
+        let number = if scientific.len() < plain.len() {
+            let exponent = scientific
+                .split_once('e')
+                .and_then(|(_, exponent)| exponent.parse().ok())
+                .expect("scientific notation should have an exponent");
+            DecimalNumber::new(value).with_exponent(exponent, true)
+        } else {
+            DecimalNumber::new(value)
+        };
+
+        Some(number.into())
+    }
+
+    /// Returns true if the number is finite and does not have a fractional part.
+    pub fn is_integer(&self) -> bool {
+        let value = self.compute_value();
+        value.is_finite() && value.fract() == 0.0
+    }
+
+    /// Returns the value as an integer if it is an integer that fits exactly in
+    /// an `i64`. Note that above 2^53, the value is the integer stored by the
+    /// float, which may not be the integer written in the original code.
+    pub fn as_integer(&self) -> Option<i64> {
+        const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+
+        let value = self.compute_value();
+        if self.is_integer() && (-TWO_POW_63..TWO_POW_63).contains(&value) {
+            Some(value as i64)
+        } else {
+            None
+        }
+    }
+
     pub fn set_uppercase(&mut self, is_uppercase: bool) {
         match self {
             Self::Decimal(number) => number.set_uppercase(is_uppercase),
@@ -487,6 +532,91 @@ mod test {
         }
     }
 
+    mod from_f64_shortest {
+        use super::*;
+
+        macro_rules! test_shortest {
+            ($($name:ident($value:expr) => $expected:expr),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let number = NumberExpression::from_f64_shortest($value);
+                        assert_eq!(number, $expected.map(NumberExpression::from));
+                        if let Some(number) = number {
+                            assert_eq!(number.compute_value().to_bits(), ($value as f64).to_bits());
+                        }
+                    }
+                )*
+            };
+        }
+
+        test_shortest!(
+            zero(0.0) => Some(DecimalNumber::new(0.0)),
+            one_tenth(0.1) => Some(DecimalNumber::new(0.1)),
+            sum_of_tenths(0.1 + 0.2) => Some(DecimalNumber::new(0.30000000000000004)),
+            three_tenths(0.3) => Some(DecimalNumber::new(0.3)),
+            integer(1500.0) => Some(DecimalNumber::new(1500.0)),
+            large_power_of_ten(1e300) => Some(DecimalNumber::new(1e300).with_exponent(300, true)),
+            small_value(1.5e-7) => Some(DecimalNumber::new(1.5e-7).with_exponent(-7, true)),
+            two_pow_53(9_007_199_254_740_992.0) => Some(DecimalNumber::new(9_007_199_254_740_992.0)),
+            two_pow_53_plus_one(9_007_199_254_740_993_u64 as f64)
+                => Some(DecimalNumber::new(9_007_199_254_740_992.0)),
+            negative_zero(-0.0) => None::<DecimalNumber>,
+            negative(-1.0) => None::<DecimalNumber>,
+            infinity(f64::INFINITY) => None::<DecimalNumber>,
+            nan(f64::NAN) => None::<DecimalNumber>,
+        );
+    }
+
+    mod integer {
+        use super::*;
+
+        #[test]
+        fn decimal_integer() {
+            let number = NumberExpression::from(DecimalNumber::new(42.0));
+
+            assert!(number.is_integer());
+            assert_eq!(number.as_integer(), Some(42));
+        }
+
+        #[test]
+        fn decimal_with_fraction() {
+            let number = NumberExpression::from(DecimalNumber::new(0.5));
+
+            assert!(!number.is_integer());
+            assert_eq!(number.as_integer(), None);
+        }
+
+        #[test]
+        fn hex_integer() {
+            let number = NumberExpression::from(HexNumber::new(0xFF, false));
+
+            assert_eq!(number.as_integer(), Some(255));
+        }
+
+        #[test]
+        fn integer_above_two_pow_53() {
+            let number = NumberExpression::from(DecimalNumber::new(9_007_199_254_740_994.0));
+
+            assert_eq!(number.as_integer(), Some(9_007_199_254_740_994));
+        }
+
+        #[test]
+        fn integer_too_large_for_i64() {
+            let number = NumberExpression::from(DecimalNumber::new(1e300));
+
+            assert!(number.is_integer());
+            assert_eq!(number.as_integer(), None);
+        }
+
+        #[test]
+        fn infinity_is_not_an_integer() {
+            let number = NumberExpression::from(DecimalNumber::new(f64::INFINITY));
+
+            assert!(!number.is_integer());
+        }
+    }
+
     mod parse_number {
         use super::*;
 
@@ -29,6 +29,16 @@ impl ParentheseExpression {
         &self.expression
     }
 
+    /// Returns the expression inside these parentheses and any parentheses directly
+    /// nested in them.
+    pub fn innermost_expression(&self) -> &Expression {
+        let mut expression = &self.expression;
+        while let Expression::Parenthese(parenthese) = expression {
+            expression = &parenthese.expression;
+        }
+        expression
+    }
+
     #[inline]
     pub fn into_inner_expression(self) -> Expression {
         self.expression
@@ -1,5 +1,6 @@
 use crate::nodes::{
-    Expression, FieldExpression, FunctionCall, Identifier, IndexExpression, ParentheseExpression,
+    impl_variant_accessors, Expression, FieldExpression, FunctionCall, Identifier, IndexExpression,
+    ParentheseExpression,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -12,6 +13,17 @@ pub enum Prefix {
 }
 
 impl Prefix {
+    impl_variant_accessors!(Prefix {
+        Call(FunctionCall) => as_call, as_call_mut, FunctionCall::from_name("print");
+        Field(FieldExpression) => as_field, as_field_mut,
+            FieldExpression::new(Prefix::from_name("object"), "field");
+        Identifier(Identifier) => as_identifier, as_identifier_mut, Identifier::new("name");
+        Index(IndexExpression) => as_index, as_index_mut,
+            IndexExpression::new(Prefix::from_name("object"), 1.0);
+        Parenthese(ParentheseExpression) => as_parenthese, as_parenthese_mut,
+            ParentheseExpression::new(true);
+    });
+
     pub fn from_name<S: Into<Identifier>>(name: S) -> Self {
         Self::Identifier(name.into())
     }
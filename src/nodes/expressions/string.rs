@@ -1,12 +1,14 @@
-use std::str::CharIndices;
+use std::borrow::Cow;
 
 use crate::nodes::{StringError, Token};
 
 use super::string_utils;
 
+/// A string literal. Lua strings are sequences of bytes, so the value of a string can
+/// contain data that is not valid UTF-8 (for example with escapes like `"\255"`).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StringExpression {
-    value: String,
+    value: Vec<u8>,
     token: Option<Token>,
 }
 
@@ -42,7 +44,7 @@ impl StringExpression {
 
         match (chars.next(), chars.next_back()) {
             (Some((_, first_char)), Some((_, last_char))) if first_char == last_char => {
-                string_utils::read_escaped_string(chars, Some(string.len())).map(Self::from_value)
+                string_utils::read_escaped_string(chars, Some(string.len())).map(Self::from_bytes)
             }
             (None, None) | (None, Some(_)) | (Some(_), None) => {
                 Err(StringError::invalid("missing quotes"))
@@ -53,12 +55,17 @@ impl StringExpression {
 
     pub fn empty() -> Self {
         Self {
-            value: "".to_owned(),
+            value: Vec::new(),
             token: None,
         }
     }
 
     pub fn from_value<T: Into<String>>(value: T) -> Self {
+        Self::from_bytes(value.into())
+    }
+
+    /// Creates a string from its bytes, which do not need to be valid UTF-8.
+    pub fn from_bytes<T: Into<Vec<u8>>>(value: T) -> Self {
         Self {
             value: value.into(),
             token: None,
@@ -81,53 +88,83 @@ impl StringExpression {
     }
 
     #[inline]
-    pub fn get_value(&self) -> &str {
+    pub fn as_bytes(&self) -> &[u8] {
         &self.value
     }
 
     #[inline]
-    pub fn into_value(self) -> String {
+    pub fn into_bytes(self) -> Vec<u8> {
         self.value
     }
 
+    /// Returns the value of the string if it is valid UTF-8.
+    #[inline]
+    pub fn get_string_value(&self) -> Option<&str> {
+        std::str::from_utf8(&self.value).ok()
+    }
+
+    /// Converts the string into its value if it is valid UTF-8.
+    #[inline]
+    pub fn into_string(self) -> Option<String> {
+        String::from_utf8(self.value).ok()
+    }
+
+    /// Returns the value of the string, replacing invalid UTF-8 sequences with the
+    /// replacement character.
+    #[deprecated(
+        note = "strings can contain bytes that are not valid UTF-8, use `get_string_value` or `as_bytes`"
+    )]
+    pub fn get_value(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.value)
+    }
+
+    /// Converts the string into its value, replacing invalid UTF-8 sequences with the
+    /// replacement character.
+    #[deprecated(
+        note = "strings can contain bytes that are not valid UTF-8, use `into_string` or `into_bytes`"
+    )]
+    pub fn into_value(self) -> String {
+        String::from_utf8(self.value)
+            .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned())
+    }
+
     pub fn is_multiline(&self) -> bool {
-        self.value.contains('\n')
+        self.value.contains(&b'\n')
     }
 
     pub fn has_single_quote(&self) -> bool {
-        self.find_not_escaped('\'').is_some()
+        self.find_not_escaped(b'\'').is_some()
     }
 
     pub fn has_double_quote(&self) -> bool {
-        self.find_not_escaped('"').is_some()
-    }
-
-    fn find_not_escaped(&self, pattern: char) -> Option<usize> {
-        self.find_not_escaped_from(pattern, &mut self.value.char_indices())
+        self.find_not_escaped(b'"').is_some()
     }
 
-    fn find_not_escaped_from(&self, pattern: char, chars: &mut CharIndices) -> Option<usize> {
+    fn find_not_escaped(&self, pattern: u8) -> Option<usize> {
         let mut escaped = false;
-        chars.find_map(|(index, character)| {
-            if escaped {
-                escaped = false;
-                None
-            } else {
-                match character {
-                    '\\' => {
-                        escaped = true;
-                        None
-                    }
-                    value => {
-                        if value == pattern {
-                            Some(index)
-                        } else {
+        self.value
+            .iter()
+            .enumerate()
+            .find_map(|(index, character)| {
+                if escaped {
+                    escaped = false;
+                    None
+                } else {
+                    match *character {
+                        b'\\' => {
+                            escaped = true;
                             None
                         }
+                        value => {
+                            if value == pattern {
+                                Some(index)
+                            } else {
+                                None
+                            }
+                        }
                     }
                 }
-            }
-        })
+            })
     }
 
     super::impl_token_fns!(iter = [token]);
@@ -148,8 +185,8 @@ mod test {
                         assert_eq!(
                             StringExpression::new(&quoted)
                                 .expect("unable to parse string")
-                                .get_value(),
-                            StringExpression::from_value($value).get_value(),
+                                .as_bytes(),
+                            StringExpression::from_value($value).as_bytes(),
                         );
                     }
                 )*
@@ -164,8 +201,8 @@ mod test {
                         assert_eq!(
                             StringExpression::new(&quoted)
                                 .expect("unable to parse string")
-                                .get_value(),
-                            StringExpression::from_value($value).get_value(),
+                                .as_bytes(),
+                            StringExpression::from_value($value).as_bytes(),
                         );
                     }
                 )*
@@ -237,56 +274,94 @@ mod test {
     fn new_removes_double_quotes() {
         let string = StringExpression::new(r#""hello""#).unwrap();
 
-        assert_eq!(string.get_value(), "hello");
+        assert_eq!(string.get_string_value(), Some("hello"));
     }
 
     #[test]
     fn new_removes_single_quotes() {
         let string = StringExpression::new("'hello'").unwrap();
 
-        assert_eq!(string.get_value(), "hello");
+        assert_eq!(string.get_string_value(), Some("hello"));
     }
 
     #[test]
     fn new_removes_double_brackets() {
         let string = StringExpression::new("[[hello]]").unwrap();
 
-        assert_eq!(string.get_value(), "hello");
+        assert_eq!(string.get_string_value(), Some("hello"));
     }
 
     #[test]
     fn new_removes_double_brackets_and_skip_first_new_line() {
         let string = StringExpression::new("[[\nhello]]").unwrap();
 
-        assert_eq!(string.get_value(), "hello");
+        assert_eq!(string.get_string_value(), Some("hello"));
     }
 
     #[test]
     fn new_removes_double_brackets_with_one_equals() {
         let string = StringExpression::new("[=[hello]=]").unwrap();
 
-        assert_eq!(string.get_value(), "hello");
+        assert_eq!(string.get_string_value(), Some("hello"));
     }
 
     #[test]
     fn new_removes_double_brackets_with_multiple_equals() {
         let string = StringExpression::new("[==[hello]==]").unwrap();
 
-        assert_eq!(string.get_value(), "hello");
+        assert_eq!(string.get_string_value(), Some("hello"));
     }
 
     #[test]
     fn new_skip_invalid_escape_in_double_quoted_string() {
         let string = StringExpression::new("'\\oo'").unwrap();
 
-        assert_eq!(string.get_value(), "oo");
+        assert_eq!(string.get_string_value(), Some("oo"));
     }
 
     #[test]
     fn new_skip_invalid_escape_in_single_quoted_string() {
         let string = StringExpression::new("\"\\oo\"").unwrap();
 
-        assert_eq!(string.get_value(), "oo");
+        assert_eq!(string.get_string_value(), Some("oo"));
+    }
+
+    #[test]
+    fn new_reads_decimal_escapes_as_bytes() {
+        let string = StringExpression::new("'\\255\\0\\1'").unwrap();
+
+        assert_eq!(string.as_bytes(), &[0xFF, 0, 1]);
+        assert_eq!(string.get_string_value(), None);
+    }
+
+    #[test]
+    fn new_reads_hexadecimal_escapes_as_bytes() {
+        let string = StringExpression::new("'\\xFFok'").unwrap();
+
+        assert_eq!(string.as_bytes(), b"\xFFok");
+    }
+
+    #[test]
+    fn new_reads_mixed_binary_and_text_content() {
+        let string = StringExpression::new("'caf\\u{E9} \\200\\201'").unwrap();
+
+        assert_eq!(string.as_bytes(), b"caf\xC3\xA9 \xC8\xC9");
+        assert!(string.into_string().is_none());
+    }
+
+    #[test]
+    fn from_bytes_keeps_valid_utf8_as_text() {
+        let string = StringExpression::from_bytes(b"hello".to_vec());
+
+        assert_eq!(string, StringExpression::from_value("hello"));
+        assert_eq!(string.into_string(), Some("hello".to_owned()));
+    }
+
+    #[test]
+    fn has_single_quote_is_true_in_non_utf8_string() {
+        let string = StringExpression::from_bytes(b"\xFF'".to_vec());
+
+        assert!(string.has_single_quote());
     }
 
     #[test]
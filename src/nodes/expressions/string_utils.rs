@@ -45,10 +45,10 @@ impl StringError {
 pub(crate) fn read_escaped_string(
     chars: CharIndices,
     reserve_size: Option<usize>,
-) -> Result<String, StringError> {
+) -> Result<Vec<u8>, StringError> {
     let mut chars = chars.peekable();
 
-    let mut value = String::new();
+    let mut value = Vec::new();
     if let Some(reserve_size) = reserve_size {
         value.reserve(reserve_size);
     }
@@ -57,19 +57,19 @@ pub(crate) fn read_escaped_string(
         if char == '\\' {
             if let Some((_, next_char)) = chars.next() {
                 match next_char {
-                    '\n' | '"' | '\'' | '\\' => value.push(next_char),
-                    'n' => value.push('\n'),
-                    't' => value.push('\t'),
-                    'a' => value.push('\u{7}'),
-                    'b' => value.push('\u{8}'),
-                    'v' => value.push('\u{B}'),
-                    'f' => value.push('\u{C}'),
-                    'r' => value.push('\r'),
+                    '\n' | '"' | '\'' | '\\' => push_char(&mut value, next_char),
+                    'n' => value.push(b'\n'),
+                    't' => value.push(b'\t'),
+                    'a' => value.push(7),
+                    'b' => value.push(8),
+                    'v' => value.push(0xB),
+                    'f' => value.push(0xC),
+                    'r' => value.push(b'\r'),
                     first_digit if first_digit.is_ascii_digit() => {
                         let number = read_number(&mut chars, Some(first_digit), 10, 3);
 
                         if number < 256 {
-                            value.push(number as u8 as char);
+                            value.push(number as u8);
                         } else {
                             return Err(StringError::malformed_escape_sequence(
                                 position,
@@ -86,7 +86,7 @@ pub(crate) fn read_escaped_string(
                                 + second_digit.to_digit(16).unwrap();
 
                             if number < 256 {
-                                value.push(number as u8 as char);
+                                value.push(number as u8);
                             } else {
                                 return Err(StringError::malformed_escape_sequence(
                                     position,
@@ -124,7 +124,10 @@ pub(crate) fn read_escaped_string(
                             ));
                         }
 
-                        value.push(char::from_u32(number).expect("unable to convert u32 to char"));
+                        push_char(
+                            &mut value,
+                            char::from_u32(number).expect("unable to convert u32 to char"),
+                        );
                     }
                     'z' => {
                         while chars
@@ -137,7 +140,7 @@ pub(crate) fn read_escaped_string(
                     }
                     _ => {
                         // an invalid escape does not error: it simply skips the backslash
-                        value.push(next_char);
+                        push_char(&mut value, next_char);
                     }
                 }
             } else {
@@ -147,7 +150,7 @@ pub(crate) fn read_escaped_string(
                 ));
             }
         } else {
-            value.push(char);
+            push_char(&mut value, char);
         }
     }
 
@@ -156,6 +159,12 @@ pub(crate) fn read_escaped_string(
     Ok(value)
 }
 
+#[inline]
+fn push_char(value: &mut Vec<u8>, character: char) {
+    let mut buffer = [0; 4];
+    value.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+}
+
 fn read_number(
     chars: &mut Peekable<CharIndices>,
     first_digit: Option<char>,
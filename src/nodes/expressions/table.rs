@@ -1,6 +1,6 @@
 use crate::{
     nodes::{Expression, Identifier, Token, Trivia},
-    process::utils::is_valid_identifier,
+    process::{utils::is_valid_identifier, Evaluator, LuaValue},
 };
 
 use super::StringExpression;
@@ -197,6 +197,54 @@ impl TableEntry {
     }
 }
 
+/// A constant key that can be used to look up entries of a [`TableExpression`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum LuaKey {
+    String(Vec<u8>),
+    Number(f64),
+    Boolean(bool),
+}
+
+impl LuaKey {
+    fn from_expression(expression: &Expression) -> Option<Self> {
+        if let Expression::String(string) = expression {
+            return Some(Self::String(string.as_bytes().to_vec()));
+        }
+        match Evaluator::default().evaluate(expression) {
+            LuaValue::String(string) => Some(Self::String(string.into_bytes())),
+            LuaValue::Bytes(bytes) => Some(Self::String(bytes)),
+            LuaValue::Number(number) if !number.is_nan() => Some(Self::Number(number)),
+            LuaValue::True => Some(Self::Boolean(true)),
+            LuaValue::False => Some(Self::Boolean(false)),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for LuaKey {
+    fn from(value: &str) -> Self {
+        Self::String(value.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for LuaKey {
+    fn from(value: String) -> Self {
+        Self::String(value.into_bytes())
+    }
+}
+
+impl From<f64> for LuaKey {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<bool> for LuaKey {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
 impl From<TableFieldEntry> for TableEntry {
     fn from(entry: TableFieldEntry) -> Self {
         Self::Field(entry)
@@ -311,6 +359,81 @@ impl TableExpression {
         self
     }
 
+    /// Finds the entry that defines the given key, whether it is a field entry
+    /// (`{ key = value }`), an index entry with a constant key (`{ [key] = value }`)
+    /// or an array value (`{ value }`) for number keys. When a key is defined
+    /// more than once, the last entry is returned.
+    pub fn find_entry(&self, key: &LuaKey) -> Option<&TableEntry> {
+        self.find_entry_index(key).map(|index| &self.entries[index])
+    }
+
+    /// Returns the value associated with the given string key.
+    pub fn get_value_for_string_key(&self, key: &str) -> Option<&Expression> {
+        self.find_entry(&LuaKey::from(key))
+            .and_then(|entry| match entry {
+                TableEntry::Field(entry) => Some(entry.get_value()),
+                TableEntry::Index(entry) => Some(entry.get_value()),
+                TableEntry::Value(_) => None,
+            })
+    }
+
+    /// Replaces the value of the entry that defines the given string key and returns
+    /// the previous value. If the key is not defined, a new entry is appended.
+    pub fn set_string_key(&mut self, key: &str, value: Expression) -> Option<Expression> {
+        match self
+            .find_entry_index(&LuaKey::from(key))
+            .map(|index| &mut self.entries[index])
+        {
+            Some(TableEntry::Field(entry)) => Some(std::mem::replace(entry.mutate_value(), value)),
+            Some(TableEntry::Index(entry)) => Some(std::mem::replace(entry.mutate_value(), value)),
+            Some(TableEntry::Value(_)) | None => {
+                self.entries
+                    .push(TableEntry::from_string_key_and_value(key, value));
+                None
+            }
+        }
+    }
+
+    /// Returns a lower bound of the length of the table (the `#` operator), which
+    /// is the number of leading array values that are known to be non-nil.
+    pub fn array_len_lower_bound(&self) -> usize {
+        let evaluator = Evaluator::default();
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                TableEntry::Value(value) => Some(value),
+                TableEntry::Field(_) | TableEntry::Index(_) => None,
+            })
+            .take_while(|value| {
+                !matches!(evaluator.evaluate(value), LuaValue::Nil | LuaValue::Unknown)
+            })
+            .count()
+    }
+
+    fn find_entry_index(&self, key: &LuaKey) -> Option<usize> {
+        let mut array_index = 0;
+        let mut found = None;
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let entry_key = match entry {
+                TableEntry::Field(entry) => Some(LuaKey::String(
+                    entry.get_field().get_name().as_bytes().to_vec(),
+                )),
+                TableEntry::Index(entry) => LuaKey::from_expression(entry.get_key()),
+                TableEntry::Value(_) => {
+                    array_index += 1;
+                    Some(LuaKey::Number(array_index as f64))
+                }
+            };
+
+            if entry_key.as_ref() == Some(key) {
+                found = Some(index);
+            }
+        }
+
+        found
+    }
+
     super::impl_token_fns!(iter = [tokens, entries]);
 }
 
@@ -319,3 +442,175 @@ impl Default for TableExpression {
         Self::new(Vec::new())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_table(code: &str) -> TableExpression {
+        let block = crate::Parser::default()
+            .parse(&format!("return {}", code))
+            .expect("code should parse");
+        match block.get_last_statement() {
+            Some(crate::nodes::LastStatement::Return(statement)) => {
+                match statement.iter_expressions().next() {
+                    Some(Expression::Table(table)) => table.clone(),
+                    _ => panic!("return statement should contain a table"),
+                }
+            }
+            _ => panic!("block should end with a return statement"),
+        }
+    }
+
+    #[test]
+    fn find_entry_with_field_key() {
+        let table = parse_table("{ 1, name = true, [2] = false }");
+
+        assert_eq!(
+            table.find_entry(&LuaKey::from("name")),
+            Some(&TableEntry::Field(TableFieldEntry::new("name", true)))
+        );
+    }
+
+    #[test]
+    fn find_entry_with_string_index_key() {
+        let table = parse_table("{ ['key with spaces'] = 1 }");
+
+        assert_eq!(
+            table.find_entry(&LuaKey::from("key with spaces")),
+            table.get_entries().first()
+        );
+    }
+
+    #[test]
+    fn find_entry_with_number_keys_in_mixed_table() {
+        let table = parse_table("{ 'a', name = 'b', 'c', [-1] = 'd', [2.5] = 'e' }");
+
+        assert_eq!(
+            table.find_entry(&LuaKey::from(2.0)),
+            Some(&TableEntry::Value(StringExpression::from_value("c").into()))
+        );
+        assert_eq!(
+            table.find_entry(&LuaKey::from(-1.0)),
+            table.get_entries().get(3)
+        );
+        assert_eq!(
+            table.find_entry(&LuaKey::from(2.5)),
+            table.get_entries().get(4)
+        );
+        assert_eq!(table.find_entry(&LuaKey::from(3.0)), None);
+    }
+
+    #[test]
+    fn find_entry_with_boolean_key() {
+        let table = parse_table("{ [true] = 1, [false] = 2 }");
+
+        assert_eq!(
+            table.find_entry(&LuaKey::from(false)),
+            table.get_entries().get(1)
+        );
+    }
+
+    #[test]
+    fn find_entry_returns_last_definition_of_a_key() {
+        let table = parse_table("{ key = 1, ['key'] = 2, other = 3 }");
+
+        assert_eq!(
+            table.find_entry(&LuaKey::from("key")),
+            table.get_entries().get(1)
+        );
+        assert_eq!(
+            table.get_value_for_string_key("key"),
+            Some(&Expression::from(2.0))
+        );
+    }
+
+    #[test]
+    fn find_entry_does_not_match_non_constant_keys() {
+        let table = parse_table("{ [key] = 1 }");
+
+        assert_eq!(table.find_entry(&LuaKey::from("key")), None);
+    }
+
+    #[test]
+    fn get_value_for_missing_string_key() {
+        let table = parse_table("{ 'key' }");
+
+        assert_eq!(table.get_value_for_string_key("key"), None);
+    }
+
+    #[test]
+    fn set_string_key_replaces_field_value() {
+        let mut table = parse_table("{ key = 1 }");
+
+        assert_eq!(
+            table.set_string_key("key", Expression::from(true)),
+            Some(Expression::from(1.0))
+        );
+        assert_eq!(
+            table.get_value_for_string_key("key"),
+            Some(&Expression::from(true))
+        );
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn set_string_key_replaces_last_definition() {
+        let mut table = parse_table("{ key = 1, ['key'] = 2 }");
+
+        table.set_string_key("key", Expression::from(false));
+
+        assert_eq!(
+            table.get_entries().first(),
+            Some(&TableEntry::Field(TableFieldEntry::new(
+                "key",
+                Expression::from(1.0)
+            )))
+        );
+        assert_eq!(
+            table.get_value_for_string_key("key"),
+            Some(&Expression::from(false))
+        );
+    }
+
+    #[test]
+    fn set_string_key_appends_new_entries() {
+        let mut table = TableExpression::default();
+
+        assert_eq!(table.set_string_key("name", Expression::from(true)), None);
+        assert_eq!(table.set_string_key("end", Expression::from(false)), None);
+
+        assert_eq!(
+            table,
+            TableExpression::default()
+                .append_field("name", true)
+                .append_index(StringExpression::from_value("end"), false)
+        );
+    }
+
+    #[test]
+    fn array_len_lower_bound_of_empty_table() {
+        assert_eq!(TableExpression::default().array_len_lower_bound(), 0);
+    }
+
+    #[test]
+    fn array_len_lower_bound_of_mixed_table() {
+        let table = parse_table("{ 1, key = 'a', false, {}, function() end }");
+
+        assert_eq!(table.array_len_lower_bound(), 4);
+    }
+
+    #[test]
+    fn array_len_lower_bound_stops_at_nil_value() {
+        let table = parse_table("{ 1, 2, nil, 4 }");
+
+        assert_eq!(table.array_len_lower_bound(), 2);
+    }
+
+    #[test]
+    fn array_len_lower_bound_stops_at_unknown_value() {
+        let table = parse_table("{ 'a', value, 'c', ... }");
+
+        assert_eq!(table.array_len_lower_bound(), 1);
+    }
+}
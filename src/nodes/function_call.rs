@@ -1,4 +1,29 @@
-use crate::nodes::{Arguments, Expression, Identifier, Prefix, Token};
+use std::fmt;
+
+use crate::{
+    nodes::{Arguments, Expression, FieldExpression, Identifier, Prefix, Token},
+    process::utils::is_valid_identifier,
+};
+
+/// An error returned when building a [`FunctionCall`] from a path that contains a
+/// segment which is not a valid identifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidFunctionPathError {
+    path: String,
+    segment: String,
+}
+
+impl fmt::Display for InvalidFunctionPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid function path `{}`: `{}` is not a valid identifier",
+            self.path, self.segment
+        )
+    }
+}
+
+impl std::error::Error for InvalidFunctionPathError {}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FunctionCallTokens {
@@ -45,6 +70,31 @@ impl FunctionCall {
         }
     }
 
+    /// Creates a call to a function accessed through a path of fields separated with
+    /// dots (like `string.format`). Each segment must be a valid identifier.
+    pub fn from_path(path: &str) -> Result<Self, InvalidFunctionPathError> {
+        let mut segments = path.split('.').map(|segment| {
+            if is_valid_identifier(segment) {
+                Ok(Identifier::new(segment))
+            } else {
+                Err(InvalidFunctionPathError {
+                    path: path.to_owned(),
+                    segment: segment.to_owned(),
+                })
+            }
+        });
+
+        let root = segments
+            .next()
+            .expect("split should always return at least one segment")?;
+
+        segments
+            .try_fold(Prefix::from(root), |prefix, field| {
+                field.map(|field| FieldExpression::new(prefix, field).into())
+            })
+            .map(Self::from_prefix)
+    }
+
     pub fn with_tokens(mut self, tokens: FunctionCallTokens) -> Self {
         self.tokens = Some(tokens);
         self
@@ -117,3 +167,87 @@ impl FunctionCall {
 
     super::impl_token_fns!(iter = [tokens, method]);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::nodes::TupleArguments;
+
+    #[test]
+    fn from_path_with_single_name() {
+        assert_eq!(
+            FunctionCall::from_path("print"),
+            Ok(FunctionCall::from_name("print"))
+        );
+    }
+
+    #[test]
+    fn from_path_creates_field_prefixes() {
+        let call = FunctionCall::from_path("string.format").unwrap();
+
+        assert_eq!(
+            call.get_prefix(),
+            &Prefix::from(FieldExpression::new(Prefix::from_name("string"), "format"))
+        );
+    }
+
+    #[test]
+    fn from_path_with_nested_fields() {
+        let call = FunctionCall::from_path("a.b.c").unwrap();
+
+        assert_eq!(
+            call.get_prefix(),
+            &Prefix::from(FieldExpression::new(
+                FieldExpression::new(Prefix::from_name("a"), "b"),
+                "c"
+            ))
+        );
+    }
+
+    #[test]
+    fn from_path_with_arguments_and_method() {
+        let call = FunctionCall::from_path("game.Players")
+            .unwrap()
+            .with_method("GetPlayers")
+            .with_arguments([Expression::from(true), Expression::nil()]);
+
+        assert_eq!(call.get_method(), Some(&Identifier::new("GetPlayers")));
+        assert_eq!(
+            call.get_arguments(),
+            &Arguments::from(
+                TupleArguments::default()
+                    .with_argument(true)
+                    .with_argument(Expression::nil())
+            )
+        );
+    }
+
+    #[test]
+    fn from_path_with_empty_segment_errors() {
+        assert_eq!(
+            FunctionCall::from_path("string..format")
+                .unwrap_err()
+                .to_string(),
+            "invalid function path `string..format`: `` is not a valid identifier"
+        );
+    }
+
+    #[test]
+    fn from_path_with_spaces_errors() {
+        assert_eq!(
+            FunctionCall::from_path(" not x").unwrap_err().to_string(),
+            "invalid function path ` not x`: ` not x` is not a valid identifier"
+        );
+    }
+
+    #[test]
+    fn from_path_with_keyword_errors() {
+        assert_eq!(
+            FunctionCall::from_path("table.end")
+                .unwrap_err()
+                .to_string(),
+            "invalid function path `table.end`: `end` is not a valid identifier"
+        );
+    }
+}
@@ -1,7 +1,61 @@
-use crate::nodes::Token;
+use std::fmt;
+
+use crate::{nodes::Token, process::utils::KEYWORDS, LuaDialect};
 
 use super::{Type, TypedIdentifier};
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum InvalidIdentifierErrorKind {
+    Empty,
+    StartsWithDigit,
+    InvalidCharacter { character: char, position: usize },
+    Keyword,
+}
+
+/// An error returned when a name cannot be used as a Lua identifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidIdentifierError {
+    name: String,
+    kind: InvalidIdentifierErrorKind,
+}
+
+impl fmt::Display for InvalidIdentifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            InvalidIdentifierErrorKind::Empty => write!(f, "identifier cannot be empty"),
+            InvalidIdentifierErrorKind::StartsWithDigit => write!(
+                f,
+                "invalid identifier `{}`: identifiers cannot start with a digit",
+                self.name
+            ),
+            InvalidIdentifierErrorKind::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid identifier `{}`: unexpected character `{}` at {}",
+                self.name, character, position
+            ),
+            InvalidIdentifierErrorKind::Keyword => write!(
+                f,
+                "invalid identifier `{}`: `{}` is a reserved keyword",
+                self.name, self.name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidIdentifierError {}
+
+fn is_identifier_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '_'
+}
+
+fn is_reserved_keyword(name: &str, dialect: LuaDialect) -> bool {
+    KEYWORDS.contains(&name)
+        || (matches!(dialect, LuaDialect::Lua52 | LuaDialect::Lua54) && name == "goto")
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Identifier {
     name: String,
@@ -10,10 +64,80 @@ pub struct Identifier {
 
 impl Identifier {
     pub fn new<S: Into<String>>(name: S) -> Self {
-        Self {
-            name: name.into(),
-            token: None,
+        let name = name.into();
+        debug_assert!(
+            !name.contains([' ', '.']),
+            "identifier `{}` cannot contain spaces or dots",
+            name
+        );
+        Self { name, token: None }
+    }
+
+    /// Creates an identifier after verifying that the name can be used as a Luau (or
+    /// Lua 5.1) identifier: it must only contain ASCII letters, digits or underscores,
+    /// must not start with a digit and must not be a reserved keyword.
+    pub fn try_new(name: &str) -> Result<Self, InvalidIdentifierError> {
+        Self::try_new_with_dialect(name, LuaDialect::default())
+    }
+
+    /// Same as [`Identifier::try_new`], using the reserved keywords of the given
+    /// dialect (Lua 5.2 and Lua 5.4 also reserve `goto`).
+    pub fn try_new_with_dialect(
+        name: &str,
+        dialect: LuaDialect,
+    ) -> Result<Self, InvalidIdentifierError> {
+        let error = |kind| {
+            Err(InvalidIdentifierError {
+                name: name.to_owned(),
+                kind,
+            })
+        };
+
+        if name.is_empty() {
+            return error(InvalidIdentifierErrorKind::Empty);
+        }
+        if name.starts_with(|c: char| c.is_ascii_digit()) {
+            return error(InvalidIdentifierErrorKind::StartsWithDigit);
+        }
+        if let Some((position, character)) = name
+            .char_indices()
+            .find(|(_, character)| !is_identifier_character(*character))
+        {
+            return error(InvalidIdentifierErrorKind::InvalidCharacter {
+                character,
+                position,
+            });
+        }
+        if is_reserved_keyword(name, dialect) {
+            return error(InvalidIdentifierErrorKind::Keyword);
         }
+
+        Ok(Self::new(name))
+    }
+
+    /// Creates a valid identifier from any name, by replacing characters that cannot
+    /// be used in identifiers with underscores. An underscore is prepended when the
+    /// name is empty, starts with a digit or is a reserved keyword.
+    pub fn sanitize(name: &str) -> Self {
+        let mut sanitized: String = name
+            .chars()
+            .map(|character| {
+                if is_identifier_character(character) {
+                    character
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        if sanitized.is_empty()
+            || sanitized.starts_with(|c: char| c.is_ascii_digit())
+            || is_reserved_keyword(&sanitized, LuaDialect::Lua52)
+        {
+            sanitized.insert(0, '_');
+        }
+
+        Self::new(sanitized)
     }
 
     pub fn with_type(self, r#type: impl Into<Type>) -> TypedIdentifier {
@@ -69,10 +193,7 @@ impl Identifier {
 
 impl<IntoString: Into<String>> From<IntoString> for Identifier {
     fn from(identifier: IntoString) -> Self {
-        Self {
-            name: identifier.into(),
-            token: None,
-        }
+        Self::new(identifier)
     }
 }
 
@@ -97,4 +218,117 @@ mod test {
             })
         );
     }
+
+    mod try_new {
+        use super::*;
+
+        macro_rules! test_valid {
+            ($($name:ident => $value:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(Identifier::try_new($value), Ok(Identifier::new($value)));
+                    }
+                )*
+            };
+        }
+
+        macro_rules! test_invalid {
+            ($($name:ident ( $value:literal ) => $message:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(
+                            Identifier::try_new($value).unwrap_err().to_string(),
+                            $message
+                        );
+                    }
+                )*
+            };
+        }
+
+        test_valid!(
+            single_letter => "a",
+            underscore => "_",
+            with_digits => "var2",
+            upper_case => "VAR",
+            leading_underscore_digit => "_0",
+            luau_contextual_keyword_type => "type",
+            luau_contextual_keyword_continue => "continue",
+            lua52_keyword_goto => "goto",
+        );
+
+        test_invalid!(
+            empty("") => "identifier cannot be empty",
+            starts_with_digit("1var") => "invalid identifier `1var`: identifiers cannot start with a digit",
+            only_digits("10") => "invalid identifier `10`: identifiers cannot start with a digit",
+            with_space("my error") => "invalid identifier `my error`: unexpected character ` ` at 2",
+            with_dash("foo-bar") => "invalid identifier `foo-bar`: unexpected character `-` at 3",
+            with_dot("a.b") => "invalid identifier `a.b`: unexpected character `.` at 1",
+            with_unicode("café") => "invalid identifier `café`: unexpected character `é` at 3",
+            keyword_end("end") => "invalid identifier `end`: `end` is a reserved keyword",
+            keyword_nil("nil") => "invalid identifier `nil`: `nil` is a reserved keyword",
+        );
+
+        #[test]
+        fn all_keywords_are_invalid() {
+            for keyword in KEYWORDS {
+                assert!(Identifier::try_new(keyword).is_err());
+            }
+        }
+
+        #[test]
+        fn goto_is_invalid_with_lua52_dialect() {
+            assert_eq!(
+                Identifier::try_new_with_dialect("goto", LuaDialect::Lua52)
+                    .unwrap_err()
+                    .to_string(),
+                "invalid identifier `goto`: `goto` is a reserved keyword"
+            );
+        }
+    }
+
+    mod sanitize {
+        use super::*;
+
+        macro_rules! test_sanitize {
+            ($($name:ident ( $value:literal ) => $expected:literal),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let identifier = Identifier::sanitize($value);
+
+                        assert_eq!(identifier.get_name(), $expected);
+                        assert!(Identifier::try_new(identifier.get_name()).is_ok());
+                    }
+                )*
+            };
+        }
+
+        test_sanitize!(
+            valid_name("name") => "name",
+            empty("") => "_",
+            with_dash("foo-bar") => "foo_bar",
+            with_space("my error") => "my_error",
+            with_dot("string.format") => "string_format",
+            starts_with_digit("1var") => "_1var",
+            keyword("end") => "_end",
+            lua52_keyword("goto") => "_goto",
+            unicode("café") => "caf_",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "identifier `string.format` cannot contain spaces or dots")]
+    #[cfg(debug_assertions)]
+    fn new_rejects_names_with_dots() {
+        Identifier::new("string.format");
+    }
+
+    #[test]
+    #[should_panic(expected = "identifier ` not x` cannot contain spaces or dots")]
+    #[cfg(debug_assertions)]
+    fn new_rejects_names_with_spaces() {
+        Identifier::new(" not x");
+    }
 }
@@ -144,3 +144,53 @@ macro_rules! impl_token_fns {
 }
 
 pub(crate) use impl_token_fns;
+
+macro_rules! impl_variant_accessors {
+    (
+        $enum_name:ident {
+            $( $variant:ident($ty:ty) => $as_ref:ident, $as_mut:ident, $example:expr; )*
+        }
+    ) => {
+        $(
+            #[doc = concat!(
+                "Returns a reference to the inner [`", stringify!($ty), "`] if the ",
+                "value is a `", stringify!($enum_name), "::", stringify!($variant), "`.\n\n",
+                "```\n",
+                "# use darklua_core::nodes::*;\n",
+                "let value = ", stringify!($enum_name), "::from(", stringify!($example), ");\n",
+                "\n",
+                "assert!(value.", stringify!($as_ref), "().is_some());\n",
+                "```",
+            )]
+            #[inline]
+            pub fn $as_ref(&self) -> Option<&$ty> {
+                match self {
+                    Self::$variant(value) => Some(::std::borrow::Borrow::<$ty>::borrow(value)),
+                    _ => None,
+                }
+            }
+
+            #[doc = concat!(
+                "Returns a mutable reference to the inner [`", stringify!($ty), "`] if the ",
+                "value is a `", stringify!($enum_name), "::", stringify!($variant), "`.\n\n",
+                "```\n",
+                "# use darklua_core::nodes::*;\n",
+                "let mut value = ", stringify!($enum_name), "::from(", stringify!($example), ");\n",
+                "\n",
+                "assert!(value.", stringify!($as_mut), "().is_some());\n",
+                "```",
+            )]
+            #[inline]
+            pub fn $as_mut(&mut self) -> Option<&mut $ty> {
+                match self {
+                    Self::$variant(value) => {
+                        Some(::std::borrow::BorrowMut::<$ty>::borrow_mut(value))
+                    }
+                    _ => None,
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_variant_accessors;
@@ -236,6 +236,14 @@ impl FunctionStatement {
         self.return_type.as_mut()
     }
 
+    /// Removes the return type annotation of the function and returns it.
+    pub fn remove_return_type(&mut self) -> Option<FunctionReturnType> {
+        if let Some(tokens) = &mut self.tokens {
+            tokens.return_type_colon.take();
+        }
+        self.return_type.take()
+    }
+
     pub fn with_generic_parameters(mut self, generic_parameters: GenericParameters) -> Self {
         self.generic_parameters = Some(generic_parameters);
         self
@@ -251,6 +259,11 @@ impl FunctionStatement {
         self.generic_parameters.as_ref()
     }
 
+    #[inline]
+    pub fn mutate_generic_parameters(&mut self) -> Option<&mut GenericParameters> {
+        self.generic_parameters.as_mut()
+    }
+
     #[inline]
     pub fn get_block(&self) -> &Block {
         &self.block
@@ -322,6 +335,7 @@ impl FunctionStatement {
         }
         if let Some(tokens) = &mut self.tokens {
             tokens.variable_arguments_colon.take();
+            tokens.return_type_colon.take();
         }
     }
 
@@ -330,3 +344,81 @@ impl FunctionStatement {
         iter = [parameters, generic_parameters, tokens]
     );
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        generator::{LuaGenerator, TokenBasedLuaGenerator},
+        nodes::{Statement, TypeName},
+        Parser,
+    };
+
+    fn parse_function(code: &str) -> FunctionStatement {
+        let mut block = Parser::default().preserve_tokens().parse(code).unwrap();
+        match block.take_statements().pop() {
+            Some(Statement::Function(function)) => function,
+            _ => panic!("function statement expected"),
+        }
+    }
+
+    fn generate(function: FunctionStatement, code: &str) -> String {
+        let mut generator = TokenBasedLuaGenerator::new(code);
+        generator.write_block(&Block::from(function));
+        generator.into_string()
+    }
+
+    #[test]
+    fn mutate_return_type_is_generated() {
+        let code = "function obj:f(a: number): string return a end";
+        let mut function = parse_function(code);
+
+        *function.mutate_return_type().unwrap() = TypeName::new("boolean").into();
+
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "function obj:f(a: number): boolean return a end"
+        );
+    }
+
+    #[test]
+    fn remove_return_type() {
+        let code = "function f(a: number): string return a end";
+        let mut function = parse_function(code);
+
+        assert!(function.remove_return_type().is_some());
+        assert!(!function.has_return_type());
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "function f(a: number)return a end"
+        );
+    }
+
+    #[test]
+    fn mutate_generic_parameters_is_generated() {
+        let code = "function f<T>(a: T): T return a end";
+        let mut function = parse_function(code);
+
+        let generic_parameters = function.mutate_generic_parameters().unwrap();
+        *generic_parameters = GenericParameters::from_type_variable("U");
+
+        for parameter in function.iter_mut_parameters() {
+            *parameter.mutate_type().unwrap() = TypeName::new("U").into();
+        }
+
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "function f<U>(a: U): T return a end"
+        );
+    }
+
+    #[test]
+    fn clear_types_removes_all_annotations() {
+        let code = "function f<T>(a: T, ...: string): T return a end";
+        let mut function = parse_function(code);
+
+        function.clear_types();
+
+        pretty_assertions::assert_eq!(generate(function, code), "function f(a, ...)return a end");
+    }
+}
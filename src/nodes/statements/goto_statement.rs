@@ -0,0 +1,61 @@
+use crate::nodes::{Identifier, Token};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GotoTokens {
+    pub goto: Token,
+}
+
+impl GotoTokens {
+    super::impl_token_fns!(target = [goto]);
+}
+
+/// Represents a `goto` statement (available from Lua 5.2).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GotoStatement {
+    label: Identifier,
+    tokens: Option<GotoTokens>,
+}
+
+impl GotoStatement {
+    pub fn new(label: impl Into<Identifier>) -> Self {
+        Self {
+            label: label.into(),
+            tokens: None,
+        }
+    }
+
+    pub fn with_tokens(mut self, tokens: GotoTokens) -> Self {
+        self.tokens = Some(tokens);
+        self
+    }
+
+    #[inline]
+    pub fn get_label(&self) -> &Identifier {
+        &self.label
+    }
+
+    #[inline]
+    pub fn mutate_label(&mut self) -> &mut Identifier {
+        &mut self.label
+    }
+
+    #[inline]
+    pub fn set_tokens(&mut self, tokens: GotoTokens) {
+        self.tokens = Some(tokens);
+    }
+
+    #[inline]
+    pub fn get_tokens(&self) -> Option<&GotoTokens> {
+        self.tokens.as_ref()
+    }
+
+    #[inline]
+    pub fn mutate_tokens(&mut self) -> Option<&mut GotoTokens> {
+        self.tokens.as_mut()
+    }
+
+    super::impl_token_fns!(
+        target = [label]
+        iter = [tokens]
+    );
+}
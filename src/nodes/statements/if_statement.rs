@@ -1,6 +1,6 @@
 use std::mem;
 
-use crate::nodes::{Block, Expression, Token};
+use crate::nodes::{Block, Expression, Statement, Token};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IfBranchTokens {
@@ -36,6 +36,18 @@ impl IfBranch {
         }
     }
 
+    /// Appends the given statements to the block of the branch.
+    pub fn with_statements<I, S>(mut self, statements: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Statement>,
+    {
+        for statement in statements {
+            self.block.push_statement(statement);
+        }
+        self
+    }
+
     pub fn with_tokens(mut self, tokens: IfBranchTokens) -> Self {
         self.tokens = Some(tokens);
         self
@@ -152,11 +164,31 @@ impl IfStatement {
         self
     }
 
+    /// Adds an `elseif` branch, like [`with_new_branch`](Self::with_new_branch). This
+    /// makes chains read like the Lua code they create:
+    /// ```
+    /// # use darklua_core::nodes::{Block, Expression, IfStatement, LastStatement};
+    /// let statement = IfStatement::create(Expression::identifier("a"), Block::default())
+    ///     .elseif(Expression::identifier("b"), LastStatement::new_break())
+    ///     .with_else(Block::default());
+    ///
+    /// assert_eq!(statement.branch_count(), 2);
+    /// assert!(statement.get_else_block().is_some());
+    /// ```
+    pub fn elseif(self, condition: impl Into<Expression>, block: impl Into<Block>) -> Self {
+        self.with_new_branch(condition, block)
+    }
+
     pub fn with_else_block<B: Into<Block>>(mut self, block: B) -> Self {
         self.else_block.replace(block.into());
         self
     }
 
+    /// Sets the `else` block, like [`with_else_block`](Self::with_else_block).
+    pub fn with_else(self, block: impl Into<Block>) -> Self {
+        self.with_else_block(block)
+    }
+
     pub fn mutate_all_blocks(&mut self) -> Vec<&mut Block> {
         let mut blocks: Vec<&mut Block> = self
             .branches
@@ -235,3 +267,99 @@ impl IfStatement {
 
     super::impl_token_fns!(iter = [tokens, branches]);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::nodes::{LastStatement, LocalAssignStatement};
+
+    #[test]
+    fn create_single_branch() {
+        let statement = IfStatement::create(true, LastStatement::new_break());
+
+        pretty_assertions::assert_eq!(
+            statement,
+            IfStatement::new(vec![IfBranch::new(true, LastStatement::new_break())], None)
+        );
+    }
+
+    #[test]
+    fn create_single_branch_with_else() {
+        let statement =
+            IfStatement::create(true, Block::default()).with_else(LastStatement::new_break());
+
+        pretty_assertions::assert_eq!(
+            statement,
+            IfStatement::new(
+                vec![IfBranch::empty(true)],
+                Some(LastStatement::new_break().into())
+            )
+        );
+    }
+
+    #[test]
+    fn create_two_branches() {
+        let statement = IfStatement::create(Expression::identifier("a"), Block::default())
+            .elseif(Expression::identifier("b"), LastStatement::new_break());
+
+        pretty_assertions::assert_eq!(
+            statement,
+            IfStatement::new(
+                vec![
+                    IfBranch::empty(Expression::identifier("a")),
+                    IfBranch::new(Expression::identifier("b"), LastStatement::new_break()),
+                ],
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn create_three_branches_with_else() {
+        let statement = IfStatement::create(Expression::identifier("a"), Block::default())
+            .elseif(Expression::identifier("b"), Block::default())
+            .elseif(Expression::identifier("c"), Block::default())
+            .with_else(LastStatement::new_continue());
+
+        pretty_assertions::assert_eq!(
+            statement,
+            IfStatement::new(
+                vec![
+                    IfBranch::empty(Expression::identifier("a")),
+                    IfBranch::empty(Expression::identifier("b")),
+                    IfBranch::empty(Expression::identifier("c")),
+                ],
+                Some(LastStatement::new_continue().into())
+            )
+        );
+    }
+
+    #[test]
+    fn branch_with_statements() {
+        let branch = IfBranch::empty(true).with_statements([
+            LocalAssignStatement::from_variable("a"),
+            LocalAssignStatement::from_variable("b"),
+        ]);
+
+        pretty_assertions::assert_eq!(
+            branch,
+            IfBranch::new(
+                true,
+                Block::default()
+                    .with_statement(LocalAssignStatement::from_variable("a"))
+                    .with_statement(LocalAssignStatement::from_variable("b"))
+            )
+        );
+    }
+
+    #[test]
+    fn branch_with_statements_keeps_existing_statements() {
+        let branch = IfBranch::new(true, LocalAssignStatement::from_variable("a"))
+            .with_statements(Vec::<Statement>::new());
+
+        pretty_assertions::assert_eq!(
+            branch,
+            IfBranch::new(true, LocalAssignStatement::from_variable("a"))
+        );
+    }
+}
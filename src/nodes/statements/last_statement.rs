@@ -113,6 +113,46 @@ impl LastStatement {
     pub fn new_continue() -> Self {
         Self::Continue(None)
     }
+
+    /// Returns the first token of the statement, if the statement has tokens.
+    pub fn get_first_token(&self) -> Option<&Token> {
+        match self {
+            Self::Break(token) | Self::Continue(token) => token.as_ref(),
+            Self::Return(statement) => statement.get_tokens().map(|tokens| &tokens.r#return),
+        }
+    }
+
+    /// Returns a mutable reference to the first token of the statement. When the
+    /// statement does not have tokens, tokens are created from their content.
+    pub fn mutate_first_token(&mut self) -> &mut Token {
+        match self {
+            Self::Break(token) => token.get_or_insert_with(|| Token::from_content("break")),
+            Self::Continue(token) => token.get_or_insert_with(|| Token::from_content("continue")),
+            Self::Return(statement) => {
+                if statement.get_tokens().is_none() {
+                    statement.set_tokens(ReturnTokens {
+                        r#return: Token::from_content("return"),
+                        commas: Vec::new(),
+                    });
+                }
+                &mut statement
+                    .mutate_tokens()
+                    .expect("return tokens should be set")
+                    .r#return
+            }
+        }
+    }
+
+    /// Places the statement on the given line when generating code that retains
+    /// lines.
+    pub fn set_line(&mut self, line_number: usize) {
+        self.mutate_first_token().set_line_number(line_number);
+    }
+
+    pub fn with_line(mut self, line_number: usize) -> Self {
+        self.set_line(line_number);
+        self
+    }
 }
 
 impl From<ReturnStatement> for LastStatement {
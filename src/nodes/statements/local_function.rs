@@ -151,6 +151,14 @@ impl LocalFunctionStatement {
         self.return_type.as_mut()
     }
 
+    /// Removes the return type annotation of the function and returns it.
+    pub fn remove_return_type(&mut self) -> Option<FunctionReturnType> {
+        if let Some(tokens) = &mut self.tokens {
+            tokens.return_type_colon.take();
+        }
+        self.return_type.take()
+    }
+
     pub fn with_generic_parameters(mut self, generic_parameters: GenericParameters) -> Self {
         self.generic_parameters = Some(generic_parameters);
         self
@@ -166,6 +174,11 @@ impl LocalFunctionStatement {
         self.generic_parameters.as_ref()
     }
 
+    #[inline]
+    pub fn mutate_generic_parameters(&mut self) -> Option<&mut GenericParameters> {
+        self.generic_parameters.as_mut()
+    }
+
     #[inline]
     pub fn mutate_parameters(&mut self) -> &mut Vec<TypedIdentifier> {
         &mut self.parameters
@@ -242,6 +255,7 @@ impl LocalFunctionStatement {
         }
         if let Some(tokens) = &mut self.tokens {
             tokens.variable_arguments_colon.take();
+            tokens.return_type_colon.take();
         }
     }
 
@@ -254,6 +268,79 @@ impl LocalFunctionStatement {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{
+        generator::{LuaGenerator, TokenBasedLuaGenerator},
+        nodes::{Statement, TypeName},
+        Parser,
+    };
+
+    fn parse_function(code: &str) -> LocalFunctionStatement {
+        let mut block = Parser::default().preserve_tokens().parse(code).unwrap();
+        match block.take_statements().pop() {
+            Some(Statement::LocalFunction(function)) => function,
+            _ => panic!("local function statement expected"),
+        }
+    }
+
+    fn generate(function: LocalFunctionStatement, code: &str) -> String {
+        let mut generator = TokenBasedLuaGenerator::new(code);
+        generator.write_block(&Block::from(function));
+        generator.into_string()
+    }
+
+    #[test]
+    fn mutate_return_type_is_generated() {
+        let code = "local function f(a: number): string return a end";
+        let mut function = parse_function(code);
+
+        *function.mutate_return_type().unwrap() = TypeName::new("boolean").into();
+
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "local function f(a: number): boolean return a end"
+        );
+    }
+
+    #[test]
+    fn set_return_type_on_function_without_return_type() {
+        let code = "local function f(a) return a end";
+        let mut function = parse_function(code);
+
+        function.set_return_type(TypeName::new("number"));
+
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "local function f(a) :number return a end"
+        );
+    }
+
+    #[test]
+    fn remove_return_type() {
+        let code = "local function f(a: number): string return a end";
+        let mut function = parse_function(code);
+
+        assert!(function.remove_return_type().is_some());
+        assert!(!function.has_return_type());
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "local function f(a: number)return a end"
+        );
+    }
+
+    #[test]
+    fn clear_types_removes_all_annotations() {
+        let code = "local function f<T>(a: T, ...: string): T return a end";
+        let mut function = parse_function(code);
+
+        function.clear_types();
+
+        assert!(function.get_generic_parameters().is_none());
+        assert!(function.mutate_generic_parameters().is_none());
+        pretty_assertions::assert_eq!(
+            generate(function, code),
+            "local function f(a, ...)return a end"
+        );
+    }
 
     #[test]
     fn has_parameter_is_true_when_single_param_matches() {
@@ -3,7 +3,9 @@ mod compound_assign;
 mod do_statement;
 mod function;
 mod generic_for;
+mod goto_statement;
 mod if_statement;
+mod label_statement;
 mod last_statement;
 mod local_assign;
 mod local_function;
@@ -17,7 +19,9 @@ pub use compound_assign::*;
 pub use do_statement::*;
 pub use function::*;
 pub use generic_for::*;
+pub use goto_statement::*;
 pub use if_statement::*;
+pub use label_statement::*;
 pub use last_statement::*;
 pub use local_assign::*;
 pub use local_function::*;
@@ -26,9 +30,12 @@ pub use repeat_statement::*;
 pub use type_declaration::*;
 pub use while_statement::*;
 
-use crate::nodes::FunctionCall;
+use crate::nodes::{
+    FunctionBodyTokens, FunctionCall, Identifier, ParentheseExpression, ParentheseTokens, Prefix,
+    Token, Variable,
+};
 
-use super::impl_token_fns;
+use super::{impl_token_fns, impl_variant_accessors};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Statement {
@@ -38,7 +45,9 @@ pub enum Statement {
     CompoundAssign(CompoundAssignStatement),
     Function(FunctionStatement),
     GenericFor(GenericForStatement),
+    Goto(GotoStatement),
     If(IfStatement),
+    Label(LabelStatement),
     LocalAssign(LocalAssignStatement),
     LocalFunction(LocalFunctionStatement),
     NumericFor(Box<NumericForStatement>),
@@ -47,6 +56,305 @@ pub enum Statement {
     TypeDeclaration(TypeDeclarationStatement),
 }
 
+impl Statement {
+    impl_variant_accessors!(Statement {
+        Assign(AssignStatement) => as_assign, as_assign_mut,
+            AssignStatement::from_variable(Variable::new("name"), true);
+        Do(DoStatement) => as_do, as_do_mut, DoStatement::default();
+        Call(FunctionCall) => as_call, as_call_mut, FunctionCall::from_name("print");
+        CompoundAssign(CompoundAssignStatement) => as_compound_assign, as_compound_assign_mut,
+            CompoundAssignStatement::new(CompoundOperator::Plus, Variable::new("name"), 1.0);
+        Function(FunctionStatement) => as_function, as_function_mut,
+            FunctionStatement::from_name("name", Block::default());
+        GenericFor(GenericForStatement) => as_generic_for, as_generic_for_mut,
+            GenericForStatement::new(
+                vec!["key".into()],
+                vec![Expression::identifier("list")],
+                Block::default(),
+            );
+        Goto(GotoStatement) => as_goto, as_goto_mut, GotoStatement::new("label");
+        If(IfStatement) => as_if, as_if_mut, IfStatement::create(true, Block::default());
+        Label(LabelStatement) => as_label, as_label_mut, LabelStatement::new("label");
+        LocalAssign(LocalAssignStatement) => as_local_assign, as_local_assign_mut,
+            LocalAssignStatement::from_variable("name");
+        LocalFunction(LocalFunctionStatement) => as_local_function, as_local_function_mut,
+            LocalFunctionStatement::from_name("name", Block::default());
+        NumericFor(NumericForStatement) => as_numeric_for, as_numeric_for_mut,
+            NumericForStatement::new("i", 1.0, 10.0, None, Block::default());
+        Repeat(RepeatStatement) => as_repeat, as_repeat_mut,
+            RepeatStatement::new(Block::default(), true);
+        While(WhileStatement) => as_while, as_while_mut,
+            WhileStatement::new(Block::default(), true);
+        TypeDeclaration(TypeDeclarationStatement) => as_type_declaration,
+            as_type_declaration_mut, TypeDeclarationStatement::new("Name", TypeName::new("string"));
+    });
+
+    /// Returns the first token of the statement, if the statement has tokens.
+    pub fn get_first_token(&self) -> Option<&Token> {
+        match self {
+            Self::Assign(assign) => assign
+                .get_variables()
+                .first()
+                .and_then(get_variable_first_token),
+            Self::Do(do_statement) => do_statement.get_tokens().map(|tokens| &tokens.r#do),
+            Self::Call(call) => get_prefix_first_token(call.get_prefix()),
+            Self::CompoundAssign(assign) => get_variable_first_token(assign.get_variable()),
+            Self::Function(function) => function.get_tokens().map(|tokens| &tokens.function),
+            Self::GenericFor(generic_for) => generic_for.get_tokens().map(|tokens| &tokens.r#for),
+            Self::Goto(goto) => goto.get_tokens().map(|tokens| &tokens.goto),
+            Self::If(if_statement) => if_statement.get_tokens().map(|tokens| &tokens.r#if),
+            Self::Label(label) => label.get_tokens().map(|tokens| &tokens.left_colons),
+            Self::LocalAssign(assign) => assign.get_tokens().map(|tokens| &tokens.local),
+            Self::LocalFunction(function) => function.get_tokens().map(|tokens| &tokens.local),
+            Self::NumericFor(numeric_for) => numeric_for.get_tokens().map(|tokens| &tokens.r#for),
+            Self::Repeat(repeat) => repeat.get_tokens().map(|tokens| &tokens.repeat),
+            Self::While(while_statement) => {
+                while_statement.get_tokens().map(|tokens| &tokens.r#while)
+            }
+            Self::TypeDeclaration(declaration) => declaration
+                .get_tokens()
+                .map(|tokens| tokens.export.as_ref().unwrap_or(&tokens.r#type)),
+        }
+    }
+
+    /// Returns a mutable reference to the first token of the statement. When the
+    /// statement does not have tokens, tokens are created from their content. An
+    /// assignment without any variable does not have a first token.
+    pub fn mutate_first_token(&mut self) -> Option<&mut Token> {
+        let token = match self {
+            Self::Assign(assign) => variable_first_token(assign.iter_mut_variables().next()?),
+            Self::Do(do_statement) => {
+                if do_statement.get_tokens().is_none() {
+                    do_statement.set_tokens(DoTokens {
+                        r#do: Token::from_content("do"),
+                        end: Token::from_content("end"),
+                    });
+                }
+                &mut do_statement.mutate_tokens()?.r#do
+            }
+            Self::Call(call) => prefix_first_token(call.mutate_prefix()),
+            Self::CompoundAssign(assign) => variable_first_token(assign.mutate_variable()),
+            Self::Function(function) => {
+                if function.get_tokens().is_none() {
+                    function.set_tokens(FunctionBodyTokens {
+                        function: Token::from_content("function"),
+                        opening_parenthese: Token::from_content("("),
+                        closing_parenthese: Token::from_content(")"),
+                        end: Token::from_content("end"),
+                        parameter_commas: Vec::new(),
+                        variable_arguments: None,
+                        variable_arguments_colon: None,
+                        return_type_colon: None,
+                    });
+                }
+                &mut function.mutate_tokens()?.function
+            }
+            Self::GenericFor(generic_for) => {
+                if generic_for.get_tokens().is_none() {
+                    generic_for.set_tokens(GenericForTokens {
+                        r#for: Token::from_content("for"),
+                        r#in: Token::from_content("in"),
+                        r#do: Token::from_content("do"),
+                        end: Token::from_content("end"),
+                        identifier_commas: Vec::new(),
+                        value_commas: Vec::new(),
+                    });
+                }
+                &mut generic_for.mutate_tokens()?.r#for
+            }
+            Self::Goto(goto) => {
+                if goto.get_tokens().is_none() {
+                    goto.set_tokens(GotoTokens {
+                        goto: Token::from_content("goto"),
+                    });
+                }
+                &mut goto.mutate_tokens()?.goto
+            }
+            Self::If(if_statement) => {
+                if if_statement.get_tokens().is_none() {
+                    if_statement.set_tokens(IfStatementTokens {
+                        r#if: Token::from_content("if"),
+                        then: Token::from_content("then"),
+                        end: Token::from_content("end"),
+                        r#else: None,
+                    });
+                }
+                &mut if_statement.mutate_tokens()?.r#if
+            }
+            Self::Label(label) => {
+                if label.get_tokens().is_none() {
+                    label.set_tokens(LabelTokens {
+                        left_colons: Token::from_content("::"),
+                        right_colons: Token::from_content("::"),
+                    });
+                }
+                &mut label.mutate_tokens()?.left_colons
+            }
+            Self::LocalAssign(local_assign) => {
+                if local_assign.get_tokens().is_none() {
+                    local_assign.set_tokens(LocalAssignTokens {
+                        local: Token::from_content("local"),
+                        equal: None,
+                        variable_commas: Vec::new(),
+                        value_commas: Vec::new(),
+                    });
+                }
+                &mut local_assign.mutate_tokens()?.local
+            }
+            Self::LocalFunction(local_function) => {
+                if local_function.get_tokens().is_none() {
+                    local_function.set_tokens(LocalFunctionTokens {
+                        local: Token::from_content("local"),
+                        function_body: FunctionBodyTokens {
+                            function: Token::from_content("function"),
+                            opening_parenthese: Token::from_content("("),
+                            closing_parenthese: Token::from_content(")"),
+                            end: Token::from_content("end"),
+                            parameter_commas: Vec::new(),
+                            variable_arguments: None,
+                            variable_arguments_colon: None,
+                            return_type_colon: None,
+                        },
+                    });
+                }
+                &mut local_function.mutate_tokens()?.local
+            }
+            Self::NumericFor(numeric_for) => {
+                if numeric_for.get_tokens().is_none() {
+                    numeric_for.set_tokens(NumericForTokens {
+                        r#for: Token::from_content("for"),
+                        equal: Token::from_content("="),
+                        r#do: Token::from_content("do"),
+                        end: Token::from_content("end"),
+                        end_comma: Token::from_content(","),
+                        step_comma: None,
+                    });
+                }
+                &mut numeric_for.mutate_tokens()?.r#for
+            }
+            Self::Repeat(repeat) => {
+                if repeat.get_tokens().is_none() {
+                    repeat.set_tokens(RepeatTokens {
+                        repeat: Token::from_content("repeat"),
+                        until: Token::from_content("until"),
+                    });
+                }
+                &mut repeat.mutate_tokens()?.repeat
+            }
+            Self::While(while_statement) => {
+                if while_statement.get_tokens().is_none() {
+                    while_statement.set_tokens(WhileTokens {
+                        r#while: Token::from_content("while"),
+                        r#do: Token::from_content("do"),
+                        end: Token::from_content("end"),
+                    });
+                }
+                &mut while_statement.mutate_tokens()?.r#while
+            }
+            Self::TypeDeclaration(type_declaration) => {
+                let is_exported = type_declaration.is_exported();
+                if type_declaration.get_tokens().is_none() {
+                    type_declaration.set_tokens(TypeDeclarationTokens {
+                        r#type: Token::from_content("type"),
+                        equal: Token::from_content("="),
+                        export: None,
+                    });
+                }
+                let tokens = type_declaration.mutate_tokens()?;
+                if is_exported {
+                    tokens
+                        .export
+                        .get_or_insert_with(|| Token::from_content("export"))
+                } else {
+                    &mut tokens.r#type
+                }
+            }
+        };
+        Some(token)
+    }
+
+    /// Places the statement on the given line when generating code that retains
+    /// lines, unless the generated code already goes past that line. This is useful
+    /// for rules that create statements from the construct they replace or precede.
+    pub fn set_line(&mut self, line_number: usize) {
+        if let Some(token) = self.mutate_first_token() {
+            token.set_line_number(line_number);
+        }
+    }
+
+    pub fn with_line(mut self, line_number: usize) -> Self {
+        self.set_line(line_number);
+        self
+    }
+}
+
+fn get_variable_first_token(variable: &Variable) -> Option<&Token> {
+    match variable {
+        Variable::Identifier(identifier) => identifier.get_token(),
+        Variable::Field(field) => get_prefix_first_token(field.get_prefix()),
+        Variable::Index(index) => get_prefix_first_token(index.get_prefix()),
+    }
+}
+
+fn get_prefix_first_token(mut prefix: &Prefix) -> Option<&Token> {
+    loop {
+        match prefix {
+            Prefix::Call(call) => prefix = call.get_prefix(),
+            Prefix::Field(field) => prefix = field.get_prefix(),
+            Prefix::Index(index) => prefix = index.get_prefix(),
+            Prefix::Identifier(identifier) => break identifier.get_token(),
+            Prefix::Parenthese(parenthese) => {
+                break parenthese
+                    .get_tokens()
+                    .map(|tokens| &tokens.left_parenthese)
+            }
+        }
+    }
+}
+
+fn variable_first_token(variable: &mut Variable) -> &mut Token {
+    match variable {
+        Variable::Identifier(identifier) => identifier_first_token(identifier),
+        Variable::Field(field) => prefix_first_token(field.mutate_prefix()),
+        Variable::Index(index) => prefix_first_token(index.mutate_prefix()),
+    }
+}
+
+fn prefix_first_token(mut prefix: &mut Prefix) -> &mut Token {
+    loop {
+        match prefix {
+            Prefix::Call(call) => prefix = call.mutate_prefix(),
+            Prefix::Field(field) => prefix = field.mutate_prefix(),
+            Prefix::Index(index) => prefix = index.mutate_prefix(),
+            Prefix::Identifier(identifier) => break identifier_first_token(identifier),
+            Prefix::Parenthese(parenthese) => break parenthese_first_token(parenthese),
+        }
+    }
+}
+
+fn identifier_first_token(identifier: &mut Identifier) -> &mut Token {
+    if identifier.get_token().is_none() {
+        let name = identifier.get_name().to_owned();
+        identifier.set_token(Token::from_content(name));
+    }
+    identifier
+        .mutate_token()
+        .expect("identifier token should be set")
+}
+
+fn parenthese_first_token(parenthese: &mut ParentheseExpression) -> &mut Token {
+    if parenthese.get_tokens().is_none() {
+        parenthese.set_tokens(ParentheseTokens {
+            left_parenthese: Token::from_content("("),
+            right_parenthese: Token::from_content(")"),
+        });
+    }
+    &mut parenthese
+        .mutate_tokens()
+        .expect("parenthese tokens should be set")
+        .left_parenthese
+}
+
 impl From<AssignStatement> for Statement {
     fn from(assign: AssignStatement) -> Statement {
         Statement::Assign(assign)
@@ -83,12 +391,24 @@ impl From<GenericForStatement> for Statement {
     }
 }
 
+impl From<GotoStatement> for Statement {
+    fn from(goto: GotoStatement) -> Statement {
+        Statement::Goto(goto)
+    }
+}
+
 impl From<IfStatement> for Statement {
     fn from(if_statement: IfStatement) -> Statement {
         Statement::If(if_statement)
     }
 }
 
+impl From<LabelStatement> for Statement {
+    fn from(label: LabelStatement) -> Statement {
+        Statement::Label(label)
+    }
+}
+
 impl From<LocalAssignStatement> for Statement {
     fn from(assign: LocalAssignStatement) -> Statement {
         Statement::LocalAssign(assign)
@@ -124,3 +444,61 @@ impl From<TypeDeclarationStatement> for Statement {
         Statement::TypeDeclaration(type_declaration)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn with_line_creates_tokens_of_statement() {
+        let statement = Statement::from(DoStatement::default()).with_line(4);
+
+        let Statement::Do(do_statement) = statement else {
+            panic!("do statement expected");
+        };
+        let tokens = do_statement.get_tokens().expect("tokens should be created");
+        assert_eq!(tokens.r#do.get_line_number(), Some(4));
+        assert_eq!(tokens.end.get_line_number(), None);
+    }
+
+    #[test]
+    fn with_line_sets_first_identifier_of_call() {
+        let statement = Statement::from(FunctionCall::from_name("print")).with_line(2);
+
+        let Statement::Call(call) = statement else {
+            panic!("call statement expected");
+        };
+        let Prefix::Identifier(identifier) = call.get_prefix() else {
+            panic!("identifier prefix expected");
+        };
+        assert_eq!(
+            identifier.get_token().and_then(Token::get_line_number),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn with_line_does_not_apply_to_assign_without_variables() {
+        let statement = Statement::from(AssignStatement::new(Vec::new(), Vec::new())).with_line(1);
+
+        assert_eq!(
+            statement,
+            AssignStatement::new(Vec::new(), Vec::new()).into()
+        );
+    }
+
+    #[test]
+    fn last_statement_with_line_creates_return_tokens() {
+        let statement = LastStatement::from(ReturnStatement::default()).with_line(3);
+
+        let LastStatement::Return(statement) = statement else {
+            panic!("return statement expected");
+        };
+        assert_eq!(
+            statement
+                .get_tokens()
+                .and_then(|tokens| tokens.r#return.get_line_number()),
+            Some(3)
+        );
+    }
+}
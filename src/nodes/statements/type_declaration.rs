@@ -59,6 +59,11 @@ impl TypeDeclarationStatement {
         self.generic_parameters.as_mut()
     }
 
+    #[inline]
+    pub fn remove_generic_parameters(&mut self) -> Option<GenericParametersWithDefaults> {
+        self.generic_parameters.take()
+    }
+
     pub fn export(mut self) -> Self {
         self.exported = true;
         self
@@ -257,3 +262,90 @@ impl TypeDeclarationStatement {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        generator::{LuaGenerator, TokenBasedLuaGenerator},
+        nodes::{Block, Statement, TypeName, TypeVariableWithDefault},
+        Parser,
+    };
+
+    fn parse_type_declaration(code: &str) -> TypeDeclarationStatement {
+        let mut block = Parser::default().preserve_tokens().parse(code).unwrap();
+        match block.take_statements().pop() {
+            Some(Statement::TypeDeclaration(declaration)) => declaration,
+            _ => panic!("type declaration statement expected"),
+        }
+    }
+
+    fn generate(declaration: TypeDeclarationStatement, code: &str) -> String {
+        let mut generator = TokenBasedLuaGenerator::new(code);
+        generator.write_block(&Block::from(declaration));
+        generator.into_string()
+    }
+
+    #[test]
+    fn remove_exported_is_generated() {
+        let code = "export type A = string";
+        let mut declaration = parse_type_declaration(code);
+
+        declaration.remove_exported();
+
+        assert!(!declaration.is_exported());
+        pretty_assertions::assert_eq!(generate(declaration, code), "type A = string");
+    }
+
+    #[test]
+    fn set_exported_is_generated() {
+        let code = "type A = string";
+        let mut declaration = parse_type_declaration(code);
+
+        declaration.set_exported();
+
+        assert!(declaration.is_exported());
+        pretty_assertions::assert_eq!(generate(declaration, code), "export type A = string");
+    }
+
+    #[test]
+    fn rename_exported_type_is_generated() {
+        let code = "export type A<T> = { T }";
+        let mut declaration = parse_type_declaration(code);
+
+        declaration.mutate_name().set_name("List");
+
+        pretty_assertions::assert_eq!(generate(declaration, code), "export type List<T> = { T }");
+    }
+
+    #[test]
+    fn mutate_generic_parameters_is_generated() {
+        let code = "export type A<T> = { T }";
+        let mut declaration = parse_type_declaration(code);
+
+        *declaration.mutate_generic_parameters().unwrap() =
+            GenericParametersWithDefaults::from_type_variable("T")
+                .with_type_variable_with_default(TypeVariableWithDefault::new(
+                    "U",
+                    TypeName::new("string"),
+                ))
+                .unwrap();
+
+        pretty_assertions::assert_eq!(
+            generate(declaration, code),
+            "export type A<T, U=string> = { T }"
+        );
+    }
+
+    #[test]
+    fn remove_generic_parameters_is_generated() {
+        let code = "type A<T> = { T }";
+        let mut declaration = parse_type_declaration(code);
+
+        assert!(declaration.remove_generic_parameters().is_some());
+        *declaration.mutate_type() = TypeName::new("string").into();
+
+        // the whitespace after the generic parameters is removed with them
+        pretty_assertions::assert_eq!(generate(declaration, code), "type A= string");
+    }
+}
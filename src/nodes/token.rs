@@ -159,6 +159,12 @@ impl Token {
         self.trailing_trivia.push(trivia);
     }
 
+    /// Removes the leading trivia of the token and returns it.
+    #[inline]
+    pub fn take_leading_trivia(&mut self) -> Vec<Trivia> {
+        std::mem::take(&mut self.leading_trivia)
+    }
+
     #[inline]
     pub fn iter_leading_trivia(&self) -> impl Iterator<Item = &Trivia> {
         self.leading_trivia.iter()
@@ -186,6 +192,38 @@ impl Token {
         }
     }
 
+    /// Sets the line number of the token. A token created only from its content gets
+    /// placed on that line by generators that retain lines.
+    pub fn set_line_number(&mut self, line_number: usize) {
+        match &mut self.position {
+            Position::LineNumberReference {
+                line_number: current,
+                ..
+            }
+            | Position::LineNumber {
+                line_number: current,
+                ..
+            } => {
+                *current = line_number;
+            }
+            Position::Any { content } => {
+                self.position = Position::LineNumber {
+                    content: std::mem::take(content),
+                    line_number,
+                };
+            }
+        }
+    }
+
+    /// Returns the byte offset where the token starts in the original code, if the
+    /// token still refers to it.
+    pub(crate) fn get_start(&self) -> Option<usize> {
+        match &self.position {
+            Position::LineNumberReference { start, .. } => Some(*start),
+            Position::LineNumber { .. } | Position::Any { .. } => None,
+        }
+    }
+
     pub fn replace_with_content<IntoCowStr: Into<Cow<'static, str>>>(
         &mut self,
         content: IntoCowStr,
@@ -292,4 +330,23 @@ mod test {
 
         assert_eq!("true", token.read(""));
     }
+
+    #[test]
+    fn set_line_number_of_any_position_token() {
+        let mut token = Token::from_content("true");
+        token.set_line_number(4);
+
+        assert_eq!(
+            token,
+            Token::from_position(Position::line_number("true", 4))
+        );
+    }
+
+    #[test]
+    fn set_line_number_of_line_number_reference_token() {
+        let mut token = Token::new_with_line(7, 11, 1);
+        token.set_line_number(3);
+
+        assert_eq!(token, Token::new_with_line(7, 11, 3));
+    }
 }
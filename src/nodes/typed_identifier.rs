@@ -1,10 +1,48 @@
 use crate::nodes::{Identifier, Token, Type};
 
+/// An attribute of a local variable declaration, available in Lua 5.4 (`local x <const> = 1`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    Const,
+    Close,
+}
+
+impl Attribute {
+    /// Returns the attribute from its name (the content between the angle brackets).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "const" => Some(Self::Const),
+            "close" => Some(Self::Close),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Const => "const",
+            Self::Close => "close",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeTokens {
+    pub opening_chevron: Token,
+    pub name: Token,
+    pub closing_chevron: Token,
+}
+
+impl AttributeTokens {
+    super::impl_token_fns!(target = [opening_chevron, name, closing_chevron]);
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TypedIdentifier {
     name: Identifier,
     r#type: Option<Type>,
     token: Option<Token>,
+    attribute: Option<Attribute>,
+    attribute_tokens: Option<AttributeTokens>,
 }
 
 impl TypedIdentifier {
@@ -13,6 +51,8 @@ impl TypedIdentifier {
             name: Identifier::new(name.into()),
             r#type: None,
             token: None,
+            attribute: None,
+            attribute_tokens: None,
         }
     }
 
@@ -61,9 +101,50 @@ impl TypedIdentifier {
         self.r#type.take()
     }
 
+    pub fn with_attribute(mut self, attribute: Attribute) -> Self {
+        self.attribute = Some(attribute);
+        self
+    }
+
+    #[inline]
+    pub fn set_attribute(&mut self, attribute: Attribute) {
+        self.attribute = Some(attribute);
+    }
+
+    #[inline]
+    pub fn get_attribute(&self) -> Option<Attribute> {
+        self.attribute
+    }
+
+    #[inline]
+    pub fn has_attribute(&self) -> bool {
+        self.attribute.is_some()
+    }
+
+    /// Removes the attribute of the identifier, along with its tokens.
+    pub fn remove_attribute(&mut self) -> Option<Attribute> {
+        self.attribute_tokens.take();
+        self.attribute.take()
+    }
+
+    pub fn with_attribute_tokens(mut self, tokens: AttributeTokens) -> Self {
+        self.attribute_tokens = Some(tokens);
+        self
+    }
+
+    #[inline]
+    pub fn set_attribute_tokens(&mut self, tokens: AttributeTokens) {
+        self.attribute_tokens = Some(tokens);
+    }
+
+    #[inline]
+    pub fn get_attribute_tokens(&self) -> Option<&AttributeTokens> {
+        self.attribute_tokens.as_ref()
+    }
+
     super::impl_token_fns!(
         target = [name]
-        iter = [token]
+        iter = [token, attribute_tokens]
     );
 }
 
@@ -73,6 +154,8 @@ impl<IntoIdentifier: Into<Identifier>> From<IntoIdentifier> for TypedIdentifier
             name: name.into(),
             r#type: None,
             token: None,
+            attribute: None,
+            attribute_tokens: None,
         }
     }
 }
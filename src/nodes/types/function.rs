@@ -161,6 +161,11 @@ impl FunctionType {
         self.generic_parameters.as_ref()
     }
 
+    #[inline]
+    pub fn mutate_generic_parameters(&mut self) -> Option<&mut GenericParameters> {
+        self.generic_parameters.as_mut()
+    }
+
     pub fn with_argument(mut self, argument: impl Into<FunctionArgumentType>) -> Self {
         self.arguments.push(argument.into());
         self
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use crate::nodes::{StringError, StringExpression, Token};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -38,10 +40,31 @@ impl StringType {
     }
 
     #[inline]
-    pub fn get_value(&self) -> &str {
+    pub fn as_bytes(&self) -> &[u8] {
+        self.value.as_bytes()
+    }
+
+    #[inline]
+    pub fn get_string_value(&self) -> Option<&str> {
+        self.value.get_string_value()
+    }
+
+    #[inline]
+    pub fn into_string(self) -> Option<String> {
+        self.value.into_string()
+    }
+
+    #[deprecated(
+        note = "strings can contain bytes that are not valid UTF-8, use `get_string_value` or `as_bytes`"
+    )]
+    #[allow(deprecated)]
+    #[inline]
+    pub fn get_value(&self) -> Cow<'_, str> {
         self.value.get_value()
     }
 
+    #[deprecated(note = "strings can contain bytes that are not valid UTF-8, use `into_string`")]
+    #[allow(deprecated)]
     #[inline]
     pub fn into_value(self) -> String {
         self.value.into_value()
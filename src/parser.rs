@@ -1,6 +1,7 @@
 use std::fmt;
 
-use full_moon::ast::Ast;
+use full_moon::{ast::Ast, LuaVersion};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     ast_converter::{AstConverter, ConvertError},
@@ -8,15 +9,40 @@ use crate::{
     utils::Timer,
 };
 
+/// The Lua dialect that the parser accepts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LuaDialect {
+    /// Luau syntax (type annotations, compound assignments, `continue`, ...).
+    #[default]
+    Luau,
+    /// Lua 5.2 syntax, which adds `goto` statements and labels.
+    Lua52,
+    /// Lua 5.4 syntax, which adds attributes on local variables (`<const>` and `<close>`).
+    Lua54,
+}
+
+impl LuaDialect {
+    fn lua_version(self) -> LuaVersion {
+        match self {
+            Self::Luau => LuaVersion::luau(),
+            Self::Lua52 => LuaVersion::lua52(),
+            Self::Lua54 => LuaVersion::lua54(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Parser {
     hold_token_data: bool,
+    dialect: LuaDialect,
 }
 
 impl Parser {
     pub fn parse(&self, code: &str) -> Result<Block, ParserError> {
         let full_moon_parse_timer = Timer::now();
-        let parse_result = full_moon::parse(code);
+        let parse_result =
+            full_moon::parse_fallible(code, self.dialect.lua_version()).into_result();
         log::trace!(
             "full-moon parsing done in {}",
             full_moon_parse_timer.duration_label()
@@ -38,6 +64,12 @@ impl Parser {
         self
     }
 
+    /// Sets the Lua dialect used to parse code. By default, code is parsed as Luau.
+    pub fn with_dialect(mut self, dialect: LuaDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
     pub(crate) fn is_preserving_tokens(&self) -> bool {
         self.hold_token_data
     }
@@ -510,6 +542,286 @@ mod test {
         ),
     );
 
+    mod lua52_dialect {
+        use super::*;
+
+        fn parse(code: &str) -> Block {
+            Parser::default()
+                .with_dialect(LuaDialect::Lua52)
+                .parse(code)
+                .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", code, err))
+        }
+
+        #[test]
+        fn parse_goto_statement() {
+            pretty_assertions::assert_eq!(
+                parse("goto continue"),
+                Block::from(GotoStatement::new("continue"))
+            );
+        }
+
+        #[test]
+        fn parse_label_statement() {
+            pretty_assertions::assert_eq!(
+                parse("::continue::"),
+                Block::from(LabelStatement::new("continue"))
+            );
+        }
+
+        #[test]
+        fn parse_goto_and_label_in_loop() {
+            pretty_assertions::assert_eq!(
+                parse("while true do goto skip ::skip:: end"),
+                Block::from(WhileStatement::new(
+                    Block::default()
+                        .with_statement(GotoStatement::new("skip"))
+                        .with_statement(LabelStatement::new("skip")),
+                    true,
+                ))
+            );
+        }
+
+        #[test]
+        fn parse_goto_with_tokens() {
+            let block = Parser::default()
+                .with_dialect(LuaDialect::Lua52)
+                .preserve_tokens()
+                .parse("goto done")
+                .unwrap();
+
+            pretty_assertions::assert_eq!(
+                block,
+                Block::from(
+                    GotoStatement::new(
+                        Identifier::new("done").with_token(Token::new_with_line(5, 9, 1))
+                    )
+                    .with_tokens(GotoTokens {
+                        goto: Token::new_with_line(0, 4, 1)
+                            .with_trailing_trivia(TriviaKind::Whitespace.at(4, 5, 1)),
+                    })
+                )
+                .with_tokens(BlockTokens {
+                    semicolons: vec![None],
+                    last_semicolon: None,
+                    final_token: None,
+                })
+            );
+        }
+
+        #[test]
+        fn parse_label_with_tokens() {
+            let block = Parser::default()
+                .with_dialect(LuaDialect::Lua52)
+                .preserve_tokens()
+                .parse("::done::")
+                .unwrap();
+
+            pretty_assertions::assert_eq!(
+                block,
+                Block::from(
+                    LabelStatement::new(
+                        Identifier::new("done").with_token(Token::new_with_line(2, 6, 1))
+                    )
+                    .with_tokens(LabelTokens {
+                        left_colons: Token::new_with_line(0, 2, 1),
+                        right_colons: Token::new_with_line(6, 8, 1),
+                    })
+                )
+                .with_tokens(BlockTokens {
+                    semicolons: vec![None],
+                    last_semicolon: None,
+                    final_token: None,
+                })
+            );
+        }
+
+        #[test]
+        fn luau_dialect_does_not_parse_labels() {
+            assert!(Parser::default().parse("::continue::").is_err());
+        }
+
+        #[test]
+        fn lua52_dialect_does_not_parse_compound_assignments() {
+            assert!(Parser::default()
+                .with_dialect(LuaDialect::Lua52)
+                .parse("a += 1")
+                .is_err());
+        }
+    }
+
+    mod lua54_dialect {
+        use super::*;
+
+        fn parse(code: &str) -> Block {
+            Parser::default()
+                .with_dialect(LuaDialect::Lua54)
+                .parse(code)
+                .unwrap_or_else(|err| panic!("failed to parse `{}`: {}", code, err))
+        }
+
+        #[test]
+        fn parse_local_const() {
+            pretty_assertions::assert_eq!(
+                parse("local x <const> = 1"),
+                Block::from(
+                    LocalAssignStatement::from_variable(
+                        TypedIdentifier::new("x").with_attribute(Attribute::Const)
+                    )
+                    .with_value(1)
+                )
+            );
+        }
+
+        #[test]
+        fn parse_local_close() {
+            pretty_assertions::assert_eq!(
+                parse("local file <close> = open()"),
+                Block::from(
+                    LocalAssignStatement::from_variable(
+                        TypedIdentifier::new("file").with_attribute(Attribute::Close)
+                    )
+                    .with_value(FunctionCall::from_name("open"))
+                )
+            );
+        }
+
+        #[test]
+        fn parse_attributes_on_some_variables() {
+            pretty_assertions::assert_eq!(
+                parse("local a <const>, b, c <close>"),
+                Block::from(
+                    LocalAssignStatement::from_variable(
+                        TypedIdentifier::new("a").with_attribute(Attribute::Const)
+                    )
+                    .with_variable("b")
+                    .with_variable(TypedIdentifier::new("c").with_attribute(Attribute::Close))
+                )
+            );
+        }
+
+        #[test]
+        fn parse_local_const_with_tokens() {
+            let block = Parser::default()
+                .with_dialect(LuaDialect::Lua54)
+                .preserve_tokens()
+                .parse("local x<const>")
+                .unwrap();
+
+            pretty_assertions::assert_eq!(
+                block,
+                Block::from(
+                    LocalAssignStatement::from_variable(
+                        TypedIdentifier::from(
+                            Identifier::new("x").with_token(Token::new_with_line(6, 7, 1))
+                        )
+                        .with_attribute(Attribute::Const)
+                        .with_attribute_tokens(AttributeTokens {
+                            opening_chevron: Token::new_with_line(7, 8, 1),
+                            name: Token::new_with_line(8, 13, 1),
+                            closing_chevron: Token::new_with_line(13, 14, 1),
+                        })
+                    )
+                    .with_tokens(LocalAssignTokens {
+                        local: Token::new_with_line(0, 5, 1)
+                            .with_trailing_trivia(TriviaKind::Whitespace.at(5, 6, 1)),
+                        equal: None,
+                        variable_commas: Vec::new(),
+                        value_commas: Vec::new(),
+                    })
+                )
+                .with_tokens(BlockTokens {
+                    semicolons: vec![None],
+                    last_semicolon: None,
+                    final_token: None,
+                })
+            );
+        }
+
+        #[test]
+        fn parse_unknown_attribute_errors() {
+            let error = Parser::default()
+                .with_dialect(LuaDialect::Lua54)
+                .parse("local x <unknown> = 1")
+                .unwrap_err();
+
+            pretty_assertions::assert_eq!(
+                error.to_string(),
+                "unable to convert attribute from `<unknown>`"
+            );
+        }
+
+        #[test]
+        fn luau_dialect_does_not_parse_attributes() {
+            assert!(Parser::default().parse("local x <const> = 1").is_err());
+        }
+    }
+
+    mod shebang {
+        use super::*;
+
+        #[test]
+        fn parse_shebang() {
+            pretty_assertions::assert_eq!(
+                Parser::default()
+                    .parse("#!/usr/bin/env lua\nprint('hello')")
+                    .unwrap(),
+                Block::from(
+                    FunctionCall::from_name("print")
+                        .with_argument(StringExpression::from_value("hello"))
+                )
+                .with_shebang("#!/usr/bin/env lua")
+            );
+        }
+
+        #[test]
+        fn parse_shebang_new_line() {
+            let block = Parser::default()
+                .parse("#!/usr/bin/env lua\nreturn")
+                .unwrap();
+
+            pretty_assertions::assert_eq!(block.get_shebang_new_line(), Some("\n"));
+        }
+
+        #[test]
+        fn parse_shebang_crlf_new_line() {
+            let block = Parser::default()
+                .preserve_tokens()
+                .parse("#!/usr/bin/env lua\r\nreturn")
+                .unwrap();
+
+            pretty_assertions::assert_eq!(block.get_shebang(), Some("#!/usr/bin/env lua"));
+            pretty_assertions::assert_eq!(block.get_shebang_new_line(), Some("\r\n"));
+        }
+
+        #[test]
+        fn parse_shebang_without_code() {
+            pretty_assertions::assert_eq!(
+                Parser::default().parse("#!/usr/bin/env lua\n").unwrap(),
+                Block::default().with_shebang("#!/usr/bin/env lua")
+            );
+        }
+
+        #[test]
+        fn parse_shebang_with_tokens() {
+            let code = "#!/usr/bin/env lua\n-- comment\nreturn";
+            let block = Parser::default().preserve_tokens().parse(code).unwrap();
+
+            pretty_assertions::assert_eq!(block.get_shebang(), Some("#!/usr/bin/env lua"));
+
+            let token = match block.get_last_statement() {
+                Some(LastStatement::Return(statement)) => statement.get_tokens().unwrap(),
+                _ => panic!("unexpected last statement"),
+            };
+            let trivia: Vec<_> = token
+                .r#return
+                .iter_leading_trivia()
+                .map(|trivia| trivia.read(code))
+                .collect();
+
+            pretty_assertions::assert_eq!(trivia, vec!["-- comment", "\n"]);
+        }
+    }
+
     mod parse_with_tokens {
         use super::*;
 
@@ -1,4 +1,6 @@
-use crate::nodes::{Expression, NumberExpression, StringExpression};
+use crate::nodes::{
+    Expression, NumberExpression, StringExpression, UnaryExpression, UnaryOperator,
+};
 
 /// Represents an evaluated Expression result.
 #[derive(Debug, Clone, PartialEq)]
@@ -8,6 +10,8 @@ pub enum LuaValue {
     Nil,
     Number(f64),
     String(String),
+    /// A string that contains bytes that are not valid UTF-8.
+    Bytes(Vec<u8>),
     Table,
     True,
     Unknown,
@@ -75,7 +79,8 @@ impl LuaValue {
             Self::True => Some(Expression::from(true)),
             Self::Nil => Some(Expression::nil()),
             Self::String(value) => Some(StringExpression::from_value(value).into()),
-            Self::Number(value) => Some(Expression::from(value)),
+            Self::Bytes(value) => Some(StringExpression::from_bytes(value).into()),
+            Self::Number(value) => Some(number_to_expression(value)),
             _ => None,
         }
     }
@@ -117,6 +122,18 @@ impl LuaValue {
     }
 }
 
+/// Writes finite numbers with the fewest digits that parse back to the same value. The
+/// sign is written with a unary minus, since number literals cannot be negative.
+fn number_to_expression(value: f64) -> Expression {
+    match NumberExpression::from_f64_shortest(value.abs()) {
+        Some(number) if value.is_sign_negative() => {
+            UnaryExpression::new(UnaryOperator::Minus, number).into()
+        }
+        Some(number) => number.into(),
+        None => Expression::from(value),
+    }
+}
+
 impl Default for LuaValue {
     fn default() -> Self {
         Self::Unknown
@@ -185,6 +202,11 @@ mod test {
         assert!(LuaValue::String("".to_owned()).is_truthy().unwrap());
     }
 
+    #[test]
+    fn bytes_value_is_truthy() {
+        assert!(LuaValue::Bytes(vec![255]).is_truthy().unwrap());
+    }
+
     #[test]
     fn table_value_is_truthy() {
         assert!(LuaValue::Table.is_truthy().unwrap());
@@ -244,4 +266,35 @@ mod test {
             two_seperated_digits(" 1 2")
         );
     }
+
+    mod to_expression {
+        use super::*;
+        use crate::nodes::DecimalNumber;
+
+        macro_rules! number_to_expression {
+            ($($name:ident($value:expr) => $expected:expr),* $(,)?) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(
+                            LuaValue::Number($value).to_expression(),
+                            Some(Expression::from($expected))
+                        );
+                    }
+                )*
+            };
+        }
+
+        number_to_expression!(
+            zero(0.0) => DecimalNumber::new(0.0),
+            negative_zero(-0.0) => UnaryExpression::new(UnaryOperator::Minus, DecimalNumber::new(0.0)),
+            sum_of_tenths(0.1 + 0.2) => DecimalNumber::new(0.30000000000000004),
+            negative(-1.5) => UnaryExpression::new(UnaryOperator::Minus, DecimalNumber::new(1.5)),
+            small_value(1.5e-7) => DecimalNumber::new(1.5e-7).with_exponent(-7, true),
+            negative_small_value(-1.5e-7) => UnaryExpression::new(
+                UnaryOperator::Minus,
+                DecimalNumber::new(1.5e-7).with_exponent(-7, true),
+            ),
+        );
+    }
 }
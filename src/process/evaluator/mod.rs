@@ -26,7 +26,10 @@ impl Evaluator {
             Expression::Function(_) => LuaValue::Function,
             Expression::Nil(_) => LuaValue::Nil,
             Expression::Number(number) => LuaValue::from(number.compute_value()),
-            Expression::String(string) => LuaValue::from(string.get_value()),
+            Expression::String(string) => string
+                .get_string_value()
+                .map(LuaValue::from)
+                .unwrap_or_else(|| LuaValue::Bytes(string.as_bytes().to_vec())),
             Expression::Table(_) => LuaValue::Table,
             Expression::True(_) => LuaValue::True,
             Expression::Binary(binary) => self.evaluate_binary(binary),
@@ -60,6 +63,7 @@ impl Evaluator {
                                 }
                                 LuaValue::Function
                                 | LuaValue::Number(_)
+                                | LuaValue::Bytes(_)
                                 | LuaValue::Table
                                 | LuaValue::Unknown => return LuaValue::Unknown,
                             }
@@ -276,6 +280,7 @@ impl Evaluator {
             | LuaValue::Nil
             | LuaValue::Number(_)
             | LuaValue::String(_)
+            | LuaValue::Bytes(_)
             | LuaValue::Table
             | LuaValue::True => false,
             LuaValue::Unknown => true,
@@ -346,6 +351,7 @@ impl Evaluator {
                 LuaValue::from((a - b).abs() < f64::EPSILON)
             }
             (LuaValue::String(a), LuaValue::String(b)) => LuaValue::from(a == b),
+            (LuaValue::Bytes(a), LuaValue::Bytes(b)) => LuaValue::from(a == b),
             _ => LuaValue::False,
         }
     }
@@ -479,6 +485,7 @@ mod test {
         number_expression(DecimalNumber::new(0.0)) => LuaValue::Number(0.0),
         number_expression_negative_zero(DecimalNumber::new(-0.0)) => LuaValue::Number(-0.0),
         string_expression(StringExpression::from_value("foo")) => LuaValue::String("foo".to_owned()),
+        non_utf8_string_expression(StringExpression::from_bytes(vec![0xFF, b'a'])) => LuaValue::Bytes(vec![0xFF, b'a']),
         empty_interpolated_string_expression(InterpolatedStringExpression::empty()) => LuaValue::String("".to_owned()),
         interpolated_string_expression_with_one_string(InterpolatedStringExpression::empty().with_segment("hello"))
             => LuaValue::String("hello".to_owned()),
@@ -697,6 +704,11 @@ mod test {
                 Expression::from(11.0),
                 Expression::from(3.0)
             ) => LuaValue::Number(3.0),
+            minus_seven_floor_division_by_two(
+                BinaryOperator::DoubleSlash,
+                Expression::from(-7.0),
+                Expression::from(2.0)
+            ) => LuaValue::Number(-4.0),
             one_floor_division_by_zero(
                 BinaryOperator::DoubleSlash,
                 Expression::from(1.0),
@@ -869,6 +881,18 @@ mod test {
                 StringExpression::from_value("foo"),
                 StringExpression::from_value("bar")
             ) => LuaValue::False,
+            same_non_utf8_strings(
+                StringExpression::from_bytes(vec![0xFF]),
+                StringExpression::from_bytes(vec![0xFF])
+            ) => LuaValue::True,
+            different_non_utf8_strings(
+                StringExpression::from_bytes(vec![0xFF]),
+                StringExpression::from_bytes(vec![0xFE])
+            ) => LuaValue::False,
+            non_utf8_string_and_string(
+                StringExpression::from_bytes(vec![0xFF]),
+                StringExpression::from_value("foo")
+            ) => LuaValue::False,
         );
 
         macro_rules! evaluate_equality_with_relational_operators {
@@ -10,8 +10,11 @@ pub struct NodeCounter {
     pub do_count: usize,
     pub function_count: usize,
     pub generic_for_count: usize,
+    pub goto_count: usize,
+    pub label_count: usize,
     pub if_count: usize,
     pub local_assign_count: usize,
+    pub local_attribute_count: usize,
     pub local_function_count: usize,
     pub numeric_for_count: usize,
     pub repeat_count: usize,
@@ -59,6 +62,18 @@ impl NodeProcessor for NodeCounter {
         self.generic_for_count += 1;
     }
 
+    fn process_goto_statement(&mut self, _: &mut GotoStatement) {
+        self.goto_count += 1;
+    }
+
+    fn process_label_statement(&mut self, _: &mut LabelStatement) {
+        self.label_count += 1;
+    }
+
+    fn process_local_attribute(&mut self, _: &mut TypedIdentifier) {
+        self.local_attribute_count += 1;
+    }
+
     fn process_if_statement(&mut self, _: &mut IfStatement) {
         self.if_count += 1;
     }
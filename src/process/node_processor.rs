@@ -14,9 +14,13 @@ pub trait NodeProcessor {
     fn process_do_statement(&mut self, _: &mut DoStatement) {}
     fn process_function_statement(&mut self, _: &mut FunctionStatement) {}
     fn process_generic_for_statement(&mut self, _: &mut GenericForStatement) {}
+    fn process_goto_statement(&mut self, _: &mut GotoStatement) {}
     fn process_if_statement(&mut self, _: &mut IfStatement) {}
+    fn process_label_statement(&mut self, _: &mut LabelStatement) {}
     fn process_last_statement(&mut self, _: &mut LastStatement) {}
     fn process_local_assign_statement(&mut self, _: &mut LocalAssignStatement) {}
+    /// Called for each variable of a local assignment that has an attribute (`<const>` or `<close>`).
+    fn process_local_attribute(&mut self, _: &mut TypedIdentifier) {}
     fn process_local_function_statement(&mut self, _: &mut LocalFunctionStatement) {}
     fn process_numeric_for_statement(&mut self, _: &mut NumericForStatement) {}
     fn process_repeat_statement(&mut self, _: &mut RepeatStatement) {}
@@ -24,6 +28,7 @@ pub trait NodeProcessor {
     fn process_type_declaration(&mut self, _: &mut TypeDeclarationStatement) {}
 
     fn process_variable(&mut self, _: &mut Variable) {}
+    fn process_typed_identifier(&mut self, _: &mut TypedIdentifier) {}
 
     fn process_expression(&mut self, _: &mut Expression) {}
 
@@ -73,7 +78,9 @@ pub trait NodePostProcessor {
     fn process_after_do_statement(&mut self, _: &mut DoStatement) {}
     fn process_after_function_statement(&mut self, _: &mut FunctionStatement) {}
     fn process_after_generic_for_statement(&mut self, _: &mut GenericForStatement) {}
+    fn process_after_goto_statement(&mut self, _: &mut GotoStatement) {}
     fn process_after_if_statement(&mut self, _: &mut IfStatement) {}
+    fn process_after_label_statement(&mut self, _: &mut LabelStatement) {}
     fn process_after_last_statement(&mut self, _: &mut LastStatement) {}
     fn process_after_local_assign_statement(&mut self, _: &mut LocalAssignStatement) {}
     fn process_after_local_function_statement(&mut self, _: &mut LocalFunctionStatement) {}
@@ -83,6 +90,7 @@ pub trait NodePostProcessor {
     fn process_after_type_declaration(&mut self, _: &mut TypeDeclarationStatement) {}
 
     fn process_after_variable(&mut self, _: &mut Variable) {}
+    fn process_after_typed_identifier(&mut self, _: &mut TypedIdentifier) {}
 
     fn process_after_expression(&mut self, _: &mut Expression) {}
 
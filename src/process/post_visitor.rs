@@ -32,7 +32,9 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
             }
             Statement::Function(statement) => Self::visit_function_statement(statement, processor),
             Statement::GenericFor(statement) => Self::visit_generic_for(statement, processor),
+            Statement::Goto(statement) => Self::visit_goto_statement(statement, processor),
             Statement::If(statement) => Self::visit_if_statement(statement, processor),
+            Statement::Label(statement) => Self::visit_label_statement(statement, processor),
             Statement::LocalAssign(statement) => Self::visit_local_assign(statement, processor),
             Statement::LocalFunction(statement) => Self::visit_local_function(statement, processor),
             Statement::NumericFor(statement) => Self::visit_numeric_for(statement, processor),
@@ -155,11 +157,8 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
 
         Self::visit_block(function.mutate_block(), processor);
 
-        for r#type in function
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in function.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, processor);
         }
 
         if let Some(variadic_type) = function.mutate_variadic_type() {
@@ -193,6 +192,16 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
         processor.process_after_do_statement(statement);
     }
 
+    fn visit_goto_statement(statement: &mut GotoStatement, processor: &mut T) {
+        processor.process_goto_statement(statement);
+        processor.process_after_goto_statement(statement);
+    }
+
+    fn visit_label_statement(statement: &mut LabelStatement, processor: &mut T) {
+        processor.process_label_statement(statement);
+        processor.process_after_label_statement(statement);
+    }
+
     fn visit_compound_assign(statement: &mut CompoundAssignStatement, processor: &mut T) {
         processor.process_compound_assign_statement(statement);
         Self::visit_variable(statement.mutate_variable(), processor);
@@ -211,11 +220,8 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, processor);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -238,11 +244,8 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        for r#type in statement
-            .iter_mut_identifiers()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_identifiers() {
+            Self::visit_typed_identifier(identifier, processor);
         }
         processor.process_after_generic_for_statement(statement);
     }
@@ -266,15 +269,19 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
     fn visit_local_assign(statement: &mut LocalAssignStatement, processor: &mut T) {
         processor.process_local_assign_statement(statement);
 
+        for variable in statement
+            .iter_mut_variables()
+            .filter(|variable| variable.has_attribute())
+        {
+            processor.process_local_attribute(variable);
+        }
+
         statement
             .iter_mut_values()
             .for_each(|value| Self::visit_expression(value, processor));
 
-        for r#type in statement
-            .iter_mut_variables()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_variables() {
+            Self::visit_typed_identifier(identifier, processor);
         }
         processor.process_after_local_assign_statement(statement);
     }
@@ -284,11 +291,8 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, processor);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -330,9 +334,7 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        if let Some(r#type) = statement.mutate_identifier().mutate_type() {
-            Self::visit_type(r#type, processor);
-        }
+        Self::visit_typed_identifier(statement.mutate_identifier(), processor);
         processor.process_after_numeric_for_statement(statement);
     }
 
@@ -491,6 +493,15 @@ pub trait NodePostVisitor<T: NodeProcessor + NodePostProcessor> {
         processor.process_after_prefix_expression(prefix);
     }
 
+    fn visit_typed_identifier(identifier: &mut TypedIdentifier, processor: &mut T) {
+        processor.process_typed_identifier(identifier);
+
+        if let Some(r#type) = identifier.mutate_type() {
+            Self::visit_type(r#type, processor);
+        }
+        processor.process_after_typed_identifier(identifier);
+    }
+
     fn visit_type(r#type: &mut Type, processor: &mut T) {
         processor.process_type(r#type);
 
@@ -54,15 +54,19 @@ impl<T: NodeProcessor + Scope> NodeVisitor<T> for ScopeVisitor {
     fn visit_local_assign(statement: &mut LocalAssignStatement, scope: &mut T) {
         scope.process_local_assign_statement(statement);
 
+        for variable in statement
+            .iter_mut_variables()
+            .filter(|variable| variable.has_attribute())
+        {
+            scope.process_local_attribute(variable);
+        }
+
         statement
             .iter_mut_values()
             .for_each(|value| Self::visit_expression(value, scope));
 
-        for r#type in statement
-            .iter_mut_variables()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_variables() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         statement.for_each_assignment(|variable, expression| {
@@ -73,11 +77,8 @@ impl<T: NodeProcessor + Scope> NodeVisitor<T> for ScopeVisitor {
     fn visit_function_expression(function: &mut FunctionExpression, scope: &mut T) {
         scope.process_function_expression(function);
 
-        for r#type in function
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in function.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         if let Some(variadic_type) = function.mutate_variadic_type() {
@@ -104,11 +105,8 @@ impl<T: NodeProcessor + Scope> NodeVisitor<T> for ScopeVisitor {
         scope.process_function_statement(statement);
         scope.process_variable_expression(statement.mutate_function_name().mutate_identifier());
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -139,11 +137,8 @@ impl<T: NodeProcessor + Scope> NodeVisitor<T> for ScopeVisitor {
 
         scope.insert_local_function(statement);
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -178,11 +173,8 @@ impl<T: NodeProcessor + Scope> NodeVisitor<T> for ScopeVisitor {
             .iter_mut_identifiers()
             .for_each(|identifier| scope.insert(identifier.mutate_name()));
 
-        for r#type in statement
-            .iter_mut_identifiers()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_identifiers() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         scope.process_scope(statement.mutate_block(), None);
@@ -201,9 +193,7 @@ impl<T: NodeProcessor + Scope> NodeVisitor<T> for ScopeVisitor {
             Self::visit_expression(step, scope);
         };
 
-        if let Some(r#type) = statement.mutate_identifier().mutate_type() {
-            Self::visit_type(r#type, scope);
-        }
+        Self::visit_typed_identifier(statement.mutate_identifier(), scope);
 
         scope.push();
         scope.insert(statement.mutate_identifier().mutate_name());
@@ -260,15 +250,19 @@ impl<T: NodeProcessor + NodePostProcessor + Scope> NodePostVisitor<T> for ScopeP
     fn visit_local_assign(statement: &mut LocalAssignStatement, scope: &mut T) {
         scope.process_local_assign_statement(statement);
 
+        for variable in statement
+            .iter_mut_variables()
+            .filter(|variable| variable.has_attribute())
+        {
+            scope.process_local_attribute(variable);
+        }
+
         statement
             .iter_mut_values()
             .for_each(|value| Self::visit_expression(value, scope));
 
-        for r#type in statement
-            .iter_mut_variables()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_variables() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         statement.for_each_assignment(|variable, expression| {
@@ -281,11 +275,8 @@ impl<T: NodeProcessor + NodePostProcessor + Scope> NodePostVisitor<T> for ScopeP
     fn visit_function_expression(function: &mut FunctionExpression, scope: &mut T) {
         scope.process_function_expression(function);
 
-        for r#type in function
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in function.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         if let Some(variadic_type) = function.mutate_variadic_type() {
@@ -314,11 +305,8 @@ impl<T: NodeProcessor + NodePostProcessor + Scope> NodePostVisitor<T> for ScopeP
         scope.process_function_statement(statement);
         scope.process_variable_expression(statement.mutate_function_name().mutate_identifier());
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -351,11 +339,8 @@ impl<T: NodeProcessor + NodePostProcessor + Scope> NodePostVisitor<T> for ScopeP
 
         scope.insert_local_function(statement);
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -392,11 +377,8 @@ impl<T: NodeProcessor + NodePostProcessor + Scope> NodePostVisitor<T> for ScopeP
             .iter_mut_identifiers()
             .for_each(|identifier| scope.insert(identifier.mutate_name()));
 
-        for r#type in statement
-            .iter_mut_identifiers()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, scope);
+        for identifier in statement.iter_mut_identifiers() {
+            Self::visit_typed_identifier(identifier, scope);
         }
 
         scope.process_scope(statement.mutate_block(), None);
@@ -417,9 +399,7 @@ impl<T: NodeProcessor + NodePostProcessor + Scope> NodePostVisitor<T> for ScopeP
             Self::visit_expression(step, scope);
         };
 
-        if let Some(r#type) = statement.mutate_identifier().mutate_type() {
-            Self::visit_type(r#type, scope);
-        }
+        Self::visit_typed_identifier(statement.mutate_identifier(), scope);
 
         scope.push();
         scope.insert(statement.mutate_identifier().mutate_name());
@@ -29,7 +29,9 @@ pub trait NodeVisitor<T: NodeProcessor> {
             }
             Statement::Function(statement) => Self::visit_function_statement(statement, processor),
             Statement::GenericFor(statement) => Self::visit_generic_for(statement, processor),
+            Statement::Goto(statement) => Self::visit_goto_statement(statement, processor),
             Statement::If(statement) => Self::visit_if_statement(statement, processor),
+            Statement::Label(statement) => Self::visit_label_statement(statement, processor),
             Statement::LocalAssign(statement) => Self::visit_local_assign(statement, processor),
             Statement::LocalFunction(statement) => Self::visit_local_function(statement, processor),
             Statement::NumericFor(statement) => Self::visit_numeric_for(statement, processor),
@@ -142,11 +144,8 @@ pub trait NodeVisitor<T: NodeProcessor> {
 
         Self::visit_block(function.mutate_block(), processor);
 
-        for r#type in function
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in function.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, processor);
         }
 
         if let Some(variadic_type) = function.mutate_variadic_type() {
@@ -177,6 +176,14 @@ pub trait NodeVisitor<T: NodeProcessor> {
         Self::visit_block(statement.mutate_block(), processor);
     }
 
+    fn visit_goto_statement(statement: &mut GotoStatement, processor: &mut T) {
+        processor.process_goto_statement(statement);
+    }
+
+    fn visit_label_statement(statement: &mut LabelStatement, processor: &mut T) {
+        processor.process_label_statement(statement);
+    }
+
     fn visit_compound_assign(statement: &mut CompoundAssignStatement, processor: &mut T) {
         processor.process_compound_assign_statement(statement);
         Self::visit_variable(statement.mutate_variable(), processor);
@@ -194,11 +201,8 @@ pub trait NodeVisitor<T: NodeProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, processor);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -220,11 +224,8 @@ pub trait NodeVisitor<T: NodeProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        for r#type in statement
-            .iter_mut_identifiers()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_identifiers() {
+            Self::visit_typed_identifier(identifier, processor);
         }
     }
 
@@ -246,15 +247,19 @@ pub trait NodeVisitor<T: NodeProcessor> {
     fn visit_local_assign(statement: &mut LocalAssignStatement, processor: &mut T) {
         processor.process_local_assign_statement(statement);
 
+        for variable in statement
+            .iter_mut_variables()
+            .filter(|variable| variable.has_attribute())
+        {
+            processor.process_local_attribute(variable);
+        }
+
         statement
             .iter_mut_values()
             .for_each(|value| Self::visit_expression(value, processor));
 
-        for r#type in statement
-            .iter_mut_variables()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_variables() {
+            Self::visit_typed_identifier(identifier, processor);
         }
     }
 
@@ -263,11 +268,8 @@ pub trait NodeVisitor<T: NodeProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        for r#type in statement
-            .iter_mut_parameters()
-            .filter_map(TypedIdentifier::mutate_type)
-        {
-            Self::visit_type(r#type, processor);
+        for identifier in statement.iter_mut_parameters() {
+            Self::visit_typed_identifier(identifier, processor);
         }
 
         if let Some(variadic_type) = statement.mutate_variadic_type() {
@@ -307,9 +309,7 @@ pub trait NodeVisitor<T: NodeProcessor> {
         processor.process_scope(statement.mutate_block(), None);
         Self::visit_block(statement.mutate_block(), processor);
 
-        if let Some(r#type) = statement.mutate_identifier().mutate_type() {
-            Self::visit_type(r#type, processor);
-        }
+        Self::visit_typed_identifier(statement.mutate_identifier(), processor);
     }
 
     fn visit_repeat_statement(statement: &mut RepeatStatement, processor: &mut T) {
@@ -456,6 +456,14 @@ pub trait NodeVisitor<T: NodeProcessor> {
         };
     }
 
+    fn visit_typed_identifier(identifier: &mut TypedIdentifier, processor: &mut T) {
+        processor.process_typed_identifier(identifier);
+
+        if let Some(r#type) = identifier.mutate_type() {
+            Self::visit_type(r#type, processor);
+        }
+    }
+
     fn visit_type(r#type: &mut Type, processor: &mut T) {
         processor.process_type(r#type);
 
@@ -650,6 +658,118 @@ mod test {
         assert_eq!(counter.do_count, 1);
     }
 
+    #[test]
+    fn visit_goto_and_label_statements() {
+        let mut counter = NodeCounter::new();
+        let mut block = Block::default()
+            .with_statement(GotoStatement::new("done"))
+            .with_statement(LabelStatement::new("done"));
+
+        DefaultVisitor::visit_block(&mut block, &mut counter);
+
+        assert_eq!(counter.block_count, 1);
+        assert_eq!(counter.goto_count, 1);
+        assert_eq!(counter.label_count, 1);
+    }
+
+    struct TypeNameRenamer;
+
+    impl NodeProcessor for TypeNameRenamer {
+        fn process_type_name(&mut self, type_name: &mut TypeName) {
+            let name = format!("T_{}", type_name.get_type_name().get_name());
+            type_name.mutate_type_name().set_name(name);
+        }
+    }
+
+    fn rename_type_names(code: &str) -> String {
+        let mut block = crate::Parser::default().parse(code).unwrap();
+
+        DefaultVisitor::visit_block(&mut block, &mut TypeNameRenamer);
+
+        let mut generator = crate::generator::DenseLuaGenerator::default();
+        crate::generator::LuaGenerator::write_block(&mut generator, &block);
+        crate::generator::LuaGenerator::into_string(generator)
+    }
+
+    #[test]
+    fn rename_type_names_in_unions() {
+        assert_eq!(
+            rename_type_names("local a: A | (B?) | (C & D)"),
+            "local a:T_A|(T_B?)|(T_C&T_D)"
+        );
+    }
+
+    #[test]
+    fn rename_type_names_in_generics() {
+        assert_eq!(
+            rename_type_names("type A<T = B> = C<D, E<F>>"),
+            "type A<T=T_B> =T_C<T_D,T_E<T_F>>"
+        );
+    }
+
+    #[test]
+    fn rename_type_names_in_table_fields() {
+        assert_eq!(
+            rename_type_names("type A = { field: B, [C]: D, ['key']: { E } }"),
+            "type A={field:T_B,[T_C]:T_D,['key']:{T_E}}"
+        );
+    }
+
+    #[test]
+    fn rename_type_names_in_function_types() {
+        assert_eq!(
+            rename_type_names("type A = (B, name: C, ...D) -> E"),
+            "type A=(T_B,name:T_C,...T_D)->T_E"
+        );
+    }
+
+    #[test]
+    fn rename_type_names_in_function_parameters() {
+        assert_eq!(
+            rename_type_names("function f(a: A, ...: B): C for i: D = 1, 2 do end end"),
+            "function f(a:T_A,...:T_B):T_C for i:T_D=1,2 do end end"
+        );
+    }
+
+    #[test]
+    fn visit_typed_identifiers() {
+        #[derive(Default)]
+        struct TypedIdentifierCounter(Vec<String>);
+
+        impl NodeProcessor for TypedIdentifierCounter {
+            fn process_typed_identifier(&mut self, identifier: &mut TypedIdentifier) {
+                self.0.push(identifier.get_name().to_owned());
+            }
+        }
+
+        let mut block = crate::Parser::default()
+            .parse("local a, b: B = function(c) end for d, e in f do end for g = 1, 2 do end local function h(i) end")
+            .unwrap();
+        let mut counter = TypedIdentifierCounter::default();
+
+        DefaultVisitor::visit_block(&mut block, &mut counter);
+
+        counter.0.sort();
+        assert_eq!(counter.0, vec!["a", "b", "c", "d", "e", "g", "i"]);
+    }
+
+    #[test]
+    fn visit_local_attributes() {
+        let mut counter = NodeCounter::new();
+        let mut block = Block::default().with_statement(
+            LocalAssignStatement::from_variable(
+                TypedIdentifier::new("a").with_attribute(Attribute::Const),
+            )
+            .with_variable("b")
+            .with_variable(TypedIdentifier::new("c").with_attribute(Attribute::Close)),
+        );
+
+        DefaultVisitor::visit_block(&mut block, &mut counter);
+
+        assert_eq!(counter.local_assign_count, 1);
+        assert_eq!(counter.local_attribute_count, 2);
+    }
+
     #[test]
     fn visit_numeric_for_statement() {
         let mut counter = NodeCounter::new();
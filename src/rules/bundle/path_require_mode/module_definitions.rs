@@ -199,26 +199,15 @@ impl BuildModuleDefinitions {
             })
             .map(Statement::from)
             .collect();
-        block.insert_statement(0, DoStatement::new(Block::new(statements, None)));
 
         let modules_table = self.build_modules_table();
-        block.insert_statement(
-            0,
-            AssignStatement::from_variable(modules_identifier, modules_table),
-        );
-        block.insert_statement(
-            0,
-            LocalAssignStatement::from_variable(self.modules_identifier),
-        );
 
-        for statement in self
-            .rename_type_declaration
-            .extract_type_declarations()
-            .into_iter()
-            .rev()
-        {
-            block.insert_statement(0, statement);
-        }
+        let mut prelude = self.rename_type_declaration.extract_type_declarations();
+        prelude.push(LocalAssignStatement::from_variable(self.modules_identifier).into());
+        prelude.push(AssignStatement::from_variable(modules_identifier, modules_table).into());
+        prelude.push(DoStatement::new(Block::new(statements, None)).into());
+
+        block.splice(0..0, prelude);
     }
 
     fn build_modules_table(&self) -> TableExpression {
@@ -74,30 +74,25 @@ impl RenameTypeDeclarationProcessor {
     }
 
     fn get_module_name(&self, value: &Expression) -> Option<String> {
-        if let Expression::Call(value) = value {
-            if let Prefix::Field(field) = value.get_prefix() {
-                if field.get_field().get_name() == self.module_load_field {
-                    if let Prefix::Identifier(variable) = field.get_prefix() {
-                        if variable.get_name() == &self.modules_identifier {
-                            return value
-                                .get_arguments()
-                                .clone()
-                                .to_expressions()
-                                .into_iter()
-                                .next()
-                                .and_then(|argument| {
-                                    if let Expression::String(string_value) = argument {
-                                        Some(string_value.into_value())
-                                    } else {
-                                        None
-                                    }
-                                });
-                        }
-                    }
-                }
-            }
+        let call = value.as_call()?;
+        let field = call.get_prefix().as_field()?;
+
+        if field.get_field().get_name() != self.module_load_field {
+            return None;
         }
-        None
+
+        let variable = field.get_prefix().as_identifier()?;
+
+        if variable.get_name() != &self.modules_identifier {
+            return None;
+        }
+
+        call.get_arguments()
+            .clone()
+            .to_expressions()
+            .into_iter()
+            .next()
+            .and_then(|argument| argument.as_string()?.get_string_value().map(str::to_owned))
     }
 
     fn rename_type_declaration(&mut self, declaration: &mut TypeDeclarationStatement) {
@@ -32,7 +32,9 @@ impl Processor {
                 | Statement::CompoundAssign(_)
                 | Statement::Function(_)
                 | Statement::GenericFor(_)
+                | Statement::Goto(_)
                 | Statement::If(_)
+                | Statement::Label(_)
                 | Statement::LocalAssign(_)
                 | Statement::LocalFunction(_)
                 | Statement::NumericFor(_)
@@ -56,7 +56,7 @@ impl NodeProcessor for ValueInjection {
             }
             Expression::Index(index) => {
                 !self.is_identifier_used("_G")
-                    && matches!(index.get_index(), Expression::String(string) if string.get_value() == self.identifier)
+                    && matches!(index.get_index(), Expression::String(string) if string.get_string_value() == Some(self.identifier.as_str()))
                     && matches!(index.get_prefix(), Prefix::Identifier(prefix) if prefix.get_name() == "_G")
             }
             _ => false,
@@ -214,7 +214,9 @@ impl RuleConfiguration for InjectGlobalValue {
                     RulePropertyValue::Float(value)
                 }
             }
-            Expression::String(string) => RulePropertyValue::from(string.get_value()),
+            Expression::String(string) => {
+                RulePropertyValue::from(String::from_utf8_lossy(string.as_bytes()).into_owned())
+            }
             Expression::Unary(unary) => {
                 if matches!(unary.operator(), UnaryOperator::Minus) {
                     if let Expression::Number(number) = unary.get_expression() {
@@ -148,7 +148,7 @@ impl<Args, T: CallMatch<Args>> ops::DerefMut for RemoveFunctionCallProcessor<Arg
 
 impl<Args, T: CallMatch<Args>> NodeProcessor for RemoveFunctionCallProcessor<Args, T> {
     fn process_statement(&mut self, statement: &mut Statement) {
-        if let Statement::Call(call) = statement {
+        if let Some(call) = statement.as_call() {
             if call.get_method().is_none()
                 && self
                     .matcher
@@ -164,7 +164,7 @@ impl<Args, T: CallMatch<Args>> NodeProcessor for RemoveFunctionCallProcessor<Arg
     }
 
     fn process_expression(&mut self, expression: &mut Expression) {
-        if let Expression::Call(call) = expression {
+        if let Some(call) = expression.as_call() {
             if call.get_method().is_none()
                 && self
                     .matcher
@@ -39,10 +39,18 @@ impl NodeProcessor for RemoveCommentProcessor {
         generic_for.clear_comments();
     }
 
+    fn process_goto_statement(&mut self, statement: &mut GotoStatement) {
+        statement.clear_comments();
+    }
+
     fn process_if_statement(&mut self, if_statement: &mut IfStatement) {
         if_statement.clear_comments();
     }
 
+    fn process_label_statement(&mut self, statement: &mut LabelStatement) {
+        statement.clear_comments();
+    }
+
     fn process_last_statement(&mut self, statement: &mut LastStatement) {
         match statement {
             LastStatement::Break(token) | LastStatement::Continue(token) => {
@@ -281,10 +289,18 @@ impl NodeProcessor for FilterCommentProcessor<'_> {
         generic_for.filter_comments(|trivia| self.ignore_trivia(trivia));
     }
 
+    fn process_goto_statement(&mut self, statement: &mut GotoStatement) {
+        statement.filter_comments(|trivia| self.ignore_trivia(trivia));
+    }
+
     fn process_if_statement(&mut self, if_statement: &mut IfStatement) {
         if_statement.filter_comments(|trivia| self.ignore_trivia(trivia));
     }
 
+    fn process_label_statement(&mut self, statement: &mut LabelStatement) {
+        statement.filter_comments(|trivia| self.ignore_trivia(trivia));
+    }
+
     fn process_last_statement(&mut self, statement: &mut LastStatement) {
         match statement {
             LastStatement::Break(token) | LastStatement::Continue(token) => {
@@ -208,7 +208,7 @@ impl Processor {
         variable: impl Into<Variable>,
     ) -> Statement {
         let variable = variable.into();
-        DoStatement::new(
+        let do_statement: Statement = DoStatement::new(
             Block::default()
                 .with_statement(assign.into())
                 .with_statement(self.create_new_assignment_with_variable(
@@ -217,7 +217,17 @@ impl Processor {
                     Some(variable),
                 )),
         )
-        .into()
+        .into();
+
+        // place the new `do` block on the line of the compound assignment, so that
+        // generators retaining lines do not split it across the previous lines
+        match compound_assignment
+            .get_tokens()
+            .and_then(|tokens| tokens.operator.get_line_number())
+        {
+            Some(line) => do_statement.with_line(line),
+            None => do_statement,
+        }
     }
 
     fn create_new_assignment(
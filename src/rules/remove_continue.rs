@@ -1,58 +1,231 @@
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::mem;
 
 use crate::nodes::{
-    AssignStatement, Block, GenericForStatement, Identifier, IfStatement, LastStatement,
-    LocalAssignStatement, NumericForStatement, RepeatStatement, UnaryExpression, UnaryOperator,
+    AssignStatement, Block, DoStatement, Expression, FunctionExpression, FunctionStatement,
+    GenericForStatement, GotoStatement, GotoTokens, Identifier, IfStatement, LabelStatement,
+    LastStatement, LocalAssignStatement, LocalFunctionStatement, NumericForStatement,
+    RepeatStatement, Statement, Token, TypedIdentifier, UnaryExpression, UnaryOperator, Variable,
     WhileStatement,
 };
-use crate::process::{DefaultPostVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor};
-use crate::rules::{Context, RuleConfiguration, RuleConfigurationError, RuleProperties};
+use crate::process::processors::FindVariables;
+use crate::process::{
+    DefaultPostVisitor, DefaultVisitor, NodePostProcessor, NodePostVisitor, NodeProcessor,
+    NodeVisitor,
+};
+use crate::rules::{
+    Context, RuleConfiguration, RuleConfigurationError, RuleProperties, RulePropertyValue,
+};
+
+use super::FlawlessRule;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ContinueStrategy {
+    #[default]
+    Repeat,
+    Goto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoopKind {
+    NumericFor,
+    GenericFor,
+    While,
+    Repeat,
+}
 
-use super::{verify_no_rule_properties, FlawlessRule};
+impl LoopKind {
+    const ALL: [LoopKind; 4] = [
+        LoopKind::NumericFor,
+        LoopKind::GenericFor,
+        LoopKind::While,
+        LoopKind::Repeat,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            LoopKind::NumericFor => "numeric_for",
+            LoopKind::GenericFor => "generic_for",
+            LoopKind::While => "while",
+            LoopKind::Repeat => "repeat",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|kind| kind.as_str() == name).copied()
+    }
+}
 
 #[derive(Default)]
 struct Processor {
+    strategy: ContinueStrategy,
+    share_flag_variable: bool,
+    loop_kinds: Option<Vec<LoopKind>>,
     loop_stack: Vec<Option<LoopData>>,
     loop_identifier_count: u16,
+    // for each function (and the root block), the identifier id of its shared flag
+    shared_flag_scopes: Vec<Option<u16>>,
 }
 
 struct LoopData {
     has_continue_statement: bool,
+    has_break_statement: bool,
+    use_shared_flag: bool,
     loop_break_id: u16,
 }
 
 impl LoopData {
-    fn new(loop_break_id: u16) -> Self {
+    fn new(loop_break_id: u16, has_break_statement: bool, use_shared_flag: bool) -> Self {
         Self {
             has_continue_statement: false,
+            has_break_statement,
+            use_shared_flag,
             loop_break_id,
         }
     }
 
     fn get_identifier(&self) -> Identifier {
-        Identifier::new(format!("__DARKLUA_CONTINUE_{}", self.loop_break_id))
+        flag_identifier(self.loop_break_id)
+    }
+}
+
+fn flag_identifier(id: u16) -> Identifier {
+    Identifier::new(format!("__DARKLUA_CONTINUE_{}", id))
+}
+
+/// Places a statement created around a loop body on the line where the loop starts,
+/// so that generators retaining lines write it next to the original loop.
+fn place_on_line(statement: impl Into<Statement>, line: Option<usize>) -> Statement {
+    let statement = statement.into();
+    match line {
+        Some(line) => statement.with_line(line),
+        None => statement,
     }
 }
 
 impl Processor {
-    fn push_loop(&mut self) {
+    fn new(
+        strategy: ContinueStrategy,
+        share_flag_variable: bool,
+        loop_kinds: Option<Vec<LoopKind>>,
+    ) -> Self {
+        Self {
+            strategy,
+            share_flag_variable,
+            loop_kinds,
+            shared_flag_scopes: vec![None],
+            ..Default::default()
+        }
+    }
+
+    fn push_loop(&mut self, kind: LoopKind, block: &mut Block) {
+        if let Some(loop_kinds) = &self.loop_kinds {
+            if !loop_kinds.contains(&kind) {
+                // continue statements of this loop are left as is
+                self.loop_stack.push(None);
+                return;
+            }
+        }
+
+        remove_trailing_continue(block);
+
+        let has_break_statement = has_last_statement(block, |statement| {
+            matches!(statement, LastStatement::Break(_))
+        });
+
+        let use_shared_flag = self.share_flag_variable
+            && self.strategy == ContinueStrategy::Repeat
+            && has_break_statement
+            && has_last_statement(block, |statement| {
+                matches!(statement, LastStatement::Continue(_))
+            });
+
+        let loop_break_id = if use_shared_flag {
+            self.get_shared_flag_id()
+        } else {
+            self.next_identifier_id()
+        };
+
+        self.loop_stack.push(Some(LoopData::new(
+            loop_break_id,
+            has_break_statement,
+            use_shared_flag,
+        )));
+    }
+
+    fn next_identifier_id(&mut self) -> u16 {
         self.loop_identifier_count += 1;
-        self.loop_stack
-            .push(Some(LoopData::new(self.loop_identifier_count)));
+        self.loop_identifier_count
+    }
+
+    fn get_shared_flag_id(&mut self) -> u16 {
+        if let Some(Some(id)) = self.shared_flag_scopes.last() {
+            return *id;
+        }
+
+        let id = self.next_identifier_id();
+        if let Some(shared_flag) = self.shared_flag_scopes.last_mut() {
+            *shared_flag = Some(id);
+        }
+        id
     }
 
     fn push_no_loop(&mut self) {
         self.loop_stack.push(None);
+        self.shared_flag_scopes.push(None);
+    }
+
+    fn pop_no_loop(&mut self, function_block: &mut Block) {
+        self.loop_stack.pop();
+
+        if let Some(Some(id)) = self.shared_flag_scopes.pop() {
+            insert_shared_flag_declaration(function_block, id);
+        }
     }
 
-    fn wrap_loop_block_if_needed(&mut self, block: &mut Block) {
+    fn wrap_loop_block_if_needed(
+        &mut self,
+        block: &mut Block,
+        condition: Option<&mut Expression>,
+        line: Option<usize>,
+    ) {
         if let Some(loop_data) = self.loop_stack.pop().flatten() {
             if !loop_data.has_continue_statement {
                 return;
             }
+
+            if self.strategy == ContinueStrategy::Goto {
+                append_continue_label(block, condition, loop_data.get_identifier(), line);
+                return;
+            }
+
             let mut current_loop_block = mem::take(block);
 
+            let hoisted_variables = condition
+                .map(|condition| hoist_condition_locals(&mut current_loop_block, condition))
+                .unwrap_or_default();
+
+            let mut new_block = Block::default();
+
+            if !hoisted_variables.is_empty() {
+                new_block.push_statement(place_on_line(
+                    LocalAssignStatement::new(hoisted_variables, Vec::new()),
+                    line,
+                ));
+            }
+
+            if !loop_data.has_break_statement {
+                // without any break statement, the inner loop can only be exited by
+                // reaching its end or by a converted continue statement
+                new_block.push_statement(place_on_line(
+                    RepeatStatement::new(current_loop_block, true),
+                    line,
+                ));
+                *block = new_block;
+                return;
+            }
+
             if current_loop_block.get_last_statement().is_none() {
                 current_loop_block.push_statement(AssignStatement::from_variable(
                     loop_data.get_identifier(),
@@ -60,12 +233,24 @@ impl Processor {
                 ));
             }
 
-            let new_block = Block::default()
-                .with_statement(
+            if loop_data.use_shared_flag {
+                new_block.push_statement(place_on_line(
+                    AssignStatement::from_variable(loop_data.get_identifier(), false),
+                    line,
+                ));
+            } else {
+                new_block.push_statement(place_on_line(
                     LocalAssignStatement::from_variable(loop_data.get_identifier())
                         .with_value(false),
-                )
-                .with_statement(RepeatStatement::new(current_loop_block, true))
+                    line,
+                ));
+            }
+
+            let new_block = new_block
+                .with_statement(place_on_line(
+                    RepeatStatement::new(current_loop_block, true),
+                    line,
+                ))
                 .with_statement(IfStatement::create(
                     UnaryExpression::new(UnaryOperator::Not, loop_data.get_identifier()),
                     LastStatement::Break(None),
@@ -76,108 +261,521 @@ impl Processor {
     }
 }
 
+/// Removes `continue` statements that are the last statement executed by the loop body,
+/// since the loop would move to its next iteration anyway.
+fn remove_trailing_continue(block: &mut Block) {
+    trim_unreachable_statements(block);
+
+    match block.get_last_statement() {
+        Some(LastStatement::Continue(_)) => {
+            block.take_last_statement();
+        }
+        Some(_) => {}
+        None => match block.iter_mut_statements().last() {
+            Some(Statement::Do(do_statement)) => {
+                remove_trailing_continue(do_statement.mutate_block());
+            }
+            Some(Statement::If(if_statement)) => {
+                for block in if_statement.mutate_all_blocks() {
+                    remove_trailing_continue(block);
+                }
+            }
+            _ => {}
+        },
+    }
+}
+
+/// Removes the statements following a `do` block that always ends with a `continue`
+/// statement, since they can never be reached.
+fn trim_unreachable_statements(block: &mut Block) {
+    let continue_index = block
+        .iter_statements()
+        .position(|statement| match statement {
+            Statement::Do(do_statement) => ends_with_continue(do_statement.get_block()),
+            _ => false,
+        });
+
+    if let Some(index) = continue_index {
+        block.truncate(index + 1);
+        block.take_last_statement();
+    }
+}
+
+fn ends_with_continue(block: &Block) -> bool {
+    match block.get_last_statement() {
+        Some(LastStatement::Continue(_)) => true,
+        Some(_) => false,
+        None => match block.iter_statements().last() {
+            Some(Statement::Do(do_statement)) => ends_with_continue(do_statement.get_block()),
+            _ => false,
+        },
+    }
+}
+
+/// Returns true if the block contains a last statement matching the predicate that
+/// applies to the loop owning the block.
+fn has_last_statement(block: &Block, predicate: fn(&LastStatement) -> bool) -> bool {
+    block.get_last_statement().map(predicate).unwrap_or(false)
+        || block.iter_statements().any(|statement| match statement {
+            Statement::Do(do_statement) => has_last_statement(do_statement.get_block(), predicate),
+            Statement::If(if_statement) => {
+                if_statement
+                    .iter_branches()
+                    .any(|branch| has_last_statement(branch.get_block(), predicate))
+                    || if_statement
+                        .get_else_block()
+                        .map(|block| has_last_statement(block, predicate))
+                        .unwrap_or(false)
+            }
+            _ => false,
+        })
+}
+
+/// Creates the assignment to the flag variable that goes before a break statement. The
+/// comments above the break statement are moved before the assignment.
+fn flag_assignment(
+    mut flag: Identifier,
+    value: bool,
+    break_token: &mut Option<Token>,
+) -> AssignStatement {
+    if let Some(break_token) = break_token {
+        let mut flag_token = Token::from_content(flag.get_name().to_owned());
+        for trivia in break_token.take_leading_trivia() {
+            flag_token.push_leading_trivia(trivia);
+        }
+        flag.set_token(flag_token);
+    }
+
+    AssignStatement::from_variable(flag, value)
+}
+
+fn insert_shared_flag_declaration(block: &mut Block, id: u16) {
+    block.insert_statement(0, LocalAssignStatement::from_variable(flag_identifier(id)));
+}
+
+/// Appends the label targeted by the `goto` statements at the end of the loop block.
+/// When the block declares locals, it gets wrapped into a `do` block so that the
+/// `goto` statements do not jump into the scope of a local variable.
+fn append_continue_label(
+    block: &mut Block,
+    condition: Option<&mut Expression>,
+    label: Identifier,
+    line: Option<usize>,
+) {
+    let declares_locals = block.iter_statements().any(|statement| {
+        matches!(
+            statement,
+            Statement::LocalAssign(_) | Statement::LocalFunction(_)
+        )
+    });
+
+    if declares_locals {
+        let mut current_loop_block = mem::take(block);
+
+        let hoisted_variables = condition
+            .map(|condition| hoist_condition_locals(&mut current_loop_block, condition))
+            .unwrap_or_default();
+
+        if !hoisted_variables.is_empty() {
+            block.push_statement(place_on_line(
+                LocalAssignStatement::new(hoisted_variables, Vec::new()),
+                line,
+            ));
+        }
+
+        block.push_statement(place_on_line(DoStatement::new(current_loop_block), line));
+    } else if let Some(last_statement) = block.take_last_statement() {
+        block.push_statement(DoStatement::new(
+            Block::default().with_last_statement(last_statement),
+        ));
+    }
+
+    block.push_statement(LabelStatement::new(label));
+}
+
+/// The body of a repeat loop is moved into an inner block (a `repeat ... until true` loop
+/// or a `do` block), so locals declared at the top level of the body would not be visible
+/// anymore from the `until` condition. This function converts those local declarations
+/// into assignments and returns the variables that must be declared before the inner block.
+fn hoist_condition_locals(block: &mut Block, condition: &mut Expression) -> Vec<TypedIdentifier> {
+    let mut condition_variables = CollectVariables::default();
+    DefaultVisitor::visit_expression(condition, &mut condition_variables);
+
+    if condition_variables.names.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hoisted_names = HashSet::new();
+    let mut hoisted_variables = Vec::new();
+    let mut statements: Vec<&mut Statement> = block.iter_mut_statements().collect();
+
+    for index in 0..statements.len() {
+        let new_names: Vec<String> = match &*statements[index] {
+            Statement::LocalAssign(local_assign)
+                if local_assign
+                    .iter_variables()
+                    .any(|variable| condition_variables.names.contains(variable.get_name())) =>
+            {
+                local_assign
+                    .iter_variables()
+                    .map(|variable| variable.get_name().to_owned())
+                    .filter(|name| !hoisted_names.contains(name))
+                    .collect()
+            }
+            Statement::LocalFunction(local_function)
+                if condition_variables
+                    .names
+                    .contains(local_function.get_name()) =>
+            {
+                Some(local_function.get_name().to_owned())
+                    .filter(|name| !hoisted_names.contains(name))
+                    .into_iter()
+                    .collect()
+            }
+            _ => continue,
+        };
+
+        if !new_names.is_empty() {
+            let mut find_usage: FindVariables = new_names.iter().map(String::as_str).collect();
+
+            // if a variable is referenced before it is declared, it refers to another variable
+            // and hoisting the declaration would change what it points to
+            for (statement_index, statement) in statements[..=index].iter_mut().enumerate() {
+                match &mut **statement {
+                    Statement::LocalAssign(local_assign) if statement_index == index => {
+                        for value in local_assign.iter_mut_values() {
+                            DefaultVisitor::visit_expression(value, &mut find_usage);
+                        }
+                    }
+                    // a local function can refer to itself
+                    Statement::LocalFunction(_) if statement_index == index => {}
+                    _ => DefaultVisitor::visit_statement(statement, &mut find_usage),
+                }
+            }
+
+            if find_usage.has_found_usage() {
+                continue;
+            }
+        }
+
+        let local_assign = match mem::replace(
+            &mut *statements[index],
+            AssignStatement::new(Vec::new(), Vec::new()).into(),
+        ) {
+            Statement::LocalAssign(local_assign) => local_assign,
+            Statement::LocalFunction(local_function) => {
+                let (identifier, function) = into_function_expression(local_function);
+
+                if hoisted_names.insert(identifier.get_name().to_owned()) {
+                    hoisted_variables.push(TypedIdentifier::new(identifier.get_name()));
+                }
+
+                *statements[index] =
+                    AssignStatement::from_variable(Variable::Identifier(identifier), function)
+                        .into();
+                continue;
+            }
+            _ => unreachable!("statement was matched as a local declaration"),
+        };
+
+        let (variables, mut values) = local_assign.into_assignments();
+
+        if values.is_empty() {
+            values.push(Expression::nil());
+        }
+
+        let assign_variables = variables
+            .into_iter()
+            .map(|mut variable| {
+                let name = variable.get_name().to_owned();
+                let identifier = variable.get_identifier().clone();
+
+                if hoisted_names.insert(name.clone()) {
+                    let mut declaration = TypedIdentifier::new(name);
+                    if let Some(r#type) = variable.remove_type() {
+                        declaration = declaration.with_type(r#type);
+                    }
+                    hoisted_variables.push(declaration);
+                }
+
+                Variable::Identifier(identifier)
+            })
+            .collect();
+
+        *statements[index] = AssignStatement::new(assign_variables, values).into();
+    }
+
+    hoisted_variables
+}
+
+/// Converts a local function into a function expression, so that it can be assigned
+/// to a variable declared before it.
+fn into_function_expression(
+    mut local_function: LocalFunctionStatement,
+) -> (Identifier, FunctionExpression) {
+    let mut function = FunctionExpression::new(
+        mem::take(local_function.mutate_block()),
+        mem::take(local_function.mutate_parameters()),
+        local_function.is_variadic(),
+    );
+
+    if let Some(variadic_type) = local_function.get_variadic_type() {
+        function.set_variadic_type(variadic_type.clone());
+    }
+    if let Some(return_type) = local_function.remove_return_type() {
+        function.set_return_type(return_type);
+    }
+    if let Some(generic_parameters) = local_function.get_generic_parameters() {
+        function.set_generic_parameters(generic_parameters.clone());
+    }
+
+    (local_function.get_identifier().clone(), function)
+}
+
+#[derive(Default)]
+struct CollectVariables {
+    names: HashSet<String>,
+}
+
+impl NodeProcessor for CollectVariables {
+    fn process_variable_expression(&mut self, variable: &mut Identifier) {
+        if !self.names.contains(variable.get_name()) {
+            self.names.insert(variable.get_name().to_owned());
+        }
+    }
+}
+
 impl NodeProcessor for Processor {
-    fn process_generic_for_statement(&mut self, _: &mut GenericForStatement) {
-        self.push_loop();
+    fn process_generic_for_statement(&mut self, statement: &mut GenericForStatement) {
+        self.push_loop(LoopKind::GenericFor, statement.mutate_block());
     }
 
-    fn process_numeric_for_statement(&mut self, _: &mut NumericForStatement) {
-        self.push_loop();
+    fn process_numeric_for_statement(&mut self, statement: &mut NumericForStatement) {
+        self.push_loop(LoopKind::NumericFor, statement.mutate_block());
     }
 
-    fn process_repeat_statement(&mut self, _: &mut RepeatStatement) {
-        self.push_loop();
+    fn process_repeat_statement(&mut self, statement: &mut RepeatStatement) {
+        self.push_loop(LoopKind::Repeat, statement.mutate_block());
     }
 
-    fn process_while_statement(&mut self, _: &mut WhileStatement) {
-        self.push_loop();
+    fn process_while_statement(&mut self, statement: &mut WhileStatement) {
+        self.push_loop(LoopKind::While, statement.mutate_block());
     }
 
-    fn process_function_statement(&mut self, _: &mut crate::nodes::FunctionStatement) {
+    fn process_function_statement(&mut self, _: &mut FunctionStatement) {
         self.push_no_loop();
     }
 
-    fn process_function_expression(&mut self, _: &mut crate::nodes::FunctionExpression) {
+    fn process_function_expression(&mut self, _: &mut FunctionExpression) {
+        self.push_no_loop();
+    }
+
+    fn process_local_function_statement(&mut self, _: &mut LocalFunctionStatement) {
         self.push_no_loop();
     }
 
     fn process_block(&mut self, block: &mut Block) {
-        let new_statement =
-            block
-                .mutate_last_statement()
-                .and_then(|last_statement| match last_statement {
-                    LastStatement::Continue(continue_token) => {
-                        if let Some(Some(loop_data)) = self.loop_stack.last_mut() {
-                            if !loop_data.has_continue_statement {
-                                loop_data.has_continue_statement = true;
-                            }
+        if matches!(self.loop_stack.last(), Some(Some(_))) {
+            trim_unreachable_statements(block);
+        }
 
-                            *last_statement = LastStatement::Break(continue_token.take().map(
-                                |mut continue_token| {
-                                    continue_token.replace_with_content("break");
-                                    continue_token
-                                },
-                            ));
-
-                            Some(AssignStatement::from_variable(
-                                loop_data.get_identifier(),
-                                true,
-                            ))
-                        } else {
-                            None
-                        }
+        let continue_token = match block.mutate_last_statement() {
+            Some(LastStatement::Continue(continue_token)) => continue_token,
+            Some(LastStatement::Break(break_token)) => {
+                if let Some(Some(loop_data)) = self.loop_stack.last() {
+                    if loop_data.use_shared_flag {
+                        let assign =
+                            flag_assignment(loop_data.get_identifier(), false, break_token);
+                        block.push_statement(assign);
                     }
-                    _ => None,
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        let Some(Some(loop_data)) = self.loop_stack.last_mut() else {
+            return;
+        };
+
+        loop_data.has_continue_statement = true;
+        let mut continue_token = continue_token.take();
+
+        match self.strategy {
+            ContinueStrategy::Repeat => {
+                let assign = loop_data.has_break_statement.then(|| {
+                    flag_assignment(loop_data.get_identifier(), true, &mut continue_token)
                 });
 
-        if let Some(statement) = new_statement {
-            block.push_statement(statement);
+                block.set_last_statement(LastStatement::Break(continue_token.map(
+                    |mut continue_token| {
+                        continue_token.replace_with_content("break");
+                        continue_token
+                    },
+                )));
+
+                if let Some(assign) = assign {
+                    block.push_statement(assign);
+                }
+            }
+            ContinueStrategy::Goto => {
+                block.take_last_statement();
+
+                let label = loop_data.get_identifier();
+
+                let goto = if let Some(continue_token) = continue_token {
+                    // keep the comments of the continue token around the new goto statement
+                    let mut goto_token = Token::from_content("goto");
+                    for trivia in continue_token.iter_leading_trivia() {
+                        goto_token.push_leading_trivia(trivia.clone());
+                    }
+                    let mut label_token = Token::from_content(label.get_name().to_owned());
+                    for trivia in continue_token.iter_trailing_trivia() {
+                        label_token.push_trailing_trivia(trivia.clone());
+                    }
+
+                    GotoStatement::new(label.with_token(label_token))
+                        .with_tokens(GotoTokens { goto: goto_token })
+                } else {
+                    GotoStatement::new(label)
+                };
+
+                block.push_statement(goto);
+            }
         }
     }
 }
 
 impl NodePostProcessor for Processor {
     fn process_after_generic_for_statement(&mut self, statement: &mut GenericForStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        let line = statement
+            .get_tokens()
+            .and_then(|tokens| tokens.r#for.get_line_number());
+        self.wrap_loop_block_if_needed(statement.mutate_block(), None, line);
     }
 
     fn process_after_numeric_for_statement(&mut self, statement: &mut NumericForStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        let line = statement
+            .get_tokens()
+            .and_then(|tokens| tokens.r#for.get_line_number());
+        self.wrap_loop_block_if_needed(statement.mutate_block(), None, line);
     }
 
     fn process_after_repeat_statement(&mut self, statement: &mut RepeatStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        let line = statement
+            .get_tokens()
+            .and_then(|tokens| tokens.repeat.get_line_number());
+        let (block, condition) = statement.mutate_block_and_condition();
+        self.wrap_loop_block_if_needed(block, Some(condition), line);
     }
 
     fn process_after_while_statement(&mut self, statement: &mut WhileStatement) {
-        self.wrap_loop_block_if_needed(statement.mutate_block());
+        let line = statement
+            .get_tokens()
+            .and_then(|tokens| tokens.r#while.get_line_number());
+        self.wrap_loop_block_if_needed(statement.mutate_block(), None, line);
     }
 
-    fn process_after_function_statement(&mut self, _: &mut crate::nodes::FunctionStatement) {
-        self.loop_stack.pop();
+    fn process_after_function_statement(&mut self, statement: &mut FunctionStatement) {
+        self.pop_no_loop(statement.mutate_block());
     }
 
-    fn process_after_function_expression(&mut self, _: &mut crate::nodes::FunctionExpression) {
-        self.loop_stack.pop();
+    fn process_after_function_expression(&mut self, function: &mut FunctionExpression) {
+        self.pop_no_loop(function.mutate_block());
+    }
+
+    fn process_after_local_function_statement(&mut self, statement: &mut LocalFunctionStatement) {
+        self.pop_no_loop(statement.mutate_block());
     }
 }
 
 pub const REMOVE_CONTINUE_RULE_NAME: &str = "remove_continue";
 
-/// A rule that removes continue statements and converts them into break statements.
+/// A rule that removes continue statements and converts them into break statements
+/// (or into `goto` statements when using the `goto` strategy).
 #[derive(Debug, Default, PartialEq, Eq)]
-pub struct RemoveContinue {}
+pub struct RemoveContinue {
+    strategy: ContinueStrategy,
+    share_flag_variable: bool,
+    loop_kinds: Option<Vec<LoopKind>>,
+}
 
 impl FlawlessRule for RemoveContinue {
     fn flawless_process(&self, block: &mut Block, _: &Context) {
-        let mut processor = Processor::default();
+        let mut processor = Processor::new(
+            self.strategy,
+            self.share_flag_variable,
+            self.loop_kinds.clone(),
+        );
         DefaultPostVisitor::visit_block(block, &mut processor);
+
+        if let Some(Some(id)) = processor.shared_flag_scopes.pop() {
+            insert_shared_flag_declaration(block, id);
+        }
     }
 }
 
 impl RuleConfiguration for RemoveContinue {
     fn configure(&mut self, properties: RuleProperties) -> Result<(), RuleConfigurationError> {
-        verify_no_rule_properties(&properties)?;
+        for (key, value) in properties {
+            match key.as_str() {
+                "strategy" => {
+                    self.strategy = match value.expect_string(&key)?.as_str() {
+                        "repeat" => ContinueStrategy::Repeat,
+                        "goto" => ContinueStrategy::Goto,
+                        unexpected => {
+                            return Err(RuleConfigurationError::UnexpectedValue {
+                                property: "strategy".to_owned(),
+                                message: format!(
+                                    "invalid value `{}` (must be `repeat` or `goto`)",
+                                    unexpected
+                                ),
+                            })
+                        }
+                    };
+                }
+                "share_flag_variable" => {
+                    self.share_flag_variable = value.expect_bool(&key)?;
+                }
+                "loop_kinds" => {
+                    let names = value.expect_string_list(&key)?;
+
+                    // an empty list would leave every loop unprocessed, which disables the rule
+                    if names.is_empty() {
+                        return Err(RuleConfigurationError::UnexpectedValue {
+                            property: "loop_kinds".to_owned(),
+                            message: "expected at least one loop kind".to_owned(),
+                        });
+                    }
+
+                    let mut loop_kinds = Vec::new();
+
+                    for name in names {
+                        let kind = LoopKind::from_name(&name).ok_or_else(|| {
+                            RuleConfigurationError::UnexpectedValue {
+                                property: "loop_kinds".to_owned(),
+                                message: format!(
+                                    "invalid loop kind `{}` (must be `numeric_for`, `generic_for`, `while` or `repeat`)",
+                                    name
+                                ),
+                            }
+                        })?;
+
+                        if !loop_kinds.contains(&kind) {
+                            loop_kinds.push(kind);
+                        }
+                    }
+
+                    self.loop_kinds = if LoopKind::ALL.iter().all(|kind| loop_kinds.contains(kind))
+                    {
+                        None
+                    } else {
+                        Some(loop_kinds)
+                    };
+                }
+                _ => return Err(RuleConfigurationError::UnexpectedProperty(key)),
+            }
+        }
 
         Ok(())
     }
@@ -187,7 +785,33 @@ impl RuleConfiguration for RemoveContinue {
     }
 
     fn serialize_to_properties(&self) -> RuleProperties {
-        RuleProperties::new()
+        let mut properties = RuleProperties::new();
+
+        match self.strategy {
+            ContinueStrategy::Repeat => {}
+            ContinueStrategy::Goto => {
+                properties.insert("strategy".to_owned(), "goto".into());
+            }
+        }
+
+        if self.share_flag_variable {
+            properties.insert("share_flag_variable".to_owned(), true.into());
+        }
+
+        if let Some(loop_kinds) = &self.loop_kinds {
+            properties.insert(
+                "loop_kinds".to_owned(),
+                RulePropertyValue::StringList(
+                    LoopKind::ALL
+                        .iter()
+                        .filter(|kind| loop_kinds.contains(kind))
+                        .map(|kind| kind.as_str().to_owned())
+                        .collect(),
+                ),
+            );
+        }
+
+        properties
     }
 }
 
@@ -209,6 +833,91 @@ mod test {
         assert_json_snapshot!("default_remove_continue", rule);
     }
 
+    #[test]
+    fn serialize_rule_with_goto_strategy() {
+        let rule: Box<dyn Rule> = Box::new(RemoveContinue {
+            strategy: ContinueStrategy::Goto,
+            ..Default::default()
+        });
+
+        assert_json_snapshot!("remove_continue_goto_strategy", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_shared_flag_variable() {
+        let rule: Box<dyn Rule> = Box::new(RemoveContinue {
+            share_flag_variable: true,
+            ..Default::default()
+        });
+
+        assert_json_snapshot!("remove_continue_share_flag_variable", rule);
+    }
+
+    #[test]
+    fn serialize_rule_with_loop_kinds() {
+        let rule: Box<dyn Rule> = Box::new(RemoveContinue {
+            loop_kinds: Some(vec![LoopKind::While, LoopKind::GenericFor]),
+            ..Default::default()
+        });
+
+        assert_json_snapshot!("remove_continue_loop_kinds", rule);
+    }
+
+    #[test]
+    fn configure_with_all_loop_kinds_is_default() {
+        let rule: Box<dyn Rule> = json5::from_str(
+            r#"{
+            rule: 'remove_continue',
+            loop_kinds: ['repeat', 'while', 'generic_for', 'numeric_for'],
+        }"#,
+        )
+        .unwrap();
+
+        pretty_assertions::assert_eq!(rule.serialize_to_properties(), RuleProperties::new());
+    }
+
+    #[test]
+    fn configure_with_invalid_loop_kind_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_continue',
+            loop_kinds: ['while', 'do'],
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'loop_kinds': invalid loop kind `do` (must be `numeric_for`, `generic_for`, `while` or `repeat`)"
+        );
+    }
+
+    #[test]
+    fn configure_with_empty_loop_kinds_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_continue',
+            loop_kinds: [],
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'loop_kinds': expected at least one loop kind"
+        );
+    }
+
+    #[test]
+    fn configure_with_invalid_strategy_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'remove_continue',
+            strategy: 'label',
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'strategy': invalid value `label` (must be `repeat` or `goto`)"
+        );
+    }
+
     #[test]
     fn configure_with_extra_field_error() {
         let result = json5::from_str::<Box<dyn Rule>>(
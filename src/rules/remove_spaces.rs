@@ -39,10 +39,18 @@ impl NodeProcessor for RemoveWhitespacesProcessor {
         generic_for.clear_whitespaces();
     }
 
+    fn process_goto_statement(&mut self, statement: &mut GotoStatement) {
+        statement.clear_whitespaces();
+    }
+
     fn process_if_statement(&mut self, if_statement: &mut IfStatement) {
         if_statement.clear_whitespaces();
     }
 
+    fn process_label_statement(&mut self, statement: &mut LabelStatement) {
+        statement.clear_whitespaces();
+    }
+
     fn process_last_statement(&mut self, statement: &mut LastStatement) {
         match statement {
             LastStatement::Break(token) | LastStatement::Continue(token) => {
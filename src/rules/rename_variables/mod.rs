@@ -4,8 +4,7 @@ mod rename_processor;
 
 use rename_processor::RenameProcessor;
 
-use crate::nodes::Block;
-use crate::process::utils::is_valid_identifier;
+use crate::nodes::{Block, Identifier};
 use crate::process::{DefaultVisitor, NodeVisitor, ScopeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
@@ -46,10 +45,15 @@ impl RenameVariables {
                 "$roblox" => self
                     .globals
                     .extend(globals::ROBLOX.iter().map(ToString::to_string)),
-                identifier if !is_valid_identifier(identifier) => {
-                    return Err(RuleConfigurationError::StringExpected("".to_owned()))
+                identifier => {
+                    Identifier::try_new(identifier).map_err(|error| {
+                        RuleConfigurationError::UnexpectedValue {
+                            property: "globals".to_owned(),
+                            message: error.to_string(),
+                        }
+                    })?;
+                    self.globals.push(value);
                 }
-                _ => self.globals.push(value),
             }
         }
 
@@ -209,4 +213,18 @@ mod test {
 
         assert_json_snapshot!("roblox_globals_rename_variables", rule as Box<dyn Rule>);
     }
+
+    #[test]
+    fn configure_with_invalid_global_error() {
+        let result = json5::from_str::<Box<dyn Rule>>(
+            r#"{
+            rule: 'rename_variables',
+            globals: ['$default', 'foo-bar'],
+        }"#,
+        );
+        pretty_assertions::assert_eq!(
+            result.unwrap_err().to_string(),
+            "unexpected value for field 'globals': invalid identifier `foo-bar`: unexpected character `-` at 3"
+        );
+    }
 }
@@ -24,12 +24,12 @@ pub(crate) fn is_require_call(call: &FunctionCall, identifier_tracker: &Identifi
 
 pub(crate) fn match_path_require_call(call: &FunctionCall) -> Option<PathBuf> {
     match call.get_arguments() {
-        Arguments::String(string) => Some(string.get_value()),
+        Arguments::String(string) => string.get_string_value(),
         Arguments::Tuple(tuple) if tuple.len() == 1 => {
             let expression = tuple.iter_values().next().unwrap();
 
             match expression {
-                Expression::String(string) => Some(string.get_value()),
+                Expression::String(string) => string.get_string_value(),
                 _ => None,
             }
         }
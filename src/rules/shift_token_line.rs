@@ -47,10 +47,18 @@ impl NodeProcessor for ShiftTokenLineProcessor {
         generic_for.shift_token_line(self.shift_amount);
     }
 
+    fn process_goto_statement(&mut self, statement: &mut GotoStatement) {
+        statement.shift_token_line(self.shift_amount);
+    }
+
     fn process_if_statement(&mut self, if_statement: &mut IfStatement) {
         if_statement.shift_token_line(self.shift_amount);
     }
 
+    fn process_label_statement(&mut self, statement: &mut LabelStatement) {
+        statement.shift_token_line(self.shift_amount);
+    }
+
     fn process_last_statement(&mut self, statement: &mut LastStatement) {
         match statement {
             LastStatement::Break(token) | LastStatement::Continue(token) => {
@@ -1,5 +1,5 @@
 use crate::nodes::{Block, Statement};
-use crate::process::{DefaultVisitor, Evaluator, NodeProcessor, NodeVisitor};
+use crate::process::{DefaultVisitor, NodeProcessor, NodeVisitor};
 use crate::rules::{
     Context, FlawlessRule, RuleConfiguration, RuleConfigurationError, RuleProperties,
 };
@@ -7,9 +7,7 @@ use crate::rules::{
 use super::verify_no_rule_properties;
 
 #[derive(Debug, Clone, Default)]
-struct WhileFilter {
-    evaluator: Evaluator,
-}
+struct WhileFilter;
 
 impl NodeProcessor for WhileFilter {
     fn process_block(&mut self, block: &mut Block) {
@@ -17,12 +15,7 @@ impl NodeProcessor for WhileFilter {
             Statement::While(while_statement) => {
                 let condition = while_statement.get_condition();
 
-                self.evaluator.has_side_effects(condition)
-                    || self
-                        .evaluator
-                        .evaluate(condition)
-                        .is_truthy()
-                        .unwrap_or(true)
+                condition.has_side_effects() || condition.truthiness().unwrap_or(true)
             }
             _ => true,
         });
@@ -37,7 +30,7 @@ pub struct RemoveUnusedWhile {}
 
 impl FlawlessRule for RemoveUnusedWhile {
     fn flawless_process(&self, block: &mut Block, _: &Context) {
-        let mut processor = WhileFilter::default();
+        let mut processor = WhileFilter;
         DefaultVisitor::visit_block(block, &mut processor);
     }
 }
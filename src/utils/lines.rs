@@ -63,7 +63,9 @@ fn last_statement_token(statement: &Statement) -> Option<&Token> {
         Statement::CompoundAssign(assign) => last_expression_token(assign.get_value()),
         Statement::Function(function) => function.get_tokens().map(|tokens| &tokens.end),
         Statement::GenericFor(generic_for) => generic_for.get_tokens().map(|tokens| &tokens.end),
+        Statement::Goto(goto) => goto.get_label().get_token(),
         Statement::If(if_statement) => if_statement.get_tokens().map(|tokens| &tokens.end),
+        Statement::Label(label) => label.get_tokens().map(|tokens| &tokens.right_colons),
         Statement::LocalAssign(local_assign) => local_assign
             .iter_values()
             .last()
@@ -185,7 +187,9 @@ fn first_statement_token(statement: &Statement) -> Option<&Token> {
         Statement::CompoundAssign(assign) => first_variable_token(assign.get_variable()),
         Statement::Function(function) => function.get_tokens().map(|tokens| &tokens.function),
         Statement::GenericFor(generic_for) => generic_for.get_tokens().map(|tokens| &tokens.r#for),
+        Statement::Goto(goto) => goto.get_tokens().map(|tokens| &tokens.goto),
         Statement::If(if_statement) => if_statement.get_tokens().map(|tokens| &tokens.r#if),
+        Statement::Label(label) => label.get_tokens().map(|tokens| &tokens.left_colons),
         Statement::LocalAssign(local_assign) => {
             local_assign.get_tokens().map(|tokens| &tokens.local)
         }
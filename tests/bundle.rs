@@ -32,7 +32,7 @@ fn skip_require_call_without_a_string() {
         ".darklua.json" => DARKLUA_BUNDLE_ONLY_RETAIN_LINES_CONFIG,
     );
 
-    process_main_unchanged(&resources, "local library = require( {} )");
+    process_main_unchanged(&resources, "local library = require( {} )\n");
 }
 
 #[test]
@@ -43,7 +43,7 @@ fn skip_require_call_with_method() {
 
     process_main_unchanged(
         &resources,
-        "local library = require:method('./library.luau')",
+        "local library = require:method('./library.luau')\n",
     );
 }
 
@@ -55,7 +55,7 @@ fn skip_require_call_with_2_arguments() {
 
     process_main_unchanged(
         &resources,
-        "local library = require('./example', 'argument')",
+        "local library = require('./example', 'argument')\n",
     );
 }
 
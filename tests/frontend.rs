@@ -7,7 +7,7 @@ use pretty_assertions::assert_eq;
 use utils::memory_resources;
 
 const ANY_CODE: &str = "do end return true";
-const ANY_CODE_DEFAULT_PROCESS: &str = "return true";
+const ANY_CODE_DEFAULT_PROCESS: &str = "return true\n";
 
 #[test]
 fn apply_default_config_in_place() {
@@ -66,7 +66,7 @@ fn apply_default_config_to_output_from_file_in_directory() {
 
 #[test]
 fn apply_default_config_to_output_with_nested_content() {
-    let init_lua = "return{}";
+    let init_lua = "return{}\n";
     let resources = memory_resources!(
         "src/test.lua" => ANY_CODE,
         "src/impl/init.lua" => init_lua,
@@ -154,7 +154,7 @@ fn use_default_json_config_in_place() {
         .result()
         .unwrap();
 
-    assert_eq!(resources.get("src/test.lua").unwrap(), "return 1");
+    assert_eq!(resources.get("src/test.lua").unwrap(), "return 1\n");
 }
 
 #[test]
@@ -169,7 +169,199 @@ fn use_default_json5_config_in_place() {
         .result()
         .unwrap();
 
-    assert_eq!(resources.get("src/test.lua").unwrap(), "return 'Hello'");
+    assert_eq!(resources.get("src/test.lua").unwrap(), "return 'Hello'\n");
+}
+
+#[test]
+fn retain_lines_preserves_original_string_literals() {
+    let code = "local a = [[\n  long\n  string]]\nlocal b = \"\\65\"\nreturn a, b, '\\x41'\n";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+        ".darklua.json5" => "{ rules: [], generator: 'retain_lines' }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), code);
+}
+
+#[test]
+fn readable_generator_with_keep_comments_preserves_header() {
+    let resources = memory_resources!(
+        "src/test.lua" => "--!strict\n-- header\nlocal   value=1 -- one\nreturn value",
+        ".darklua.json5" => "{ rules: ['remove_spaces'], generator: { name: 'readable', keep_comments: true } }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(
+        resources.get("src/test.lua").unwrap(),
+        "--!strict\n-- header\nlocal value = 1 -- one\n\nreturn value\n"
+    );
+}
+
+#[test]
+fn dense_generator_without_types() {
+    let resources = memory_resources!(
+        "src/test.lua" => "type T = number\nlocal value: T = 1 :: any\nreturn value",
+        ".darklua.json5" => "{ rules: [], generator: { name: 'dense', include_types: false } }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(
+        resources.get("src/test.lua").unwrap(),
+        "local value=1 return value\n"
+    );
+}
+
+#[test]
+fn dense_generator_with_long_string_threshold_never() {
+    let resources = memory_resources!(
+        "src/test.lua" => "return [[\nooof\nooof\nooof\nooof\nooof\nooof\nooof\nooof]]",
+        ".darklua.json5" => "{ rules: [], generator: { name: 'dense', long_string_threshold: 'never' } }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(
+        resources.get("src/test.lua").unwrap(),
+        "return'ooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof\\nooof'\n"
+    );
+}
+
+#[test]
+fn retain_lines_preserves_number_literals() {
+    let code = "return 0xFF, 0b1010, 1_000_000, 0.1e-3\n";
+    let resources = memory_resources!(
+        "src/test.lua" => code,
+        ".darklua.json5" => "{ rules: [], generator: 'retain_lines' }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), code);
+}
+
+#[test]
+fn dense_generator_with_prefer_hex_above_writes_folded_mask_in_hex() {
+    let resources = memory_resources!(
+        "src/test.lua" => "return 0xF0 + 0x0F, 3 + 4",
+        ".darklua.json5" => "{ rules: ['compute_expression'], generator: { name: 'dense', prefer_hex_above: 15 } }",
+    );
+
+    process(&resources, Options::new("src"))
+        .unwrap()
+        .result()
+        .unwrap();
+
+    assert_eq!(resources.get("src/test.lua").unwrap(), "return 0xff,7\n");
+}
+
+mod output_formatting {
+    use super::*;
+
+    fn process_with_config(code: &'static str, config: &'static str) -> String {
+        let resources = memory_resources!(
+            "src/test.lua" => code,
+            ".darklua.json5" => config,
+        );
+
+        process(&resources, Options::new("src"))
+            .unwrap()
+            .result()
+            .unwrap();
+
+        resources.get("src/test.lua").unwrap()
+    }
+
+    macro_rules! test_output_bytes {
+        ($($name:ident($code:literal, $config:literal) => $expected:literal),* $(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    pretty_assertions::assert_eq!(
+                        process_with_config($code, $config).as_bytes(),
+                        $expected.as_bytes()
+                    );
+                }
+            )*
+        };
+    }
+
+    test_output_bytes!(
+        default_adds_final_newline(
+            "local a = 1\nreturn a",
+            "{ rules: [] }"
+        ) => "local a = 1\nreturn a\n",
+        default_keeps_existing_final_newline(
+            "local a = 1\nreturn a\n",
+            "{ rules: [] }"
+        ) => "local a = 1\nreturn a\n",
+        default_adds_crlf_final_newline(
+            "local a = 1\r\nreturn a",
+            "{ rules: [], final_newline: true }"
+        ) => "local a = 1\r\nreturn a\r\n",
+        default_adds_crlf_final_newline_to_single_line(
+            "local a = 1\r\nreturn a",
+            "{ rules: [], generator: 'dense', final_newline: true }"
+        ) => "local a=1 return a\r\n",
+        default_keeps_crlf_after_shebang(
+            "#!/usr/bin/env lua\r\nlocal a = 1\r\nreturn a",
+            "{ rules: [] }"
+        ) => "#!/usr/bin/env lua\r\nlocal a = 1\r\nreturn a\r\n",
+        default_keeps_crlf_from_retain_lines(
+            "local a = 1\r\nreturn a\r\n",
+            "{ rules: [] }"
+        ) => "local a = 1\r\nreturn a\r\n",
+        without_final_newline(
+            "local a = 1\nreturn a",
+            "{ rules: [], final_newline: false }"
+        ) => "local a = 1\nreturn a",
+        lf_with_final_newline(
+            "local a = 1\r\nreturn a",
+            "{ rules: [], line_endings: 'lf' }"
+        ) => "local a = 1\nreturn a\n",
+        lf_without_final_newline(
+            "local a = 1\r\nreturn a",
+            "{ rules: [], line_endings: 'lf', final_newline: false }"
+        ) => "local a = 1\nreturn a",
+        crlf_with_final_newline(
+            "local a = 1\nreturn a",
+            "{ rules: [], line_endings: 'crlf' }"
+        ) => "local a = 1\r\nreturn a\r\n",
+        crlf_without_final_newline(
+            "local a = 1\nreturn a",
+            "{ rules: [], line_endings: 'crlf', final_newline: false }"
+        ) => "local a = 1\r\nreturn a",
+        crlf_keeps_crlf_from_retain_lines(
+            "local a = 1\r\nreturn a\r\n",
+            "{ rules: [], line_endings: 'crlf' }"
+        ) => "local a = 1\r\nreturn a\r\n",
+        crlf_with_readable_generator(
+            "local a = 1 return a",
+            "{ rules: [], generator: 'readable', line_endings: 'crlf' }"
+        ) => "local a = 1\r\n\r\nreturn a\r\n",
+        dense_without_final_newline(
+            "local a = 1\nreturn a",
+            "{ rules: [], generator: 'dense', final_newline: false }"
+        ) => "local a=1 return a",
+    );
 }
 
 mod errors {
@@ -274,3 +466,64 @@ mod errors {
         );
     }
 }
+
+mod generator_builder {
+    use darklua_core::{
+        generator::{GeneratorBuilder, Indentation, QuoteStyle, SemicolonStyle},
+        Configuration,
+    };
+
+    use super::*;
+
+    const CODE: &str = "local function greet(name: string)\n    print(\"hello \" .. name)\nend\ngreet(\"world\")\n";
+
+    #[test]
+    fn process_with_configured_readable_generator() {
+        let resources = memory_resources!(
+            "src/test.lua" => CODE,
+        );
+
+        let generator = GeneratorBuilder::readable()
+            .indent(Indentation::spaces(2))
+            .max_width(100)
+            .quote_style(QuoteStyle::Double)
+            .include_types(false);
+
+        process(
+            &resources,
+            Options::new("src")
+                .with_configuration(Configuration::empty().with_generator(generator)),
+        )
+        .unwrap()
+        .result()
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            resources.get("src/test.lua").unwrap(),
+            "local function greet(name)\n  print(\"hello \" .. name)\nend\n\ngreet(\"world\")\n"
+        );
+    }
+
+    #[test]
+    fn generate_block_with_configured_dense_generator() {
+        let generator = GeneratorBuilder::dense()
+            .semicolons(SemicolonStyle::Always)
+            .build();
+
+        let block = generator.build_parser().parse(CODE).unwrap();
+
+        pretty_assertions::assert_eq!(
+            generator.generate_lua(&block, CODE),
+            "local function greet(name:string)print('hello '..name);end;greet('world');"
+        );
+    }
+
+    #[test]
+    fn generate_block_with_retain_lines_generator() {
+        let generator = GeneratorBuilder::retain_lines().build();
+
+        let block = generator.build_parser().parse(CODE).unwrap();
+
+        pretty_assertions::assert_eq!(generator.generate_lua(&block, CODE), CODE);
+    }
+}
@@ -172,7 +172,7 @@ fn convert_sibling_module_from_init_module() {
     expect_file_process(
         &resources,
         "src/init.lua",
-        "local value = require(script:FindFirstChild('value'))",
+        "local value = require(script:FindFirstChild('value'))\n",
     );
 }
 
@@ -186,7 +186,7 @@ fn convert_sibling_init_module_from_init_module() {
     expect_file_process(
         &resources,
         "src/init.lua",
-        "local value = require(script:FindFirstChild('folder'))",
+        "local value = require(script:FindFirstChild('folder'))\n",
     );
 }
 
@@ -200,7 +200,7 @@ fn convert_parent_init_module_from_init_module() {
     expect_file_process(
         &resources,
         "src/module/init.lua",
-        "local value = require(script.Parent)",
+        "local value = require(script.Parent)\n",
     );
 }
 
@@ -218,7 +218,7 @@ mod luaurc {
         expect_file_process(
             &resources,
             "src/init.lua",
-            "local value = require(script:FindFirstChild('value'))",
+            "local value = require(script:FindFirstChild('value'))\n",
         );
     }
 
@@ -233,7 +233,7 @@ mod luaurc {
         expect_file_process(
             &resources,
             "src/init.lua",
-            "local value = require(script:FindFirstChild('value'):FindFirstChild('default'))",
+            "local value = require(script:FindFirstChild('value'):FindFirstChild('default'))\n",
         );
     }
 
@@ -248,7 +248,7 @@ mod luaurc {
         expect_file_process(
             &resources,
             "src/init.lua",
-            "local value = require(script:FindFirstChild('value'):FindFirstChild('default'))",
+            "local value = require(script:FindFirstChild('value'):FindFirstChild('default'))\n",
         );
     }
 }
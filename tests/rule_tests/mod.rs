@@ -43,10 +43,18 @@ macro_rules! test_rule_with_generator {
                 .process(&mut block, &context)
                 .expect("rule should succeed");
 
-            let create_generator = $generator;
-            let mut generator = create_generator($input);
-            generator.write_block(&block);
-            let lua_code = generator.into_string();
+            let lua_code = darklua_core::generator::check_idempotence(
+                &parser,
+                &block,
+                $input,
+                |block, code| {
+                    let create_generator = $generator;
+                    let mut generator = create_generator(code);
+                    generator.write_block(block);
+                    generator.into_string()
+                },
+            )
+            .unwrap_or_else(|error| panic!("{}", error));
 
             if $compare_with_tokens {
                 pretty_assertions::assert_eq!($output, lua_code,);
@@ -83,6 +91,7 @@ macro_rules! snapshot_rule_with_generator {
         $generator:expr,
         $parser:expr,
         $compare_with_tokens:expr,
+        $idempotence_parser:expr,
         $test_file_name:literal,
         $name:ident,
         $input:literal
@@ -107,10 +116,24 @@ macro_rules! snapshot_rule_with_generator {
                 .process(&mut block, &context)
                 .expect("rule should succeed");
 
-            let create_generator = $generator;
-            let mut generator = create_generator($input);
-            generator.write_block(&block);
-            let lua_code = generator.into_string();
+            let generate = |block: &darklua_core::nodes::Block, code: &str| {
+                let create_generator = $generator;
+                let mut generator = create_generator(code);
+                generator.write_block(block);
+                generator.into_string()
+            };
+
+            let idempotence_parser: Option<darklua_core::Parser> = $idempotence_parser;
+            let lua_code = match idempotence_parser {
+                Some(idempotence_parser) => darklua_core::generator::check_idempotence(
+                    &idempotence_parser,
+                    &block,
+                    $input,
+                    generate,
+                )
+                .unwrap_or_else(|error| panic!("{}", error)),
+                None => generate(&block, $input),
+            };
 
             insta::assert_snapshot!(stringify!($name), lua_code);
         }
@@ -122,6 +145,7 @@ macro_rules! snapshot_rule_with_generator {
             $generator,
             darklua_core::Parser::default(),
             false,
+            Some(darklua_core::Parser::default()),
             $test_file_name,
             $name,
             $input
@@ -349,6 +373,7 @@ macro_rules! test_rule_snapshot {
         $rule:expr,
         resources = $resources:expr,
         test_file_name = $test_file_name:literal,
+        idempotence_parser = $idempotence_parser:expr,
         $($name:ident ($input:literal)),* $(,)?
     ) => {
         paste::paste! {
@@ -363,6 +388,7 @@ macro_rules! test_rule_snapshot {
                 |input| darklua_core::generator::TokenBasedLuaGenerator::new(input),
                 darklua_core::Parser::default().preserve_tokens(),
                 false,
+                $idempotence_parser,
                 $test_file_name,
                 $name,
                 $input
@@ -374,6 +400,23 @@ macro_rules! test_rule_snapshot {
 
     };
 
+    (
+        $rule_name:ident,
+        $rule:expr,
+        resources = $resources:expr,
+        test_file_name = $test_file_name:literal,
+        $($name:ident ($input:literal)),* $(,)?
+    ) => {
+        test_rule_snapshot!(
+            $rule_name,
+            $rule,
+            resources = $resources,
+            test_file_name = $test_file_name,
+            idempotence_parser = Some(darklua_core::Parser::default().preserve_tokens()),
+            $( $name ($input), )*
+        );
+    };
+
     (
         $rule_name:ident,
         $rule:expr,
@@ -404,6 +447,22 @@ macro_rules! test_rule_snapshot {
         );
     };
 
+    (
+        $rule_name:ident,
+        $rule:expr,
+        idempotence_parser = $idempotence_parser:expr,
+        $($name:ident ($input:literal)),* $(,)?
+    ) => {
+        test_rule_snapshot!(
+            $rule_name,
+            $rule,
+            resources = darklua_core::Resources::from_memory(),
+            test_file_name = "src/test.lua",
+            idempotence_parser = $idempotence_parser,
+            $( $name ($input), )*
+        );
+    };
+
     ($rule_name:ident, $rule:expr, $($name:ident ($input:literal)),* $(,)?) => {
         test_rule_snapshot!(
             $rule_name,
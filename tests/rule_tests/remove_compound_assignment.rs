@@ -1,4 +1,4 @@
-use darklua_core::rules::{RemoveCompoundAssignment, Rule};
+use darklua_core::rules::{RemoveCompoundAssignment, RemoveSpaces, Rule};
 
 test_rule!(
     remove_compound_assignment,
@@ -44,6 +44,35 @@ test_rule_with_tokens!(
     comment_after_variable("i --[[ comment ]] += 1") => "i --[[ comment ]] =i+ 1",
 );
 
+#[test]
+fn do_block_is_placed_on_the_compound_assignment_line() {
+    use darklua_core::generator::{LuaGenerator, TokenBasedLuaGenerator};
+
+    let input = "local object = get()\n\nobject.value.count += 1";
+    let resources = darklua_core::Resources::from_memory();
+    let context = darklua_core::rules::ContextBuilder::new(".", &resources, input).build();
+
+    let mut block = darklua_core::Parser::default()
+        .preserve_tokens()
+        .parse(input)
+        .expect("unable to parse input");
+
+    RemoveSpaces::default()
+        .process(&mut block, &context)
+        .expect("rule should succeed");
+    RemoveCompoundAssignment::default()
+        .process(&mut block, &context)
+        .expect("rule should succeed");
+
+    let mut generator = TokenBasedLuaGenerator::new(input);
+    generator.write_block(&block);
+
+    pretty_assertions::assert_eq!(
+        generator.into_string(),
+        "local object=get()\n\ndo local __DARKLUA_VAR=object.value __DARKLUA_VAR.count=__DARKLUA_VAR.count+1 end"
+    );
+}
+
 #[test]
 fn deserialize_from_object_notation() {
     json5::from_str::<Box<dyn Rule>>(
@@ -1,4 +1,4 @@
-use darklua_core::rules::{RemoveContinue, Rule};
+use darklua_core::rules::{RemoveContinue, RemoveSpaces, RemoveUnusedIfBranch, Rule};
 
 test_rule_snapshot!(
     remove_continue,
@@ -117,6 +117,32 @@ test_rule_snapshot!(
     end
     "#
     ),
+    for_continue_with_comment_above(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            -- Skip even numbers
+            continue
+        end
+        print(i)
+    end
+    "#
+    ),
+    for_continue_or_break_with_comments(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            -- Skip even numbers
+            continue
+        elseif i > 7 then
+            -- Stop here
+            break
+        end
+        print(i)
+        -- End of the loop body
+    end
+    "#
+    ),
     for_loop_with_only_continue(
         r#"
     for i = 1, 10 do
@@ -139,6 +165,229 @@ test_rule_snapshot!(
     end
     "#
     ),
+    outer_continue_with_nested_while_in_do(
+        r#"
+    for i = 1, 10 do
+        do
+            while check(i) do
+                if done(i) then
+                    break
+                end
+            end
+        end
+        if i % 2 == 0 then
+            continue
+        end
+        print(i)
+    end
+    "#
+    ),
+    inner_continue_in_while_in_do(
+        r#"
+    for i = 1, 10 do
+        do
+            while check(i) do
+                if skip(i) then
+                    continue
+                end
+                print(i)
+            end
+        end
+        print(i)
+    end
+    "#
+    ),
+    outer_and_inner_continue_with_while_in_do(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            continue
+        end
+        do
+            while check(i) do
+                if skip(i) then
+                    continue
+                end
+                print(i)
+            end
+        end
+        print(i)
+    end
+    "#
+    ),
+    repeat_condition_uses_body_local(
+        r#"
+    repeat
+        local x = f()
+        if x then
+            continue
+        end
+        g(x)
+    until x == nil
+    "#
+    ),
+    repeat_condition_uses_typed_body_locals(
+        r#"
+    repeat
+        local value: number, done: boolean = f()
+        if value > 10 then
+            continue
+        end
+        local done = done or g(value)
+    until done
+    "#
+    ),
+    repeat_condition_uses_body_local_function(
+        r#"
+    repeat
+        local function done() return true end
+        if x then
+            continue
+        end
+        g()
+    until done()
+    "#
+    ),
+    trailing_continue_in_if_statement(
+        r#"
+    for i = 1, 10 do
+        print(i)
+        if i > 5 then
+            continue
+        end
+    end
+    "#
+    ),
+    trailing_continue_in_else_block_inside_do(
+        r#"
+    while check() do
+        do
+            if ready() then
+                run()
+            else
+                continue
+            end
+        end
+    end
+    "#
+    ),
+    continue_without_break_in_nested_branches(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            if i % 3 == 0 then
+                continue
+            end
+            print(i)
+        end
+        print(i)
+    end
+    "#
+    ),
+    continue_and_break_in_nested_branches(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            if i % 3 == 0 then
+                continue
+            end
+            break
+        end
+        print(i)
+    end
+    "#
+    ),
+    continue_in_nested_if_statements_inside_do(
+        r#"
+    while check() do
+        do
+            if a then
+                if b then
+                    continue
+                end
+                print(b)
+            end
+            print(a)
+        end
+        print(check)
+    end
+    "#
+    ),
+    do_continue_followed_by_unreachable_statements(
+        r#"
+    for i = 1, 10 do
+        if skip(i) then
+            do
+                continue
+            end
+            print("never")
+        end
+        print(i)
+    end
+    "#
+    ),
+    trailing_do_continue_followed_by_unreachable_statements(
+        r#"
+    for i = 1, 10 do
+        print(i)
+        do
+            continue
+        end
+        print("never")
+    end
+    "#
+    ),
+    continue_in_else_block(
+        r#"
+    for i = 1, 10 do
+        if a then
+            x()
+        else
+            continue
+        end
+        print(i)
+    end
+    "#
+    ),
+    trailing_continue_in_else_block(
+        r#"
+    for i = 1, 10 do
+        if a then
+            x()
+        else
+            continue
+        end
+    end
+    "#
+    ),
+    continue_in_elseif_and_break_in_else_block(
+        r#"
+    for i = 1, 10 do
+        if a then
+            x()
+        elseif b then
+            continue
+        else
+            break
+        end
+        print(i)
+    end
+    "#
+    ),
+    break_in_elseif_and_continue_in_else_block(
+        r#"
+    while check() do
+        if a then
+            x()
+        elseif b then
+            break
+        else
+            continue
+        end
+        print(a)
+    end
+    "#
+    ),
     for_loop_continue_in_function_statement(
         r#"
     for i = 1, 10 do
@@ -157,6 +406,309 @@ test_rule_snapshot!(
     ),
 );
 
+test_rule_snapshot!(
+    remove_continue_with_goto_strategy,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_continue',
+        strategy: 'goto',
+    }"#,
+    )
+    .unwrap(),
+    // the generated goto statements can only be parsed back with a Lua 5.2 parser
+    idempotence_parser = Some(
+        darklua_core::Parser::default()
+            .with_dialect(darklua_core::LuaDialect::Lua52)
+            .preserve_tokens()
+    ),
+    numeric_for_continue_first_case(
+        r#"
+    for i = 1, 10 do
+        if i == 1 then
+            continue
+        end
+        print(i)
+    end
+    "#
+    ),
+    generic_for_continue_or_break(
+        r#"
+    for key, value in array do
+        if skip(key) then
+            continue
+        elseif stop(key) then
+            break
+        end
+        print(value)
+    end
+    "#
+    ),
+    nested_for_continue_statements(
+        r#"
+    for i = 1, 10 do
+        for j = 1, 10 do
+            if j % 2 == 0 then
+                continue
+            end
+            print(i, j)
+        end
+    end
+    "#
+    ),
+    for_continue_with_comment(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            continue -- Skip even numbers
+        end
+        -- Print odd numbers
+        print(i)
+    end
+    "#
+    ),
+    for_loop_with_only_continue(
+        r#"
+    for i = 1, 10 do
+        continue
+    end
+    "#
+    ),
+    while_continue_with_local(
+        r#"
+    while true do
+        local value = next()
+        if value == nil then
+            continue
+        end
+        print(value)
+    end
+    "#
+    ),
+    while_continue_before_return(
+        r#"
+    while true do
+        if skip() then
+            continue
+        end
+        return value
+    end
+    "#
+    ),
+    repeat_condition_uses_body_local(
+        r#"
+    repeat
+        local x = f()
+        if x then
+            continue
+        end
+        g(x)
+    until x == nil
+    "#
+    ),
+);
+
+test_rule_snapshot!(
+    remove_continue_with_shared_flag_variable,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_continue',
+        share_flag_variable: true,
+    }"#,
+    )
+    .unwrap(),
+    generic_for_continue_or_break(
+        r#"
+    for key, value in array do
+        if skip(key) then
+            continue
+        elseif stop(key) then
+            break
+        end
+        print(value)
+    end
+    "#
+    ),
+    sequential_loops_in_function(
+        r#"
+    local function process(array)
+        for i = 1, #array do
+            if array[i] == nil then
+                continue
+            elseif array[i] == false then
+                break
+            end
+            print(array[i])
+        end
+        while next() do
+            if skip() then
+                continue
+            end
+            if stop() then
+                break
+            end
+        end
+    end
+    "#
+    ),
+    nested_loops_with_continue_and_break(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            continue
+        end
+        for j = 1, 10 do
+            if j % 2 == 0 then
+                continue
+            elseif j > i then
+                break
+            end
+            print(i, j)
+        end
+        if i > 5 then
+            break
+        end
+        print(i)
+    end
+    "#
+    ),
+    loop_without_break_in_function_expression(
+        r#"
+    return function(array)
+        for i = 1, #array do
+            if array[i] == nil then
+                continue
+            end
+            print(array[i])
+        end
+    end
+    "#
+    ),
+    nested_loops_with_return_and_break_in_inner_loop(
+        r#"
+    local function find(matrix, target)
+        for i = 1, #matrix do
+            for j = 1, #matrix[i] do
+                if matrix[i][j] == nil then
+                    continue
+                elseif matrix[i][j] == target then
+                    return i, j
+                elseif matrix[i][j] == false then
+                    break
+                end
+                visit(i, j)
+            end
+            if i > 5 then
+                continue
+            elseif done(i) then
+                break
+            end
+            print(i)
+        end
+    end
+    "#
+    ),
+    shared_flag_does_not_shadow_upvalue(
+        r#"
+    local __DARKLUA_CONTINUE = 0
+    local function run()
+        for i = 1, 10 do
+            if i % 2 == 0 then
+                continue
+            elseif i > 5 then
+                break
+            end
+            __DARKLUA_CONTINUE = __DARKLUA_CONTINUE + i
+        end
+    end
+    "#
+    ),
+);
+
+test_rule!(
+    remove_continue_nested_loops,
+    RemoveContinue::default(),
+    inner_loop_is_converted_before_outer_loop(
+        r#"
+    for i = 1, 10 do
+        for j = 1, 10 do
+            if j > i then
+                break
+            elseif j % 2 == 0 then
+                continue
+            end
+            print(i, j)
+        end
+        if i > 5 then
+            break
+        elseif i % 2 == 0 then
+            continue
+        end
+        print(i)
+    end
+    "#
+    ) => r#"
+    for i = 1, 10 do
+        local __DARKLUA_CONTINUE_1 = false
+        repeat
+            for j = 1, 10 do
+                local __DARKLUA_CONTINUE_2 = false
+                repeat
+                    if j > i then
+                        break
+                    elseif j % 2 == 0 then
+                        __DARKLUA_CONTINUE_2 = true
+                        break
+                    end
+                    print(i, j)
+                    __DARKLUA_CONTINUE_2 = true
+                until true
+                if not __DARKLUA_CONTINUE_2 then
+                    break
+                end
+            end
+            if i > 5 then
+                break
+            elseif i % 2 == 0 then
+                __DARKLUA_CONTINUE_1 = true
+                break
+            end
+            print(i)
+            __DARKLUA_CONTINUE_1 = true
+        until true
+        if not __DARKLUA_CONTINUE_1 then
+            break
+        end
+    end
+    "#,
+);
+
+test_rule_snapshot!(
+    remove_continue_with_loop_kinds,
+    json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_continue',
+        loop_kinds: ['while', 'repeat'],
+    }"#,
+    )
+    .unwrap(),
+    only_while_loop_is_converted(
+        r#"
+    for i = 1, 10 do
+        if i % 2 == 0 then
+            continue
+        end
+        while check(i) do
+            if skip(i) then
+                continue
+            end
+            print(i)
+        end
+        print(i)
+    end
+    "#
+    ),
+);
+
 test_rule_without_effects!(
     RemoveContinue::default(),
     numeric_for_loop(
@@ -225,6 +777,26 @@ test_rule_without_effects!(
     ),
 );
 
+#[test]
+fn serialize_and_deserialize_configured_rule() {
+    let rule = json5::from_str::<Box<dyn Rule>>(
+        r#"{
+        rule: 'remove_continue',
+        strategy: 'goto',
+        loop_kinds: ['numeric_for', 'while'],
+    }"#,
+    )
+    .unwrap();
+
+    let serialized = serde_json::to_string(&rule).unwrap();
+    let round_trip = json5::from_str::<Box<dyn Rule>>(&serialized).unwrap();
+
+    pretty_assertions::assert_eq!(
+        round_trip.serialize_to_properties(),
+        rule.serialize_to_properties()
+    );
+}
+
 #[test]
 fn deserialize_from_object_notation() {
     json5::from_str::<Box<dyn Rule>>(
@@ -234,3 +806,158 @@ fn deserialize_from_object_notation() {
     )
     .unwrap();
 }
+
+#[test]
+fn remove_continue_after_remove_unused_if_branch() {
+    let input = r#"
+    while check() do
+        if skip() then
+            continue
+        end
+        if true then
+            continue
+        end
+        print("never")
+    end
+    "#;
+    let resources = darklua_core::Resources::from_memory();
+    let context = darklua_core::rules::ContextBuilder::new(".", &resources, input).build();
+
+    let mut block = crate::utils::parse_input(input);
+
+    // leaves `do continue end` followed by unreachable statements
+    RemoveUnusedIfBranch::default()
+        .process(&mut block, &context)
+        .expect("rule should succeed");
+    RemoveContinue::default()
+        .process(&mut block, &context)
+        .expect("rule should succeed");
+
+    pretty_assertions::assert_eq!(
+        block,
+        crate::utils::parse_input(
+            r#"
+    while check() do
+        repeat
+            if skip() then
+                break
+            end
+            do end
+        until true
+    end
+    "#
+        )
+    );
+}
+
+#[test]
+fn remove_continue_is_idempotent() {
+    let rules: Vec<Box<dyn Rule>> = vec![
+        Box::new(RemoveContinue::default()),
+        json5::from_str("{ rule: 'remove_continue', share_flag_variable: true }").unwrap(),
+        json5::from_str("{ rule: 'remove_continue', strategy: 'goto' }").unwrap(),
+    ];
+
+    let input = r#"
+    for i = 1, 10 do
+        while check(i) do
+            if skip(i) then
+                continue
+            elseif stop(i) then
+                break
+            end
+            print(i)
+        end
+        repeat
+            local value = next(i)
+            if value then
+                continue
+            end
+        until value == nil
+        if i % 2 == 0 then
+            continue
+        end
+        print(i)
+    end
+    "#;
+    let resources = darklua_core::Resources::from_memory();
+    let context = darklua_core::rules::ContextBuilder::new(".", &resources, input).build();
+
+    for rule in rules {
+        let mut block = crate::utils::parse_input(input);
+        rule.process(&mut block, &context)
+            .expect("rule should succeed");
+
+        let expected = block.clone();
+
+        rule.process(&mut block, &context)
+            .expect("rule should succeed");
+
+        pretty_assertions::assert_eq!(block, expected);
+    }
+}
+
+fn retain_lines_after_remove_spaces(rule: &dyn Rule, input: &str) -> String {
+    use darklua_core::generator::{LuaGenerator, TokenBasedLuaGenerator};
+
+    let resources = darklua_core::Resources::from_memory();
+    let context = darklua_core::rules::ContextBuilder::new(".", &resources, input).build();
+
+    let mut block = darklua_core::Parser::default()
+        .preserve_tokens()
+        .parse(input)
+        .expect("unable to parse input");
+
+    // without whitespaces, the generator only relies on the line of each token
+    RemoveSpaces::default()
+        .process(&mut block, &context)
+        .expect("rule should succeed");
+    rule.process(&mut block, &context)
+        .expect("rule should succeed");
+
+    let mut generator = TokenBasedLuaGenerator::new(input);
+    generator.write_block(&block);
+    generator.into_string()
+}
+
+#[test]
+fn remove_continue_places_injected_statements_on_loop_line() {
+    let input = r#"local total = 0
+for i = 1, 10 do
+    if i % 2 == 0 then continue elseif i > 7 then break end
+    total += i
+end
+return total
+"#;
+
+    pretty_assertions::assert_eq!(
+        retain_lines_after_remove_spaces(&RemoveContinue::default(), input),
+        "local total=0\n\
+        for i=1,10 do local __DARKLUA_CONTINUE_1=false repeat\n\
+        if i%2==0 then __DARKLUA_CONTINUE_1=true break elseif i>7 then break end\n\
+        total+=i __DARKLUA_CONTINUE_1=true until true if not __DARKLUA_CONTINUE_1 then break end\n\
+        end\n\
+        return total"
+    );
+}
+
+#[test]
+fn remove_continue_with_goto_places_do_block_on_loop_line() {
+    let rule: Box<dyn Rule> =
+        json5::from_str("{ rule: 'remove_continue', strategy: 'goto' }").unwrap();
+    let input = r#"while check() do
+    local value = next()
+    if value then continue end
+    print(value)
+end
+"#;
+
+    pretty_assertions::assert_eq!(
+        retain_lines_after_remove_spaces(rule.as_ref(), input),
+        "while check()do do\n\
+        local value=next()\n\
+        if value then goto __DARKLUA_CONTINUE_1 end\n\
+        print(value)end::__DARKLUA_CONTINUE_1::\n\
+        end"
+    );
+}
@@ -9,6 +9,7 @@ test_rule!(
     falsy_branch_with_else_block_converts_to_do("if false then else return end") => "do return end",
     keep_branch_and_remove_empty_else("if condition then return else end") => "if condition then return end",
     one_truthy_branch_remove_else_block("if true then break else end") => "do break end",
+    non_utf8_string_branch_is_kept_alone(r#"if "\255" then break else return end"#) => "do break end",
     remove_falsy_elseif_branch("if foo then break elseif false then end") => "if foo then break end",
     remove_falsy_elseif_branch_and_empty_else("if foo then break elseif false then else end") => "if foo then break end",
     remove_branch_after_truthy_branch("if foo then break elseif true then return elseif foo then end")